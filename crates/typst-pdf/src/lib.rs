@@ -18,7 +18,8 @@ use base64::Engine;
 use ecow::{eco_format, EcoString};
 use pdf_writer::types::Direction;
 use pdf_writer::{Finish, Name, Pdf, Ref, TextStr};
-use typst::foundations::Datetime;
+use typst::foundations::{Datetime, Label};
+use typst::introspection::Location;
 use typst::layout::{Abs, Dir, Em, Transform};
 use typst::model::Document;
 use typst::text::{Font, Lang};
@@ -30,7 +31,7 @@ use crate::color::ColorSpaces;
 use crate::extg::ExtGState;
 use crate::gradient::PdfGradient;
 use crate::image::EncodedImage;
-use crate::page::Page;
+use crate::page::{LinkAction, Page};
 use crate::pattern::PdfPattern;
 
 /// Export a document into a PDF file.
@@ -55,6 +56,7 @@ pub fn pdf(
 ) -> Vec<u8> {
     let mut ctx = PdfContext::new(document);
     page::construct_pages(&mut ctx, &document.pages);
+    page::write_named_destinations(&mut ctx);
     font::write_fonts(&mut ctx);
     image::write_images(&mut ctx);
     gradient::write_gradients(&mut ctx);
@@ -115,6 +117,12 @@ struct PdfContext<'a> {
     pattern_map: Remapper<PdfPattern>,
     /// Deduplicates external graphics states used across the document.
     extg_map: Remapper<ExtGState>,
+    /// The IDs of link actions shared by multiple annotations.
+    link_actions: HashMap<LinkAction, Ref>,
+    /// The labels of the elements that have a named destination.
+    loc_to_dest: HashMap<Location, Label>,
+    /// The ID of the named destinations dictionary, if there is one.
+    dests_ref: Option<Ref>,
 }
 
 impl<'a> PdfContext<'a> {
@@ -142,6 +150,9 @@ impl<'a> PdfContext<'a> {
             gradient_map: Remapper::new(),
             pattern_map: Remapper::new(),
             extg_map: Remapper::new(),
+            link_actions: HashMap::new(),
+            loc_to_dest: HashMap::new(),
+            dests_ref: None,
         }
     }
 }
@@ -271,6 +282,10 @@ fn write_catalog(ctx: &mut PdfContext, ident: Option<&str>, timestamp: Option<Da
         catalog.outlines(outline_root_id);
     }
 
+    if let Some(dests_ref) = ctx.dests_ref {
+        catalog.pair(Name(b"Dests"), dests_ref);
+    }
+
     if let Some(lang) = lang {
         catalog.lang(TextStr(lang.as_str()));
     }
@@ -420,3 +435,79 @@ fn transform_to_array(ts: Transform) -> [f32; 6] {
         ts.ty.to_f32(),
     ]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use typst::foundations::{Content, NativeElement};
+    use typst::introspection::{Introspector, Locator, Meta};
+    use typst::layout::{Frame, FrameItem, Point, Size};
+    use typst::model::{Destination, HeadingElem};
+
+    /// A labelled heading at the given location.
+    fn heading(loc: Location, label: &str) -> Meta {
+        let mut heading = HeadingElem::new(Content::empty()).pack();
+        heading.set_location(loc);
+        Meta::Elem(heading.labelled(Label::new(label)))
+    }
+
+    /// A document with a link to one of two labelled headings.
+    fn document() -> Document {
+        let mut locator = Locator::new();
+        let loc = locator.locate(1);
+        let unlinked = locator.locate(2);
+
+        let mut frame = Frame::soft(Size::splat(Abs::pt(100.0)));
+        let meta = heading(loc, "intro");
+        frame.push(Point::with_y(Abs::pt(50.0)), FrameItem::Meta(meta, Size::zero()));
+        let meta = heading(unlinked, "outro");
+        frame.push(Point::with_y(Abs::pt(80.0)), FrameItem::Meta(meta, Size::zero()));
+        let link = Meta::Link(Destination::Location(loc), Abs::zero());
+        frame.push(Point::zero(), FrameItem::Meta(link, Size::splat(Abs::pt(10.0))));
+
+        let mut introspector = Introspector::default();
+        introspector.rebuild(std::slice::from_ref(&frame));
+        Document {
+            pages: vec![frame],
+            introspector,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_named_destinations_are_stable() {
+        let first = pdf(&document(), Some("test"), None);
+        let second = pdf(&document(), Some("test"), None);
+        assert_eq!(first, second);
+
+        let contains = |needle: &[u8]| first.windows(needle.len()).any(|w| w == needle);
+        assert!(contains(b"/Dests"));
+        assert!(contains(b"/D /intro"));
+        assert!(!contains(b"/outro"));
+    }
+
+    #[test]
+    fn test_fragment_leads_to_first_of_duplicate_labels() {
+        let mut locator = Locator::new();
+        let mut frame = Frame::soft(Size::splat(Abs::pt(100.0)));
+        for y in [50.0, 80.0] {
+            let meta = heading(locator.locate(1), "dup");
+            frame.push(Point::with_y(Abs::pt(y)), FrameItem::Meta(meta, Size::zero()));
+        }
+        let link = Meta::Link(Destination::Url("#dup".into()), Abs::zero());
+        frame.push(Point::zero(), FrameItem::Meta(link, Size::splat(Abs::pt(10.0))));
+
+        let mut introspector = Introspector::default();
+        introspector.rebuild(std::slice::from_ref(&frame));
+        let document = Document {
+            pages: vec![frame],
+            introspector,
+            ..Default::default()
+        };
+
+        let buffer = pdf(&document, Some("test"), None);
+        let contains = |needle: &[u8]| buffer.windows(needle.len()).any(|w| w == needle);
+        assert!(contains(b"/D /dup"));
+    }
+}
@@ -2,9 +2,9 @@ use std::num::NonZeroUsize;
 
 use pdf_writer::{Finish, Ref, TextStr};
 use typst::foundations::{Content, NativeElement, Smart};
-use typst::layout::Abs;
 use typst::model::HeadingElem;
 
+use crate::page::jump_point;
 use crate::{AbsExt, PdfContext};
 
 /// Construct the outline for the document.
@@ -164,10 +164,10 @@ fn write_outline_item(
     let pos = ctx.document.introspector.position(loc);
     let index = pos.page.get() - 1;
     if let Some(page) = ctx.pages.get(index) {
-        let y = (pos.point.y - Abs::pt(10.0)).max(Abs::zero());
+        let point = jump_point(pos, page.size.y);
         outline.dest().page(ctx.page_refs[index]).xyz(
-            pos.point.x.to_f32(),
-            (page.size.y - y).to_f32(),
+            point.x.to_f32(),
+            point.y.to_f32(),
             None,
         );
     }
@@ -1,4 +1,5 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
 use std::num::NonZeroUsize;
 
 use ecow::{eco_format, EcoString};
@@ -6,12 +7,13 @@ use pdf_writer::types::{
     ActionType, AnnotationFlags, AnnotationType, ColorSpaceOperand, LineCapStyle,
     LineJoinStyle, NumberingStyle,
 };
-use pdf_writer::writers::PageLabel;
+use pdf_writer::writers::{Action, Destination as DestinationWriter, PageLabel};
 use pdf_writer::{Content, Filter, Finish, Name, Rect, Ref, Str, TextStr};
-use typst::introspection::Meta;
+use typst::foundations::{Label, Selector};
+use typst::introspection::{Location, Meta};
 use typst::layout::{
-    Abs, Em, Frame, FrameItem, GroupItem, PdfPageLabel, PdfPageLabelStyle, Point, Ratio,
-    Size, Transform,
+    Abs, Em, Frame, FrameItem, GroupItem, PdfPageLabel, PdfPageLabelStyle, Point,
+    Position, Ratio, Size, Transform,
 };
 use typst::model::Destination;
 use typst::text::{Font, TextItem};
@@ -50,6 +52,7 @@ pub(crate) fn construct_page(ctx: &mut PdfContext, frame: &Frame) -> (Ref, Page)
         saves: vec![],
         bottom: 0.0,
         links: vec![],
+        linked: HashSet::new(),
         resources: HashMap::default(),
     };
 
@@ -84,6 +87,8 @@ pub(crate) fn construct_page(ctx: &mut PdfContext, frame: &Frame) -> (Ref, Page)
 
 /// Write the page tree.
 pub(crate) fn write_page_tree(ctx: &mut PdfContext) {
+    write_shared_link_actions(ctx);
+
     for i in 0..ctx.pages.len() {
         write_page(ctx, i);
     }
@@ -140,11 +145,238 @@ pub(crate) fn write_page_tree(ctx: &mut PdfContext) {
     ctx.colors.write_functions(&mut ctx.pdf);
 }
 
+/// The action of a link annotation.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) enum LinkAction {
+    /// Open a URL. Relative URLs are resolved by the viewer, against the
+    /// document's base URI if it has one.
+    Uri(EcoString),
+    /// Jump to a destination in another PDF file, relative to this one.
+    GoToR { file: EcoString, dest: Option<RemoteDest> },
+    /// Jump to a point on a page, in PDF coordinates.
+    GoTo { page: usize, x: Abs, y: Abs },
+    /// Jump to the named destination of a labelled element.
+    Named(Label),
+}
+
+/// A destination in another PDF file, taken from the fragment of a link to it.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) enum RemoteDest {
+    /// A zero-based page index, from a fragment like `#page=2`.
+    Page(usize),
+    /// A named destination, from a fragment like `#nameddest=intro` or
+    /// `#intro`.
+    Named(EcoString),
+}
+
+impl RemoteDest {
+    /// Parse the fragment of a link to another PDF file.
+    fn parse(fragment: &str) -> Option<Self> {
+        if let Some(page) = fragment.strip_prefix("page=") {
+            let page = page.parse::<NonZeroUsize>().ok()?;
+            return Some(Self::Page(page.get() - 1));
+        }
+        let name = fragment.strip_prefix("nameddest=").unwrap_or(fragment);
+        (!name.is_empty()).then(|| Self::Named(name.into()))
+    }
+}
+
+/// Write the named destinations of labelled elements that links lead to.
+///
+/// The destinations are named after the labels instead of being derived from
+/// the elements' locations. This way, they stay the same across compilations
+/// of an unchanged document and can be linked to from outside of it, e.g.
+/// with `#nameddest=intro`. If multiple elements have the same label, the
+/// first one is the destination.
+pub(crate) fn write_named_destinations(ctx: &mut PdfContext) {
+    let introspector = &ctx.document.introspector;
+    let linked: HashSet<Label> = ctx
+        .pages
+        .iter()
+        .flat_map(|page| &page.links)
+        .filter_map(|(dest, _)| match dest {
+            Destination::Location(loc) => {
+                introspector.query_first(&Selector::Location(*loc))?.label()
+            }
+            Destination::Url(_) => dest.fragment().map(Label::new),
+            Destination::Position(_) => None,
+        })
+        .collect();
+
+    let mut seen = HashSet::new();
+    let mut targets: Vec<_> = introspector
+        .all()
+        .filter_map(|elem| elem.location().zip(elem.label()))
+        .filter(|(_, label)| linked.contains(label))
+        .filter(|&(_, label)| seen.insert(label))
+        .collect();
+    if targets.is_empty() {
+        return;
+    }
+
+    // Sort by name so that the dictionary doesn't depend on the order in
+    // which the elements were laid out.
+    targets.sort_by_key(|&(_, label)| label.as_str());
+
+    let dests_ref = ctx.alloc.bump();
+    let mut dests = ctx.pdf.indirect(dests_ref).dict();
+    for (loc, label) in targets {
+        let pos = ctx.document.introspector.position(loc);
+        let index = pos.page.get() - 1;
+        let Some(page) = ctx.pages.get(index) else { continue };
+        let point = jump_point(pos, page.size.y);
+        dests
+            .insert(Name(label.as_str().as_bytes()))
+            .start::<DestinationWriter>()
+            .page(ctx.page_refs[index])
+            .xyz(point.x.to_f32(), point.y.to_f32(), None);
+        ctx.loc_to_dest.insert(loc, label);
+    }
+    dests.finish();
+    ctx.dests_ref = Some(dests_ref);
+}
+
+/// The point that a link to the position jumps to, in the PDF coordinate
+/// system of a page with the given height. It lies a bit above the position,
+/// so that the target isn't right at the top edge of the viewer.
+pub(crate) fn jump_point(pos: Position, height: Abs) -> Point {
+    let y = (pos.point.y - Abs::pt(10.0)).max(Abs::zero());
+    Point::new(pos.point.x, height - y)
+}
+
+/// Determine the action for a link destination, if its target exists.
+fn link_action(ctx: &PdfContext, dest: &Destination) -> Option<LinkAction> {
+    let pos = match dest {
+        // A bare fragment leads to the first element with the label of that
+        // name, just like its named destination.
+        Destination::Url(_) if dest.fragment().is_some() => {
+            let selector = Selector::Label(Label::new(dest.fragment()?));
+            let elems = ctx.document.introspector.query(&selector);
+            return location_action(ctx, elems.first()?.location()?);
+        }
+        Destination::Url(uri) if dest.is_relative() => return Some(relative_action(uri)),
+        Destination::Url(uri) => return Some(LinkAction::Uri(uri.clone())),
+        Destination::Position(pos) => *pos,
+        Destination::Location(loc) => return location_action(ctx, *loc),
+    };
+
+    position_action(ctx, pos)
+}
+
+/// Determine the action for a relative URL. Links to other PDF files jump
+/// right to the destination named in their fragment, all others are left to
+/// the viewer to resolve.
+fn relative_action(uri: &str) -> LinkAction {
+    let (rest, fragment) = uri.split_once('#').unwrap_or((uri, ""));
+    let path = rest.split('?').next().unwrap_or(rest);
+    let is_pdf = std::path::Path::new(path)
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("pdf"));
+    if !is_pdf {
+        return LinkAction::Uri(uri.into());
+    }
+
+    LinkAction::GoToR {
+        file: path.into(),
+        dest: RemoteDest::parse(fragment),
+    }
+}
+
+/// Determine the action for a link to a location. Labelled elements are
+/// linked through their named destination.
+fn location_action(ctx: &PdfContext, loc: Location) -> Option<LinkAction> {
+    match ctx.loc_to_dest.get(&loc) {
+        Some(&label) => Some(LinkAction::Named(label)),
+        None => position_action(ctx, ctx.document.introspector.position(loc)),
+    }
+}
+
+/// Determine the action for a link to a position.
+fn position_action(ctx: &PdfContext, pos: Position) -> Option<LinkAction> {
+    let index = pos.page.get() - 1;
+    let page = ctx.pages.get(index)?;
+    let point = jump_point(pos, page.size.y);
+    Some(LinkAction::GoTo { page: index, x: point.x, y: point.y })
+}
+
+/// Write the actions of links that occur more than once in the document as
+/// indirect objects, so that their annotations can refer to them.
+fn write_shared_link_actions(ctx: &mut PdfContext) {
+    let mut actions = vec![];
+    let mut counts = HashMap::new();
+    for page in &ctx.pages {
+        for (dest, _) in &page.links {
+            let Some(action) = link_action(ctx, dest) else { continue };
+            let count = counts.entry(action.clone()).or_insert(0);
+            if *count == 0 {
+                actions.push(action);
+            }
+            *count += 1;
+        }
+    }
+
+    for action in actions {
+        if counts[&action] < 2 {
+            continue;
+        }
+
+        let id = ctx.alloc.bump();
+        let writer = ctx.pdf.indirect(id).start::<Action>();
+        write_link_action(writer, &action, &ctx.page_refs);
+        ctx.link_actions.insert(action, id);
+    }
+}
+
+/// Write a link action.
+fn write_link_action(mut writer: Action, action: &LinkAction, page_refs: &[Ref]) {
+    match action {
+        LinkAction::Uri(uri) => {
+            writer.action_type(ActionType::Uri).uri(Str(uri.as_bytes()));
+        }
+        LinkAction::GoToR { file, dest } => {
+            writer
+                .action_type(ActionType::RemoteGoTo)
+                .pair(Name(b"F"), Str(file.as_bytes()));
+            match dest {
+                Some(RemoteDest::Page(page)) => {
+                    let page = *page as i32;
+                    writer.insert(Name(b"D")).array().item(page).item(Name(b"Fit"));
+                }
+                Some(RemoteDest::Named(name)) => {
+                    writer.pair(Name(b"D"), Name(name.as_bytes()));
+                }
+                // Without a destination, the file opens at its first page.
+                None => {
+                    writer.insert(Name(b"D")).array().item(0).item(Name(b"Fit"));
+                }
+            }
+        }
+        LinkAction::GoTo { page, x, y } => {
+            writer
+                .action_type(ActionType::GoTo)
+                .destination()
+                .page(page_refs[*page])
+                .xyz(x.to_f32(), y.to_f32(), None);
+        }
+        LinkAction::Named(label) => {
+            writer
+                .action_type(ActionType::GoTo)
+                .pair(Name(b"D"), Name(label.as_str().as_bytes()));
+        }
+    }
+}
+
 /// Write a page tree node.
 fn write_page(ctx: &mut PdfContext, i: usize) {
     let page = &ctx.pages[i];
     let content_id = ctx.alloc.bump();
 
+    let actions: Vec<_> = page
+        .links
+        .iter()
+        .filter_map(|(dest, area)| Some((link_action(ctx, dest)?, area)))
+        .collect();
+
     let mut page_writer = ctx.pdf.page(page.id);
     page_writer.parent(ctx.page_tree_ref);
 
@@ -163,33 +395,25 @@ fn write_page(ctx: &mut PdfContext, i: usize) {
             .srgb();
     }
 
+    // Viewers favor annotations written later where they overlap, e.g. due
+    // to the hit padding of links. Writing them in reverse lets the link
+    // which comes first in the document win instead.
     let mut annotations = page_writer.annotations();
-    for (dest, rect) in &page.links {
+    for (action, area) in actions.into_iter().rev() {
         let mut annotation = annotations.push();
-        annotation.subtype(AnnotationType::Link).rect(*rect);
+        annotation.subtype(AnnotationType::Link).rect(area.rect);
+        if let Some(quad) = area.quad {
+            annotation.quad_points(quad);
+        }
         annotation.border(0.0, 0.0, 0.0, None).flags(AnnotationFlags::PRINT);
 
-        let pos = match dest {
-            Destination::Url(uri) => {
-                annotation
-                    .action()
-                    .action_type(ActionType::Uri)
-                    .uri(Str(uri.as_bytes()));
-                continue;
+        // Links with the same target throughout the document share a single
+        // action object instead of each repeating it inline.
+        match ctx.link_actions.get(&action) {
+            Some(&id) => {
+                annotation.pair(Name(b"A"), id);
             }
-            Destination::Position(pos) => *pos,
-            Destination::Location(loc) => ctx.document.introspector.position(*loc),
-        };
-
-        let index = pos.page.get() - 1;
-        let y = (pos.point.y - Abs::pt(10.0)).max(Abs::zero());
-        if let Some(page) = ctx.pages.get(index) {
-            annotation
-                .action()
-                .action_type(ActionType::GoTo)
-                .destination()
-                .page(ctx.page_refs[index])
-                .xyz(pos.point.x.to_f32(), (page.size.y - y).to_f32(), None);
+            None => write_link_action(annotation.action(), &action, &ctx.page_refs),
         }
     }
 
@@ -260,7 +484,7 @@ pub struct Page {
     /// Whether the page uses opacities.
     pub uses_opacities: bool,
     /// Links in the PDF coordinate system.
-    pub links: Vec<(Destination, Rect)>,
+    pub links: Vec<(Destination, LinkArea)>,
     /// The page's PDF label.
     pub label: Option<PdfPageLabel>,
     /// The page's used resources
@@ -332,7 +556,10 @@ pub struct PageContext<'a, 'b> {
     saves: Vec<State>,
     bottom: f32,
     uses_opacities: bool,
-    links: Vec<(Destination, Rect)>,
+    links: Vec<(Destination, LinkArea)>,
+    /// The canonical destinations and areas of the links so far, to merge
+    /// equivalent ones.
+    linked: HashSet<(Destination, LinkArea)>,
     /// Keep track of the resources being used in the page.
     pub resources: HashMap<PageResource, usize>,
 }
@@ -483,7 +710,7 @@ impl PageContext<'_, '_> {
 
     fn set_fill(&mut self, fill: &Paint, on_text: bool, transforms: Transforms) {
         if self.state.fill.as_ref() != Some(fill)
-            || matches!(self.state.fill, Some(Paint::Gradient(_)))
+            || matches!(self.state.fill, Some(Paint::Gradient(_) | Paint::Pattern(_)))
         {
             fill.set_as_fill(self, on_text, transforms);
             self.state.fill = Some(fill.clone());
@@ -510,7 +737,7 @@ impl PageContext<'_, '_> {
         if self.state.stroke.as_ref() != Some(stroke)
             || matches!(
                 self.state.stroke.as_ref().map(|s| &s.paint),
-                Some(Paint::Gradient(_))
+                Some(Paint::Gradient(_) | Paint::Pattern(_))
             )
         {
             let FixedStroke { paint, thickness, cap, join, dash, miter_limit } = stroke;
@@ -564,7 +791,7 @@ fn write_frame(ctx: &mut PageContext, frame: &Frame) {
             FrameItem::Shape(shape, _) => write_shape(ctx, pos, shape),
             FrameItem::Image(image, size, _) => write_image(ctx, x, y, image, *size),
             FrameItem::Meta(meta, size) => match meta {
-                Meta::Link(dest) => write_link(ctx, pos, dest, *size),
+                Meta::Link(dest, _) => write_link(ctx, pos, dest, *size),
                 Meta::Elem(_) => {}
                 Meta::Hide => {}
                 Meta::PageNumbering(_) => {}
@@ -783,32 +1010,86 @@ fn write_image(ctx: &mut PageContext, x: f32, y: f32, image: &Image, size: Size)
 
 /// Save a link for later writing in the annotations dictionary.
 fn write_link(ctx: &mut PageContext, pos: Point, dest: &Destination, size: Size) {
-    let mut min_x = Abs::inf();
-    let mut min_y = Abs::inf();
-    let mut max_x = -Abs::inf();
-    let mut max_y = -Abs::inf();
-
-    // Compute the bounding box of the transformed link.
-    for point in [
-        pos,
-        pos + Point::with_x(size.x),
-        pos + Point::with_y(size.y),
-        pos + size.to_point(),
-    ] {
-        let t = point.transform(ctx.state.transform);
-        min_x.set_min(t.x);
-        min_y.set_min(t.y);
-        max_x.set_max(t.x);
-        max_y.set_max(t.y);
-    }
-
-    let x1 = min_x.to_f32();
-    let x2 = max_x.to_f32();
-    let y1 = max_y.to_f32();
-    let y2 = min_y.to_f32();
-    let rect = Rect::new(x1, y1, x2, y2);
-
-    ctx.links.push((dest.clone(), rect));
+    let area = LinkArea::new(pos, size, ctx.state.transform);
+
+    // Merge links with equivalent destinations covering the same area, e.g.
+    // from nested links, into a single annotation.
+    if ctx.linked.insert((dest.canonical(), area)) {
+        ctx.links.push((dest.clone(), area));
+    }
+}
+
+/// The clickable area of a link in the PDF coordinate system.
+///
+/// Areas compare and hash by the bits of their coordinates.
+#[derive(Debug, Copy, Clone)]
+pub struct LinkArea {
+    /// The bounding box of the area.
+    pub rect: Rect,
+    /// The corners of the area if it is rotated or skewed, so that viewers
+    /// supporting it only react to clicks on the link's body. They start at
+    /// the body's bottom left and go counterclockwise.
+    pub quad: Option<[f32; 8]>,
+}
+
+impl LinkArea {
+    /// The area of a link of the given size at the given position.
+    fn new(pos: Point, size: Size, ts: Transform) -> Self {
+        let corners = [
+            pos + Point::with_y(size.y),
+            pos + size.to_point(),
+            pos + Point::with_x(size.x),
+            pos,
+        ]
+        .map(|point| point.transform(ts));
+
+        // Compute the bounding box of the transformed link.
+        let mut min_x = Abs::inf();
+        let mut min_y = Abs::inf();
+        let mut max_x = -Abs::inf();
+        let mut max_y = -Abs::inf();
+        for t in corners {
+            min_x.set_min(t.x);
+            min_y.set_min(t.y);
+            max_x.set_max(t.x);
+            max_y.set_max(t.y);
+        }
+
+        let x1 = min_x.to_f32();
+        let x2 = max_x.to_f32();
+        let y1 = max_y.to_f32();
+        let y2 = min_y.to_f32();
+        let rect = Rect::new(x1, y1, x2, y2);
+
+        // The bounding box is exact unless the link is rotated or skewed.
+        let quad = (!ts.kx.is_zero() || !ts.ky.is_zero()).then(|| {
+            let [a, b, c, d] = corners.map(|p| (p.x.to_f32(), p.y.to_f32()));
+            [a.0, a.1, b.0, b.1, c.0, c.1, d.0, d.1]
+        });
+
+        Self { rect, quad }
+    }
+
+    /// The bits of the area's coordinates.
+    fn bits(&self) -> ([u32; 4], Option<[u32; 8]>) {
+        let Rect { x1, y1, x2, y2 } = self.rect;
+        let rect = [x1, y1, x2, y2].map(f32::to_bits);
+        (rect, self.quad.map(|quad| quad.map(f32::to_bits)))
+    }
+}
+
+impl PartialEq for LinkArea {
+    fn eq(&self, other: &Self) -> bool {
+        self.bits() == other.bits()
+    }
+}
+
+impl Eq for LinkArea {}
+
+impl Hash for LinkArea {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.bits().hash(state);
+    }
 }
 
 fn to_pdf_line_cap(cap: LineCap) -> LineCapStyle {
@@ -836,3 +1117,108 @@ fn to_pdf_numbering_style(style: PdfPageLabelStyle) -> NumberingStyle {
         PdfPageLabelStyle::UpperAlpha => NumberingStyle::UpperAlpha,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use typst::introspection::Introspector;
+    use typst::layout::Angle;
+    use typst::model::Document;
+
+    /// A page with links to the given URLs, each at its own position.
+    fn page_with_links(urls: &[&str]) -> Frame {
+        let mut frame = Frame::soft(Size::splat(Abs::pt(100.0)));
+        for (i, url) in urls.iter().enumerate() {
+            let link = Meta::Link(Destination::Url((*url).into()), Abs::zero());
+            let pos = Point::with_y(Abs::pt(10.0 * i as f64));
+            frame.push(pos, FrameItem::Meta(link, Size::splat(Abs::pt(5.0))));
+        }
+        frame
+    }
+
+    /// Count the occurrences of a byte string.
+    fn count(haystack: &[u8], needle: &[u8]) -> usize {
+        haystack.windows(needle.len()).filter(|w| *w == needle).count()
+    }
+
+    #[test]
+    fn test_link_area_follows_rotation() {
+        let size = Size::new(Abs::pt(20.0), Abs::pt(10.0));
+        let ts = Transform::rotate(Angle::deg(90.0));
+        let area = LinkArea::new(Point::zero(), size, ts);
+
+        let rounded = |v: f32| v.round() as i32;
+        let rect = area.rect;
+        let bounds = [rect.x1, rect.y1, rect.x2, rect.y2].map(rounded);
+        assert_eq!(bounds, [-10, 20, 0, 0]);
+
+        let quad = area.quad.expect("rotated link should have corners").map(rounded);
+        assert_eq!(quad, [-10, 0, -10, 20, 0, 20, 0, 0]);
+    }
+
+    #[test]
+    fn test_link_area_without_rotation_has_no_corners() {
+        let size = Size::new(Abs::pt(20.0), Abs::pt(10.0));
+        let ts = Transform::translate(Abs::pt(5.0), Abs::pt(5.0));
+        let area = LinkArea::new(Point::zero(), size, ts);
+        assert_eq!(area.quad, None);
+        assert_eq!(area.rect, Rect::new(5.0, 15.0, 25.0, 5.0));
+    }
+
+    #[test]
+    fn test_repeated_link_actions_are_shared() {
+        let pages = vec![
+            page_with_links(&["https://typst.app", "https://typst.app", "https://a.b"]),
+            page_with_links(&["https://typst.app"]),
+        ];
+        let mut introspector = Introspector::default();
+        introspector.rebuild(&pages);
+        let document = Document { pages, introspector, ..Default::default() };
+        let pdf = crate::pdf(&document, Some("test"), None);
+
+        // The repeated link's action is written once as an indirect object
+        // that all three of its annotations refer to. The other link's action
+        // is written inline.
+        assert_eq!(count(&pdf, b"/Subtype /Link"), 4);
+        assert_eq!(count(&pdf, b"/S /URI"), 2);
+        assert_eq!(count(&pdf, b"(https://typst.app)"), 1);
+        assert_eq!(count(&pdf, b"(https://a.b)"), 1);
+
+        let refs: Vec<_> = pdf
+            .split(|&b| b == b'\n')
+            .filter_map(|line| {
+                let line = std::str::from_utf8(line).ok()?.trim();
+                line.strip_prefix("/A ")?.strip_suffix(" 0 R")?.parse::<i32>().ok()
+            })
+            .collect();
+        assert_eq!(refs.len(), 3);
+        assert!(refs.iter().all(|&id| id == refs[0]));
+    }
+
+    #[test]
+    fn test_relative_action() {
+        let uri = |s: &str| LinkAction::Uri(s.into());
+        let remote = |dest| LinkAction::GoToR { file: "manual.pdf".into(), dest };
+        assert_eq!(relative_action("about.html#team"), uri("about.html#team"));
+        assert_eq!(relative_action("docs/?q=pdf"), uri("docs/?q=pdf"));
+        assert_eq!(relative_action("manual.pdf"), remote(None));
+        assert_eq!(
+            relative_action("manual.pdf#page=2"),
+            remote(Some(RemoteDest::Page(1))),
+        );
+        assert_eq!(relative_action("manual.pdf#page=0"), remote(None));
+        assert_eq!(
+            relative_action("manual.pdf#nameddest=intro"),
+            remote(Some(RemoteDest::Named("intro".into()))),
+        );
+        assert_eq!(
+            relative_action("manual.pdf#intro"),
+            remote(Some(RemoteDest::Named("intro".into()))),
+        );
+        assert_eq!(
+            relative_action("old/Manual.PDF?v=2"),
+            LinkAction::GoToR { file: "old/Manual.PDF".into(), dest: None },
+        );
+    }
+}
@@ -663,8 +663,16 @@ pub fn link_prefix(text: &str) -> (&str, bool) {
                 brackets.push(b'(');
                 true
             }
-            ']' => brackets.pop() == Some(b'['),
-            ')' => brackets.pop() == Some(b'('),
+            // Only include closing brackets that match an opening bracket in
+            // the URL. Otherwise, they belong to the surrounding text.
+            ']' | ')' => {
+                let open = if c == ']' { b'[' } else { b'(' };
+                let matched = brackets.last() == Some(&open);
+                if matched {
+                    brackets.pop();
+                }
+                matched
+            }
             _ => false,
         }
     });
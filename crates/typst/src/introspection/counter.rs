@@ -247,6 +247,26 @@ impl Counter {
         Ok(CounterState(smallvec![at_state.first(), final_state.first()]))
     }
 
+    /// Gets the value of the page counter on the given physical page, after
+    /// all updates on that page.
+    pub fn at_page(
+        &self,
+        engine: &mut Engine,
+        page: NonZeroUsize,
+    ) -> SourceResult<CounterState> {
+        let sequence = self.sequence(engine)?;
+        let (mut state, at_page) = sequence
+            .iter()
+            .rev()
+            .find(|(_, at_page)| *at_page <= page)
+            .unwrap_or(&sequence[0])
+            .clone();
+        if self.is_page() {
+            state.step(NonZeroUsize::ONE, page.get() - at_page.get());
+        }
+        Ok(state)
+    }
+
     /// Produce the whole sequence of counter states.
     ///
     /// This has to happen just once for all counters, cutting down the number
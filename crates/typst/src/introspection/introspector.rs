@@ -12,7 +12,7 @@ use smallvec::SmallVec;
 use crate::diag::{bail, StrResult};
 use crate::foundations::{Content, Label, Repr, Selector};
 use crate::introspection::{Location, Meta};
-use crate::layout::{Frame, FrameItem, Point, Position, Transform};
+use crate::layout::{Frame, FrameItem, Point, Position, Size, Transform};
 use crate::model::Numbering;
 use crate::util::NonZeroExt;
 
@@ -21,6 +21,8 @@ use crate::util::NonZeroExt;
 pub struct Introspector {
     /// The number of pages in the document.
     pages: usize,
+    /// The sizes of the pages, indexed by page number minus 1.
+    page_sizes: Vec<Size>,
     /// All introspectable elements.
     elems: IndexMap<Location, (Prehashed<Content>, Position)>,
     /// Maps labels to their indices in the element list. We use a smallvec such
@@ -40,6 +42,8 @@ impl Introspector {
     #[typst_macros::time(name = "introspect")]
     pub fn rebuild(&mut self, frames: &[Frame]) {
         self.pages = frames.len();
+        self.page_sizes.clear();
+        self.page_sizes.extend(frames.iter().map(Frame::size));
         self.elems.clear();
         self.labels.clear();
         self.page_numberings.clear();
@@ -226,9 +230,24 @@ impl Introspector {
         NonZeroUsize::new(self.pages).unwrap_or(NonZeroUsize::ONE)
     }
 
+    /// The total number of pages, if the document was already laid out into
+    /// pages.
+    pub fn known_pages(&self) -> Option<NonZeroUsize> {
+        NonZeroUsize::new(self.pages)
+    }
+
+    /// The size of the given page.
+    pub fn page_size(&self, page: NonZeroUsize) -> Size {
+        self.page_sizes.get(page.get() - 1).copied().unwrap_or_default()
+    }
+
     /// Gets the page numbering for the given location, if any.
     pub fn page_numbering(&self, location: Location) -> Option<&Numbering> {
-        let page = self.page(location);
+        self.page_numbering_at(self.page(location))
+    }
+
+    /// Gets the page numbering of the given physical page, if any.
+    pub fn page_numbering_at(&self, page: NonZeroUsize) -> Option<&Numbering> {
         self.page_numberings
             .get(page.get() - 1)
             .and_then(|slot| slot.as_ref())
@@ -252,6 +271,7 @@ impl Default for Introspector {
     fn default() -> Self {
         Self {
             pages: 0,
+            page_sizes: vec![],
             elems: IndexMap::new(),
             labels: HashMap::new(),
             page_numberings: vec![],
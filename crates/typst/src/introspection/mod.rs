@@ -29,7 +29,7 @@ use crate::foundations::{
     cast, category, elem, ty, Behave, Behaviour, Category, Content, Repr, Scope,
     Unlabellable,
 };
-use crate::layout::PdfPageLabel;
+use crate::layout::{Abs, PdfPageLabel};
 use crate::model::{Destination, Numbering};
 
 /// Interactions between document parts.
@@ -74,8 +74,10 @@ impl Behave for MetaElem {
 #[ty]
 #[derive(Clone, PartialEq, Hash)]
 pub enum Meta {
-    /// An internal or external link to a destination.
-    Link(Destination),
+    /// An internal or external link to a destination. Its clickable area
+    /// extends beyond the area this metadata is attached to by the padding on
+    /// each side.
+    Link(Destination, Abs),
     /// An identifiable element that produces something within the area this
     /// metadata is attached to.
     Elem(Content),
@@ -96,7 +98,7 @@ cast! {
 impl Debug for Meta {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         match self {
-            Self::Link(dest) => write!(f, "Link({dest:?})"),
+            Self::Link(dest, _) => write!(f, "Link({dest:?})"),
             Self::Elem(content) => write!(f, "Elem({:?})", content.func()),
             Self::PageNumbering(value) => write!(f, "PageNumbering({value:?})"),
             Self::PdfPageLabel(label) => write!(f, "PdfPageLabel({label:?})"),
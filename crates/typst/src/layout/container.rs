@@ -1,7 +1,8 @@
 use crate::diag::SourceResult;
 use crate::engine::Engine;
 use crate::foundations::{
-    cast, elem, AutoValue, Content, NativeElement, Resolve, Smart, StyleChain, Value,
+    cast, dict, elem, AutoValue, Content, NativeElement, Resolve, Smart, StyleChain,
+    Value,
 };
 use crate::layout::{
     Abs, Axes, Corners, Em, Fr, Fragment, FrameKind, Layout, Length, Ratio, Regions, Rel,
@@ -118,7 +119,7 @@ impl Layout for BoxElem {
         regions: Regions,
     ) -> SourceResult<Fragment> {
         let width = match self.width(styles) {
-            Sizing::Auto => Smart::Auto,
+            Sizing::Auto | Sizing::Content(_) | Sizing::WeightedAuto => Smart::Auto,
             Sizing::Rel(rel) => Smart::Custom(rel),
             Sizing::Fr(_) => Smart::Custom(Ratio::one().into()),
         };
@@ -472,6 +473,13 @@ pub enum Sizing {
     /// A track size specified as a fraction of the remaining free space in the
     /// parent.
     Fr(Fr),
+    /// A track that fits a ratio of its cells' contents. Only used for grid
+    /// columns.
+    Content(Ratio),
+    /// A track that fits its cells' contents and additionally receives a
+    /// share of the remaining space proportional to its content width. Only
+    /// used for grid columns.
+    WeightedAuto,
 }
 
 impl Sizing {
@@ -479,6 +487,11 @@ impl Sizing {
     pub fn is_fractional(self) -> bool {
         matches!(self, Self::Fr(_))
     }
+
+    /// Whether the size of the track depends on its cells' contents.
+    pub fn fits_content(self) -> bool {
+        matches!(self, Self::Auto | Self::Content(_) | Self::WeightedAuto)
+    }
 }
 
 impl Default for Sizing {
@@ -502,6 +515,8 @@ cast! {
         Self::Auto => Value::Auto,
         Self::Rel(rel) => rel.into_value(),
         Self::Fr(fr) => fr.into_value(),
+        Self::Content(ratio) => dict! { "content" => ratio }.into_value(),
+        Self::WeightedAuto => "weighted-auto".into_value(),
     },
     _: AutoValue => Self::Auto,
     v: Rel<Length> => Self::Rel(v),
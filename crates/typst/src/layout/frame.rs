@@ -287,11 +287,20 @@ impl Frame {
     }
 
     /// Attach metadata from an iterator.
+    ///
+    /// The area of links extends beyond the frame by their padding.
     pub fn meta_iter(&mut self, iter: impl IntoIterator<Item = Meta>) {
         let mut hide = false;
         for meta in iter {
+            let padding = match meta {
+                Meta::Link(_, padding) => padding,
+                _ => Abs::zero(),
+            };
             if matches!(meta, Meta::Hide) {
                 hide = true;
+            } else if !padding.is_zero() {
+                let size = self.size + Size::splat(padding * 2.0);
+                self.prepend(Point::splat(-padding), FrameItem::Meta(meta, size));
             } else {
                 self.prepend(Point::zero(), FrameItem::Meta(meta, self.size));
             }
@@ -525,6 +534,19 @@ pub struct Position {
     pub point: Point,
 }
 
+impl Position {
+    /// Normalize the position so that positions which are numerically equal
+    /// up to floating point noise also compare and hash equal.
+    pub fn canonical(self) -> Self {
+        // Rounds to a millionth of a point and maps negative zero to zero.
+        let round = |v: Abs| Abs::raw((v.to_raw() * 1e6).round() / 1e6 + 0.0);
+        Self {
+            page: self.page,
+            point: Point::new(round(self.point.x), round(self.point.y)),
+        }
+    }
+}
+
 cast! {
     Position,
     self => Value::Dict(self.into()),
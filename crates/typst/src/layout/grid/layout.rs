@@ -1,35 +1,85 @@
-use crate::diag::{bail, At, SourceResult, StrResult};
+use std::collections::HashSet;
+use std::num::NonZeroUsize;
+use std::ops::Range;
+
+use ecow::{eco_format, EcoString};
+
+use super::DecimalElem;
+use crate::diag::{bail, warning, At, SourceResult, StrResult};
 use crate::engine::Engine;
 use crate::foundations::{
-    Array, CastInfo, Content, FromValue, Func, IntoValue, Reflect, Resolve, Smart,
-    StyleChain, Value,
+    Arg, Args, Array, Cast, CastInfo, Content, FromValue, Func, IntoValue, Label,
+    NativeElement, Reflect, Repr, Resolve, Smart, StyleChain, Value,
 };
 use crate::layout::{
-    Abs, Align, Axes, Dir, Fr, Fragment, Frame, FrameItem, Layout, Length, Point,
-    Regions, Rel, Sides, Size, Sizing,
+    Abs, Align, Axes, ColumnMode, Dir, Fr, Fragment, Frame, FrameItem, Layout, Length,
+    Point, Regions, Rel, Sides, Size, Sizing, StrokeMode,
 };
-use crate::syntax::Span;
+use crate::syntax::{Span, Spanned};
 use crate::text::TextElem;
-use crate::util::Numeric;
-use crate::visualize::{FixedStroke, Geometry, Paint};
+use crate::util::{NonZeroExt, Numeric};
+use crate::visualize::{FixedStroke, Geometry, Paint, Path, Stroke};
 
 /// A value that can be configured per cell.
+///
+/// This is the type behind the `fill` and `align` parameters of grids and
+/// tables, but it can be used by any element with a per-cell property. It casts
+/// from a bare value of type `T`, an array of `T` (one entry per column,
+/// repeating) or a function receiving the cell's column and row and returning
+/// a `T`. Call [`resolve`](Self::resolve) during layout to obtain the value
+/// for a specific cell.
 #[derive(Debug, Clone, PartialEq, Hash)]
 pub enum Celled<T> {
     /// A bare value, the same for all cells.
     Value(T),
     /// A closure mapping from cell coordinates to a value.
     Func(Func),
-    /// An array of alignment values corresponding to each column.
+    /// An array of values corresponding to each column.
     Array(Vec<T>),
 }
 
 impl<T: Default + Clone + FromValue> Celled<T> {
     /// Resolve the value based on the cell position.
+    ///
+    /// An array is indexed by the column modulo its length and yields
+    /// `T::default()` if it is empty. A function is called with the column and
+    /// row, and its return value must be castable to `T`.
     pub fn resolve(&self, engine: &mut Engine, x: usize, y: usize) -> SourceResult<T> {
+        self.resolve_in(engine, x, y, &CellContext::default())
+    }
+
+    /// Resolve the value based on the cell position and further details about
+    /// the cell, like the band of grouped rows it belongs to.
+    ///
+    /// A function additionally receives each detail which is given as a named
+    /// argument. Functions taking just the position thus keep working as long
+    /// as no details are given.
+    pub fn resolve_in(
+        &self,
+        engine: &mut Engine,
+        x: usize,
+        y: usize,
+        context: &CellContext,
+    ) -> SourceResult<T> {
         Ok(match self {
             Self::Value(value) => value.clone(),
-            Self::Func(func) => func.call(engine, [x, y])?.cast().at(func.span())?,
+            Self::Func(func) => {
+                let mut args = Args::new(func.span(), [x, y]);
+                let named = [
+                    ("band", context.band.map(IntoValue::into_value)),
+                    ("row-key", context.row_key.clone()),
+                    ("column-key", context.column_key.clone()),
+                ];
+                for (name, value) in named {
+                    let Some(value) = value else { continue };
+                    args.items.push(Arg {
+                        span: args.span,
+                        name: Some(name.into()),
+                        value: Spanned::new(value, args.span),
+                    });
+                }
+                func.call(engine, args)?.cast().at(func.span())?
+            }
             Self::Array(array) => x
                 .checked_rem(array.len())
                 .and_then(|i| array.get(i))
@@ -39,6 +89,18 @@ impl<T: Default + Clone + FromValue> Celled<T> {
     }
 }
 
+/// Details about a cell beyond its position, which are passed to the
+/// functions of [`Celled`] values as named arguments.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct CellContext {
+    /// The band of grouped rows the cell belongs to, passed as `band`.
+    pub band: Option<usize>,
+    /// The key of the cell's row, passed as `row-key`.
+    pub row_key: Option<Value>,
+    /// The key of the cell's column, passed as `column-key`.
+    pub column_key: Option<Value>,
+}
+
 impl<T: Default> Default for Celled<T> {
     fn default() -> Self {
         Self::Value(T::default())
@@ -83,17 +145,58 @@ impl<T: FromValue> FromValue for Celled<T> {
 }
 
 /// Represents a cell in CellGrid, to be laid out by GridLayouter.
+#[derive(Clone)]
 pub struct Cell {
     /// The cell's body.
     pub body: Content,
     /// The cell's fill.
     pub fill: Option<Paint>,
+    /// The amount of columns spanned by the cell.
+    pub colspan: NonZeroUsize,
+    /// The cell's layer. Cells on higher layers are drawn on top of cells on
+    /// lower layers in the same row.
+    pub layer: i64,
+    /// The cell's own stroke. Only used if the grid has no uniform stroke.
+    pub stroke: Option<FixedStroke>,
+    /// Overrides of the stroke for individual sides of the cell. A side with
+    /// `Some(None)` has no line at all.
+    pub stroke_overrides: Sides<Option<Option<FixedStroke>>>,
+    /// Content drawn behind the cell's body, sized to the cell.
+    pub background: Option<Content>,
+    /// Whether the cell's body is rotated. Such a cell is measured against
+    /// the full height of a region and doesn't break across regions.
+    pub rotated: bool,
+    /// The width below which the auto columns spanned by the cell don't
+    /// shrink, even if its content is narrower.
+    pub min_width: Abs,
+    /// Whether the cell's body may overflow into the empty cells following
+    /// it in its row instead of wrapping.
+    pub spill: bool,
+    /// Whether the cell has no body, so that a preceding cell may spill into
+    /// it.
+    pub empty: bool,
+    /// The cell to lay out instead when the header containing this cell is
+    /// repeated in a followup region, e.g. a title marked as continued.
+    pub continued: Option<Box<Cell>>,
 }
 
 impl From<Content> for Cell {
     /// Create a simple cell given its body.
     fn from(body: Content) -> Self {
-        Self { body, fill: None }
+        Self {
+            empty: body.is_empty(),
+            body,
+            fill: None,
+            colspan: NonZeroUsize::ONE,
+            layer: 0,
+            stroke: None,
+            stroke_overrides: Sides::default(),
+            background: None,
+            rotated: false,
+            min_width: Abs::zero(),
+            spill: false,
+            continued: None,
+        }
     }
 }
 
@@ -110,10 +213,37 @@ impl Layout for Cell {
 
 /// Used for cell-like elements which are aware of their final properties in
 /// the table, and may have property overrides.
+///
+/// This is the extension point for cells of grid-like elements. The grid and
+/// table cells implement it, but any other element can do so as well to have
+/// its cells placed by [`CellGrid::resolve`] and laid out by a
+/// [`GridLayouter`]. Such an element must also implement [`Default`], which is
+/// used for the positions without an explicit cell.
+///
+/// The grid resolves the fill, alignment and inset for each cell's position
+/// and passes them to [`resolve_cell`](Self::resolve_cell). How they combine
+/// with the cell's own properties is up to the implementation, but it should
+/// follow the built-in cells:
+///
+/// - The cell's own fill replaces the grid's fill.
+/// - The cell's own alignment is folded with the grid's one, so that it can
+///   override a single axis. If the grid's alignment is `auto`, the cell's one
+///   is kept as is.
+/// - The cell's own inset is folded with the grid's one, side by side.
+///
+/// The resolved properties are usually stored back into the element, which is
+/// then packed as the body of the returned [`Cell`]. This way, they are
+/// available when the cell is shown and show rules see their final values.
 pub trait ResolvableCell {
     /// Resolves the cell's fields, given its coordinates and default grid-wide
     /// fill, align and inset properties.
     /// Returns a final Cell.
+    ///
+    /// The coordinates are the ones per-cell functions receive, so a title
+    /// above the grid doesn't count as a row.
+    ///
+    /// The cell's stroke and background are resolved by the grid itself and
+    /// may be left at `None`.
     fn resolve_cell(
         self,
         x: usize,
@@ -123,16 +253,306 @@ pub trait ResolvableCell {
         inset: Sides<Rel<Length>>,
         styles: StyleChain,
     ) -> Cell;
+
+    /// Returns the amount of columns spanned by this cell.
+    fn colspan(&self, styles: StyleChain) -> NonZeroUsize;
+
+    /// Returns the cell's body.
+    fn body(&self) -> &Content;
+
+    /// Replaces the cell's body.
+    fn set_body(&mut self, body: Content);
+}
+
+/// A child of a grid which can be resolved into cells.
+pub enum ResolvableGridChild<T: ResolvableCell, I> {
+    /// A header spanning the first rows of the grid.
+    Header {
+        /// Whether the header should be repeated in each region.
+        repeat: bool,
+        /// The span of the header's element.
+        span: Span,
+        /// Further rows which are repeated along with the header, given as
+        /// indices of content rows not counting a title at the top.
+        rows: Vec<usize>,
+        /// The cells within the header.
+        items: I,
+    },
+    /// A row of cells, optionally belonging to a group of rows.
+    Row {
+        /// The key of the row's group, if any.
+        group: Option<Value>,
+        /// The key identifying the row, if any.
+        key: Option<Value>,
+        /// Whether to keep the row together with the adjacent kept rows of
+        /// its group in one region.
+        keep: bool,
+        /// The span of the row's element.
+        span: Span,
+        /// The cells within the row.
+        items: I,
+    },
+    /// A footer, whose free slots in its first row are filled with the
+    /// aggregated numbers of the cells above it.
+    Footer {
+        /// How to aggregate each column, if at all.
+        aggregates: Vec<Option<Aggregate>>,
+        /// The cells within the footer.
+        items: I,
+    },
+    /// A title in a line of its own, spanning all columns. It is not
+    /// affected by per-cell properties like the fill.
+    Title(T),
+    /// A single cell.
+    Item(T),
+}
+
+/// How to format the numbers in the cells of a column.
+#[derive(Debug, Clone, PartialEq, Hash)]
+pub enum NumberFormat {
+    /// Formats numbers with a function.
+    Func(Func),
+    /// Groups the digits of numbers in threes and writes them with the given
+    /// separators.
+    Separators { thousands: EcoString, decimal: char },
+}
+
+/// Formats a body consisting of just a number in the given way.
+///
+/// Returns `None` if the body is anything but plain text containing an integer
+/// or a float, e.g. because it was already formatted explicitly.
+fn format_numeric(
+    engine: &mut Engine,
+    body: &Content,
+    format: &NumberFormat,
+) -> SourceResult<Option<Content>> {
+    let Some(number) = parse_numeric(body) else {
+        return Ok(None);
+    };
+    match format {
+        NumberFormat::Func(func) => {
+            let formatted = func.call(engine, [number])?;
+            formatted.cast().at(func.span()).map(Some)
+        }
+        NumberFormat::Separators { thousands, decimal } => {
+            // Keep the digits as written unless there is an exponent.
+            let written = body.to::<TextElem>().map(|elem| elem.text().trim());
+            let text: EcoString = match (number, written) {
+                (_, Some(text)) if !text.contains(['e', 'E']) => text.into(),
+                (Value::Float(float), _) => eco_format!("{float}"),
+                (number, _) => number.repr(),
+            };
+            Ok(Some(TextElem::packed(separate_number(&text, thousands, *decimal))))
+        }
+    }
+}
+
+/// Parses a body consisting of just plain text containing an integer or a
+/// float into an integer or float value.
+fn parse_numeric(body: &Content) -> Option<Value> {
+    let text = body.to::<TextElem>()?.text().trim();
+    match (text.parse::<i64>(), text.parse::<f64>()) {
+        (Ok(int), _) => Some(int.into_value()),
+        (_, Ok(float)) if float.is_finite() => Some(float.into_value()),
+        _ => None,
+    }
+}
+
+/// Writes a number with its digits grouped in threes by the thousands
+/// separator and the given decimal separator.
+fn separate_number(text: &str, thousands: &str, decimal: char) -> EcoString {
+    let digits = text.trim_start_matches(['+', '-']);
+    let mut out = EcoString::new();
+    if text.starts_with('-') {
+        out.push('\u{2212}');
+    } else if text.starts_with('+') {
+        out.push('+');
+    }
+
+    let (integer, fraction) = match digits.split_once('.') {
+        Some((integer, fraction)) => (integer, Some(fraction)),
+        None => (digits, None),
+    };
+    for (i, c) in integer.chars().enumerate() {
+        if i > 0 && (integer.len() - i) % 3 == 0 {
+            out.push_str(thousands);
+        }
+        out.push(c);
+    }
+    if let Some(fraction) = fraction {
+        out.push(decimal);
+        out.push_str(fraction);
+    }
+    out
+}
+
+/// Splits a body consisting of just a number into the part before the decimal
+/// separator and the rest, which is empty for whole numbers.
+///
+/// The part before the separator may contain a sign and digit grouping with
+/// spaces, apostrophes or the other one of period and comma.
+fn split_decimal(body: &Content, separator: char) -> Option<(EcoString, EcoString)> {
+    let text = body.to::<TextElem>()?.text().trim();
+    let (integer, fraction) = text.split_at(text.find(separator).unwrap_or(text.len()));
+    let grouping = if separator == '.' { ',' } else { '.' };
+    let digits = integer.trim_start_matches(['+', '-', '\u{2212}']);
+    let valid = digits.starts_with(|c: char| c.is_ascii_digit())
+        && digits.chars().all(|c| {
+            c.is_ascii_digit()
+                || matches!(c, ' ' | '\u{2009}' | '\u{202F}' | '\'' | '\u{2019}')
+                || c == grouping
+        })
+        && fraction.chars().skip(1).all(|c| c.is_ascii_digit());
+    valid.then(|| (integer.into(), fraction.into()))
+}
+
+/// What to do with a last row which has fewer cells than there are columns.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Cast)]
+pub enum IncompleteRow {
+    /// Fill the row up with empty cells.
+    Fill,
+    /// Fail with an error.
+    Error,
+    /// Leave out the row and its cells.
+    Drop,
+}
+
+/// How to aggregate the numeric cells of a column in a footer.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Cast)]
+pub enum Aggregate {
+    /// The sum of the numbers.
+    Sum,
+    /// The arithmetic mean of the numbers.
+    Average,
+    /// The smallest number.
+    Min,
+    /// The largest number.
+    Max,
+}
+
+impl Aggregate {
+    /// Aggregates integer and float values. The result is an integer if all
+    /// values are integers, except for the average. Returns `None` if there
+    /// are no values.
+    fn apply(self, numbers: &[Value]) -> Option<Value> {
+        if numbers.is_empty() {
+            return None;
+        }
+
+        let floats = numbers.iter().map(|number| match *number {
+            Value::Int(int) => int as f64,
+            Value::Float(float) => float,
+            _ => 0.0,
+        });
+
+        let result = match self {
+            Self::Sum => floats.sum(),
+            Self::Average => floats.sum::<f64>() / numbers.len() as f64,
+            Self::Min => floats.fold(f64::INFINITY, f64::min),
+            Self::Max => floats.fold(f64::NEG_INFINITY, f64::max),
+        };
+
+        let ints = numbers.iter().all(|number| matches!(number, Value::Int(_)));
+        Some(if ints && self != Self::Average && result.abs() < i64::MAX as f64 {
+            (result as i64).into_value()
+        } else {
+            result.into_value()
+        })
+    }
+}
+
+/// A grid entry.
+pub enum Entry {
+    /// An entry which holds a cell.
+    Cell(Cell),
+    /// An entry which is merged with another cell, e.g. because that cell
+    /// spans multiple columns.
+    Merged {
+        /// The index of the cell this entry is merged with.
+        parent: usize,
+    },
+}
+
+impl Entry {
+    /// Obtains the cell inside this entry, if this is not a merged entry.
+    fn as_cell(&self) -> Option<&Cell> {
+        match self {
+            Self::Cell(cell) => Some(cell),
+            Self::Merged { .. } => None,
+        }
+    }
+}
+
+/// A header at the top of a grid, which may be repeated in each region.
+pub struct Header {
+    /// The index after the last row included in the header, including the
+    /// gutter row following it (if any).
+    pub end: usize,
+    /// Whether the header is repeated at the top of each region.
+    pub repeat: bool,
+    /// Further rows after the header which are repeated along with it, in
+    /// ascending order and including the gutter rows following them (if any).
+    pub rows: Vec<usize>,
+}
+
+/// A group of consecutive rows which is kept together in one region.
+pub struct KeptRows {
+    /// The rows of the group, without the gutter row following it (if any).
+    pub rows: Range<usize>,
+    /// The span of the group's first row.
+    pub span: Span,
+}
+
+/// The properties with which the cells of a grid are resolved.
+pub struct CellProperties<'a> {
+    /// The fill of the cells, also receiving the band of a grouped row.
+    pub fill: &'a Celled<Option<Paint>>,
+    /// Fills cycled through for the rows between the header and the footer,
+    /// taking precedence over the `fill`.
+    pub stripes: &'a [Option<Paint>],
+    /// The alignment of the cells.
+    pub align: &'a Celled<Smart<Align>>,
+    /// The stroke of the cells.
+    pub stroke: &'a Celled<Option<Stroke>>,
+    /// The background of the cells.
+    pub background: &'a Celled<Option<Content>>,
+    /// The inset of the cells.
+    pub inset: Sides<Rel<Length>>,
+    /// The number format of each column.
+    pub formats: &'a [Option<NumberFormat>],
+    /// The decimal separator of each decimal-aligned column.
+    pub decimals: &'a [Option<char>],
+    /// The key identifying each column.
+    pub column_keys: &'a [Option<Value>],
+    /// What to do with an incomplete last row.
+    pub incomplete: IncompleteRow,
+}
+
+/// The content rows of a grid which are repeated or kept together.
+#[derive(Default)]
+struct RowGroups {
+    /// The number of content rows making up the header.
+    header_rows: usize,
+    /// Whether the header is repeated at the top of each region.
+    repeat_header: bool,
+    /// Further content rows repeated along with the header.
+    repeated_rows: Vec<usize>,
+    /// The groups of content rows kept together.
+    kept: Vec<KeptRows>,
 }
 
 /// A grid of cells, including the columns, rows, and cell data.
 pub struct CellGrid {
-    /// The grid cells.
-    cells: Vec<Cell>,
+    /// The grid entries.
+    entries: Vec<Entry>,
     /// The column tracks including gutter tracks.
     cols: Vec<Sizing>,
     /// The row tracks including gutter tracks.
     rows: Vec<Sizing>,
+    /// The header of the grid, if any.
+    header: Option<Header>,
+    /// The groups of rows kept together, in ascending order.
+    kept: Vec<KeptRows>,
     /// Whether this grid has gutters.
     has_gutter: bool,
     /// Whether this is an RTL grid.
@@ -147,6 +567,25 @@ impl CellGrid {
         cells: Vec<Cell>,
         styles: StyleChain,
     ) -> Self {
+        let entries = cells.into_iter().map(Entry::Cell).collect();
+        Self::new_internal(tracks, gutter, entries, RowGroups::default(), styles)
+    }
+
+    /// Generates the cell grid, given the tracks, the resolved entries and
+    /// the content rows which are repeated or kept together.
+    fn new_internal(
+        tracks: Axes<&[Sizing]>,
+        gutter: Axes<&[Sizing]>,
+        entries: Vec<Entry>,
+        groups: RowGroups,
+        styles: StyleChain,
+    ) -> Self {
+        let RowGroups {
+            header_rows,
+            repeat_header,
+            mut repeated_rows,
+            mut kept,
+        } = groups;
         let mut cols = vec![];
         let mut rows = vec![];
 
@@ -156,7 +595,7 @@ impl CellGrid {
         // Number of content rows: At least as many as given, but also at least
         // as many as needed to place each item.
         let r = {
-            let len = cells.len();
+            let len = entries.len();
             let given = tracks.y.len();
             let needed = len / c + (len % c).clamp(0, 1);
             given.max(needed)
@@ -168,20 +607,32 @@ impl CellGrid {
         let get_or = |tracks: &[_], idx, default| {
             tracks.get(idx).or(tracks.last()).copied().unwrap_or(default)
         };
+        let cycle = |tracks: &[_], idx| {
+            if tracks.is_empty() {
+                zero
+            } else {
+                tracks[idx % tracks.len()]
+            }
+        };
 
         // Collect content and gutter columns.
         for x in 0..c {
             cols.push(get_or(tracks.x, x, auto));
             if has_gutter {
-                cols.push(get_or(gutter.x, x, zero));
+                cols.push(cycle(gutter.x, x));
             }
         }
 
-        // Collect content and gutter rows.
+        // Collect content and gutter rows. Rows can't fit a share of their
+        // contents or grow with them, they fit all of it.
+        let fit = |sizing| match sizing {
+            Sizing::Content(_) | Sizing::WeightedAuto => auto,
+            sizing => sizing,
+        };
         for y in 0..r {
-            rows.push(get_or(tracks.y, y, auto));
+            rows.push(fit(get_or(tracks.y, y, auto)));
             if has_gutter {
-                rows.push(get_or(gutter.y, y, zero));
+                rows.push(fit(cycle(gutter.y, y)));
             }
         }
 
@@ -197,64 +648,562 @@ impl CellGrid {
             cols.reverse();
         }
 
-        Self { cols, rows, cells, has_gutter, is_rtl }
+        // The header ends after its last content row and the gutter row
+        // following it. The same goes for each further repeated row.
+        repeated_rows.retain(|&y| y >= header_rows && y < r);
+        repeated_rows.sort_unstable();
+        repeated_rows.dedup();
+        let header = (header_rows > 0 || !repeated_rows.is_empty()).then(|| Header {
+            end: if has_gutter { (2 * header_rows).min(rows.len()) } else { header_rows },
+            repeat: repeat_header,
+            rows: if has_gutter {
+                repeated_rows
+                    .iter()
+                    .flat_map(|&y| [2 * y, 2 * y + 1])
+                    .filter(|&y| y < rows.len())
+                    .collect()
+            } else {
+                repeated_rows
+            },
+        });
+
+        // A kept group spans the gutter rows between its rows, but not the one
+        // following it.
+        kept.retain(|group| !group.rows.is_empty());
+        if has_gutter {
+            for group in &mut kept {
+                group.rows = 2 * group.rows.start..2 * group.rows.end - 1;
+            }
+        }
+
+        Self {
+            cols,
+            rows,
+            entries,
+            header,
+            kept,
+            has_gutter,
+            is_rtl,
+        }
     }
 
     /// Resolves all cells in the grid before creating it.
     /// Allows them to keep track of their final properties and adjust their
     /// fields accordingly.
-    /// Cells must implement Clone as they will be owned. Additionally, they
-    /// must implement Default in order to fill the last row of the grid with
-    /// empty cells, if it is not completely filled.
-    #[allow(clippy::too_many_arguments)]
-    pub fn resolve<T: ResolvableCell + Clone + Default>(
+    /// Cells are placed in row-major order, moving on to the next row when a
+    /// cell spanning multiple columns would not fit into the current one.
+    /// Cells must implement Default in order to fill empty positions and the
+    /// last row of the grid with empty cells.
+    ///
+    /// The `fill`, `align`, `stroke` and `background` of the `properties`
+    /// are resolved per cell, with rows of a title at the top not counting
+    /// towards the cell's row. The number formats and the decimal separators
+    /// of decimal-aligned columns are given per column and may be shorter
+    /// than the number of columns, or empty if not supported by the grid.
+    ///
+    /// The keys identifying the columns are given per column as well. The
+    /// keys of rows and columns are passed to the functions of the per-cell
+    /// properties if present, while bands of grouped rows are passed to the
+    /// `fill` only.
+    ///
+    /// If there are `stripes`, they are cycled through as the fills of the
+    /// rows between the header and the footer instead of the `fill`,
+    /// starting with the first row after the header.
+    ///
+    /// If the last row outside of the header and footer has fewer cells than
+    /// there are columns, `incomplete` decides whether it is filled up,
+    /// rejected or dropped.
+    pub fn resolve<T, C, I>(
         tracks: Axes<&[Sizing]>,
         gutter: Axes<&[Sizing]>,
-        cells: &[T],
-        fill: &Celled<Option<Paint>>,
-        align: &Celled<Smart<Align>>,
-        inset: Sides<Rel<Length>>,
+        children: C,
+        properties: &CellProperties,
         engine: &mut Engine,
         styles: StyleChain,
-    ) -> SourceResult<Self> {
+    ) -> SourceResult<Self>
+    where
+        T: ResolvableCell + NativeElement + Default,
+        I: IntoIterator<Item = T>,
+        C: IntoIterator<Item = ResolvableGridChild<T, I>>,
+    {
+        let &CellProperties {
+            fill,
+            stripes,
+            align,
+            stroke,
+            background,
+            inset,
+            formats,
+            decimals,
+            column_keys,
+            incomplete,
+        } = properties;
+
         // Number of content columns: Always at least one.
         let c = tracks.x.len().max(1);
 
+        // The resolved entries. Positions which haven't been filled yet are
+        // `None`.
+        let mut resolved: Vec<Option<Entry>> = vec![];
+
+        // The index of the next position available to a cell.
+        let mut auto_index = 0;
+
+        // The number of content rows in the header, if any.
+        let mut header = None;
+
+        // The labels of the cells seen so far, to detect duplicates.
+        let mut labels: HashSet<Label> = HashSet::new();
+
+        // The band of each row which belongs to a group, and the band and key
+        // of the last group.
+        let mut bands: Vec<Option<usize>> = vec![];
+        let mut last_group: Option<(usize, Value)> = None;
+
+        // The key of each row which has one.
+        let mut row_keys: Vec<Option<Value>> = vec![];
+
+        // The groups of rows kept together, with the band of each.
+        let mut kept: Vec<(Option<usize>, KeptRows)> = vec![];
+
+        // The number of rows taken by a title at the top. Per-cell
+        // properties don't count these rows.
+        let mut title_rows = 0;
+
+        // The row the footer starts at, if any.
+        let mut footer_start = None;
+
+        // The fill of the row `y`, if it is striped. Rows are striped from the
+        // start of the body, which is passed for rows that belong to it.
+        let stripe = |y: usize, body_start: Option<usize>| {
+            let start = body_start.filter(|_| !stripes.is_empty())?;
+            Some(stripes[(y - start) % stripes.len()].clone())
+        };
+
+        // The numbers in each column outside of headers and footers, to be
+        // aggregated by a footer.
+        let mut numbers: Vec<Vec<Value>> = vec![vec![]; c];
+
+        // The span of the cell placed last, to report an incomplete last row.
+        let mut last_span = Span::detached();
+
+        // Places a cell at the next position with enough space left in its
+        // row and resolves it. Returns the index it was placed at.
+        let mut place = |mut cell: T,
+                         band: Option<usize>,
+                         row_key: Option<&Value>,
+                         skip: usize,
+                         body_start: Option<usize>,
+                         auto_index: &mut usize,
+                         resolved: &mut Vec<Option<Entry>>|
+         -> SourceResult<usize> {
+            let colspan = cell.colspan(styles).get();
+            if colspan > c {
+                bail!(
+                    cell.span(),
+                    "cell's colspan would cause it to exceed the available column(s)";
+                    hint: "try reducing the cell's colspan or adding more columns"
+                );
+            }
+
+            if let Some(label) = cell.label() {
+                if !labels.insert(label) {
+                    bail!(
+                        cell.span(),
+                        "label `{}` is already used by another cell", label.repr();
+                        hint: "cell labels must be unique within a grid"
+                    );
+                }
+            }
+
+            last_span = cell.span();
+            if last_span.is_detached() {
+                last_span = cell.body().span();
+            }
+
+            // Move to the next row if the cell doesn't fit.
+            if *auto_index % c + colspan > c {
+                *auto_index += c - *auto_index % c;
+            }
+
+            let index = *auto_index;
+            let (x, y) = (index % c, index / c);
+            if let Some(Some(format)) = formats.get(x) {
+                if let Some(body) = format_numeric(engine, cell.body(), format)? {
+                    cell.set_body(body);
+                }
+            }
+            if resolved.len() < index + colspan {
+                resolved.resize_with(index + colspan, || None);
+            }
+
+            let ly = y - skip;
+            let keys = CellContext {
+                band: None,
+                row_key: row_key.cloned(),
+                column_key: column_keys.get(x).cloned().flatten(),
+            };
+            let fill = match stripe(y, body_start) {
+                Some(fill) => fill,
+                None => {
+                    fill.resolve_in(engine, x, ly, &CellContext { band, ..keys.clone() })?
+                }
+            };
+            let mut cell = cell.resolve_cell(
+                x,
+                ly,
+                &fill,
+                align.resolve_in(engine, x, ly, &keys)?,
+                inset,
+                styles,
+            );
+            cell.stroke = stroke
+                .resolve_in(engine, x, ly, &keys)?
+                .map(|stroke| stroke.resolve(styles).unwrap_or_default());
+            cell.background = background.resolve_in(engine, x, ly, &keys)?;
+
+            resolved[index] = Some(Entry::Cell(cell));
+            for slot in &mut resolved[index + 1..index + colspan] {
+                *slot = Some(Entry::Merged { parent: index });
+            }
+
+            *auto_index += colspan;
+            Ok(index)
+        };
+
+        // Numbers in cells spanning multiple columns are not aggregated.
+        let numeric = |cell: &T| {
+            (cell.colspan(styles).get() == 1)
+                .then(|| parse_numeric(cell.body()))
+                .flatten()
+        };
+
+        for child in children {
+            match child {
+                ResolvableGridChild::Header { repeat, span, rows, items } => {
+                    if header.is_some() {
+                        bail!(span, "cannot have more than one header");
+                    }
+
+                    if auto_index > title_rows * c {
+                        bail!(
+                            span,
+                            "header must start at the first row";
+                            hint: "try moving the header to the beginning of the grid"
+                        );
+                    }
+
+                    for cell in items {
+                        place(
+                            cell,
+                            None,
+                            None,
+                            title_rows,
+                            None,
+                            &mut auto_index,
+                            &mut resolved,
+                        )?;
+                    }
+
+                    // The header always occupies whole rows.
+                    let end = (auto_index + c - 1) / c;
+                    auto_index = end * c;
+                    let rows = rows.into_iter().map(|y| y + title_rows).collect();
+                    header = Some((end, repeat, span, rows));
+                }
+                ResolvableGridChild::Row { group, key, keep, span, items } => {
+                    // A row always starts on a new line.
+                    auto_index = (auto_index + c - 1) / c * c;
+                    let start = auto_index / c;
+
+                    // Consecutive rows with the same key form a band. The band
+                    // changes whenever the key does.
+                    let band = group.map(|key| {
+                        let band = match &last_group {
+                            Some((band, last)) if *last == key => *band,
+                            Some((band, _)) => band + 1,
+                            None => 0,
+                        };
+                        last_group = Some((band, key));
+                        band
+                    });
+
+                    let body_start = header.as_ref().map_or(title_rows, |h| h.0);
+                    for cell in items {
+                        let number = numeric(&cell);
+                        let index = place(
+                            cell,
+                            band,
+                            key.as_ref(),
+                            title_rows,
+                            Some(body_start),
+                            &mut auto_index,
+                            &mut resolved,
+                        )?;
+                        if let Some(number) = number {
+                            numbers[index % c].push(number);
+                        }
+                    }
+
+                    // The row occupies whole lines, so no cell spans across the
+                    // boundary of a group.
+                    let end = (auto_index + c - 1) / c;
+                    auto_index = end * c;
+                    if band.is_some() {
+                        bands.resize(bands.len().max(end), None);
+                        bands[start..end].fill(band);
+                    }
+                    if key.is_some() {
+                        row_keys.resize(row_keys.len().max(end), None);
+                        row_keys[start..end].fill(key);
+                    }
+
+                    // Kept rows directly following each other stay together if
+                    // they belong to the same group.
+                    if keep {
+                        match kept.last_mut() {
+                            Some((last, group))
+                                if band.is_some()
+                                    && *last == band
+                                    && group.rows.end == start =>
+                            {
+                                group.rows.end = end;
+                            }
+                            _ => kept.push((band, KeptRows { rows: start..end, span })),
+                        }
+                    }
+                }
+                ResolvableGridChild::Footer { aggregates, items } => {
+                    // The footer starts on a new line.
+                    auto_index = (auto_index + c - 1) / c * c;
+                    let start = auto_index;
+                    footer_start = Some(start / c);
+                    for cell in items {
+                        place(
+                            cell,
+                            None,
+                            None,
+                            title_rows,
+                            None,
+                            &mut auto_index,
+                            &mut resolved,
+                        )?;
+                    }
+
+                    // Fill the free slots in the footer's first row with the
+                    // aggregated numbers of their columns.
+                    for (x, aggregate) in aggregates.into_iter().enumerate().take(c) {
+                        let Some(value) =
+                            aggregate.and_then(|aggregate| aggregate.apply(&numbers[x]))
+                        else {
+                            continue;
+                        };
+                        if start + x < auto_index {
+                            continue;
+                        }
+
+                        let mut cell = T::default();
+                        cell.set_body(TextElem::packed(value.repr()));
+                        auto_index = start + x;
+                        place(
+                            cell,
+                            None,
+                            None,
+                            title_rows,
+                            None,
+                            &mut auto_index,
+                            &mut resolved,
+                        )?;
+                    }
+                }
+                ResolvableGridChild::Title(cell) => {
+                    // The title occupies a whole line of its own.
+                    auto_index = (auto_index + c - 1) / c * c;
+                    let index = auto_index;
+                    if index == 0 {
+                        title_rows = 1;
+                    }
+
+                    resolved.resize_with(index + c, || None);
+                    let y = index / c;
+                    let mut cell =
+                        cell.resolve_cell(0, y, &None, Smart::Auto, inset, styles);
+                    cell.colspan = NonZeroUsize::new(c).unwrap();
+                    if let Some(continued) = &mut cell.continued {
+                        continued.colspan = cell.colspan;
+                    }
+                    resolved[index] = Some(Entry::Cell(cell));
+                    for slot in &mut resolved[index + 1..index + c] {
+                        *slot = Some(Entry::Merged { parent: index });
+                    }
+
+                    auto_index += c;
+                }
+                ResolvableGridChild::Item(cell) => {
+                    let number = numeric(&cell);
+                    let body_start = header.as_ref().map_or(title_rows, |h| h.0);
+                    let index = place(
+                        cell,
+                        None,
+                        None,
+                        title_rows,
+                        Some(body_start),
+                        &mut auto_index,
+                        &mut resolved,
+                    )?;
+                    if let Some(number) = number {
+                        numbers[index % c].push(number);
+                    }
+                }
+            }
+        }
+
         // If not all columns in the last row have cells, we will add empty
         // cells and complete the row so that those positions are susceptible
-        // to show rules and receive grid styling.
+        // to show rules and receive grid styling. The same applies to
+        // positions skipped by cells spanning multiple columns.
         // We apply '% c' twice so that 'cells_remaining' is zero when
         // the last row is already filled (then 'cell_count % c' would be zero).
-        let cell_count = cells.len();
+        let mut cell_count = resolved.len();
         let cells_remaining = (c - cell_count % c) % c;
-        let cells = cells
-            .iter()
-            .cloned()
-            .chain(std::iter::repeat_with(T::default).take(cells_remaining))
+
+        // An incomplete last row of the body is handled according to the
+        // policy instead.
+        let body_start = header.as_ref().map_or(title_rows, |h| h.0);
+        let last = cell_count / c;
+        if cells_remaining > 0
+            && last >= body_start
+            && footer_start.map_or(true, |start| last < start)
+        {
+            match incomplete {
+                IncompleteRow::Fill => {}
+                IncompleteRow::Error => bail!(
+                    last_span,
+                    "last row only fills {} of {c} columns", c - cells_remaining;
+                    hint: "add cells or set `on-incomplete-row` to \"fill\" or \"drop\""
+                ),
+                IncompleteRow::Drop => {
+                    cell_count -= c - cells_remaining;
+                    resolved.truncate(cell_count);
+                }
+            }
+        }
+
+        let cells_remaining = (c - cell_count % c) % c;
+        resolved.resize_with(cell_count + cells_remaining, || None);
+
+        // Empty positions after the header and before the footer belong to
+        // the body.
+        let in_body = |y: usize| {
+            (y >= body_start && footer_start.map_or(true, |end| y < end))
+                .then_some(body_start)
+        };
+
+        let mut entries = resolved
+            .into_iter()
             .enumerate()
-            .map(|(i, cell)| {
-                let x = i % c;
-                let y = i / c;
-
-                Ok(cell.resolve_cell(
-                    x,
-                    y,
-                    &fill.resolve(engine, x, y)?,
-                    align.resolve(engine, x, y)?,
-                    inset,
-                    styles,
-                ))
+            .map(|(i, entry)| match entry {
+                Some(entry) => Ok(entry),
+                None => {
+                    let x = i % c;
+                    let y = i / c;
+                    let ly = y - title_rows;
+                    let keys = CellContext {
+                        band: None,
+                        row_key: row_keys.get(y).cloned().flatten(),
+                        column_key: column_keys.get(x).cloned().flatten(),
+                    };
+                    let band = bands.get(y).copied().flatten();
+                    let fill = match stripe(y, in_body(y)) {
+                        Some(fill) => fill,
+                        None => fill.resolve_in(
+                            engine,
+                            x,
+                            ly,
+                            &CellContext { band, ..keys.clone() },
+                        )?,
+                    };
+
+                    let mut cell = T::default().resolve_cell(
+                        x,
+                        ly,
+                        &fill,
+                        align.resolve_in(engine, x, ly, &keys)?,
+                        inset,
+                        styles,
+                    );
+                    cell.stroke = stroke
+                        .resolve_in(engine, x, ly, &keys)?
+                        .map(|stroke| stroke.resolve(styles).unwrap_or_default());
+                    cell.background = background.resolve_in(engine, x, ly, &keys)?;
+                    Ok(Entry::Cell(cell))
+                }
             })
             .collect::<SourceResult<Vec<_>>>()?;
 
-        Ok(Self::new(tracks, gutter, cells, styles))
+        // Align the numbers in decimal columns at their decimal separators.
+        // Cells spanning multiple columns are left as is.
+        for (x, separator) in decimals.iter().take(c).enumerate() {
+            let Some(separator) = *separator else { continue };
+            let numbers: Vec<_> = entries
+                .iter()
+                .enumerate()
+                .skip(x)
+                .step_by(c)
+                .filter_map(|(i, entry)| {
+                    let cell = entry.as_cell().filter(|cell| cell.colspan.get() == 1)?;
+                    let elem = cell.body.to::<T>()?;
+                    let (integer, fraction) = split_decimal(elem.body(), separator)?;
+                    Some((i, elem.clone(), integer, fraction))
+                })
+                .collect();
+
+            let fractions: Vec<EcoString> =
+                numbers.iter().map(|(.., fraction)| fraction.clone()).collect();
+            for (i, mut elem, integer, fraction) in numbers {
+                let Entry::Cell(cell) = &mut entries[i] else { continue };
+                let decimal = DecimalElem::new(integer, fraction, fractions.clone());
+                elem.set_body(decimal.pack());
+                cell.body = elem.pack();
+            }
+        }
+
+        let (header_rows, repeat_header, span, repeated_rows) =
+            header.unwrap_or((0, false, Span::detached(), vec![]));
+
+        // Repeated rows must exist in the grid.
+        let r = (entries.len() / c).max(tracks.y.len());
+        if let Some(&y) = repeated_rows.iter().find(|&&y| y >= r) {
+            bail!(
+                span,
+                "row index {} is out of range", y - title_rows;
+                hint: "the grid only has {} row(s)", r - title_rows
+            );
+        }
+
+        let groups = RowGroups {
+            header_rows,
+            repeat_header,
+            repeated_rows,
+            kept: kept.into_iter().map(|(_, group)| group).collect(),
+        };
+
+        Ok(Self::new_internal(tracks, gutter, entries, groups, styles))
     }
 
-    /// Get the content of the cell in column `x` and row `y`.
+    /// Whether any cell overrides the stroke of some of its sides.
+    pub fn has_stroke_overrides(&self) -> bool {
+        self.entries
+            .iter()
+            .filter_map(Entry::as_cell)
+            .any(|cell| cell.stroke_overrides != Sides::default())
+    }
+
+    /// Get the index of the entry in column `x` and row `y`.
     ///
     /// Returns `None` if it's a gutter cell.
     #[track_caller]
-    fn cell(&self, mut x: usize, y: usize) -> Option<&Cell> {
+    fn entry_index(&self, mut x: usize, y: usize) -> Option<usize> {
         assert!(x < self.cols.len());
         assert!(y < self.rows.len());
 
@@ -267,15 +1216,123 @@ impl CellGrid {
             // Even columns and rows are children, odd ones are gutter.
             if x % 2 == 0 && y % 2 == 0 {
                 let c = 1 + self.cols.len() / 2;
-                self.cells.get((y / 2) * c + x / 2)
+                Some((y / 2) * c + x / 2)
             } else {
                 None
             }
         } else {
             let c = self.cols.len();
-            self.cells.get(y * c + x)
+            Some(y * c + x)
         }
     }
+
+    /// Get the entry in column `x` and row `y`.
+    ///
+    /// Returns `None` if it's a gutter cell.
+    #[track_caller]
+    fn entry(&self, x: usize, y: usize) -> Option<&Entry> {
+        self.entry_index(x, y).and_then(|index| self.entries.get(index))
+    }
+
+    /// Get the content of the cell in column `x` and row `y`.
+    ///
+    /// Returns `None` if it's a gutter cell or merged with another cell.
+    #[track_caller]
+    fn cell(&self, x: usize, y: usize) -> Option<&Cell> {
+        self.entry(x, y).and_then(Entry::as_cell)
+    }
+
+    /// Get the index of the cell covering column `x` and row `y`, which is
+    /// either the entry at that position or the cell it is merged with.
+    ///
+    /// Returns `None` if it's a gutter cell.
+    #[track_caller]
+    fn parent_index(&self, x: usize, y: usize) -> Option<usize> {
+        let index = self.entry_index(x, y)?;
+        match self.entries.get(index)? {
+            Entry::Cell(_) => Some(index),
+            Entry::Merged { parent } => Some(*parent),
+        }
+    }
+
+    /// The range of column tracks covered by a cell at column `x` which spans
+    /// `colspan` columns.
+    ///
+    /// In RTL grids, the cell's position is its rightmost track, as cells
+    /// span towards the end of the row.
+    fn colspan_tracks(&self, x: usize, colspan: usize) -> Range<usize> {
+        let tracks = if self.has_gutter { 2 * colspan - 1 } else { colspan };
+        if self.is_rtl {
+            x + 1 - tracks..x + 1
+        } else {
+            x..x + tracks
+        }
+    }
+
+    /// The range of column tracks a cell at column `x` in row `y` may spill
+    /// into: its own tracks and those of the empty cells following it in
+    /// reading order, up to the first non-empty cell or the grid's edge.
+    fn spill_tracks(&self, x: usize, y: usize, colspan: usize) -> Range<usize> {
+        // The distance to the next cell's position, skipping a gutter track.
+        let step = if self.has_gutter { 2 } else { 1 };
+        let mut tracks = self.colspan_tracks(x, colspan);
+        loop {
+            let next = if self.is_rtl {
+                tracks.start.checked_sub(step)
+            } else {
+                Some(tracks.end + step - 1)
+            };
+            let Some(next) = next.filter(|&t| t < self.cols.len()) else { break };
+            let Some(cell) = self.cell(next, y).filter(|cell| cell.empty) else { break };
+            let spanned = self.colspan_tracks(next, cell.colspan.get());
+            tracks = tracks.start.min(spanned.start)..tracks.end.max(spanned.end);
+        }
+        tracks
+    }
+
+    /// The height of the (repeated) header at the top of the given rows of a
+    /// region.
+    fn header_height(&self, rows: &[RowPiece]) -> Abs {
+        let in_header = |y| {
+            self.header
+                .as_ref()
+                .map_or(false, |header| y < header.end || header.rows.contains(&y))
+        };
+        rows.iter()
+            .take_while(|piece| in_header(piece.y))
+            .map(|piece| piece.height)
+            .sum()
+    }
+
+    /// Whether the vertical line between the columns `x - 1` and `x` would
+    /// cross a cell spanning both columns in row `y`.
+    fn is_within_colspan(&self, x: usize, y: usize) -> bool {
+        if x == 0 || x >= self.cols.len() {
+            return false;
+        }
+
+        // Lines in gutter rows are only interrupted if they are interrupted
+        // in the rows around them.
+        if self.has_gutter && y % 2 == 1 {
+            return y + 1 < self.rows.len()
+                && self.is_within_colspan(x, y - 1)
+                && self.is_within_colspan(x, y + 1);
+        }
+
+        // Determine the content columns on both sides of the line, skipping
+        // gutter columns.
+        let (left, right) = if self.has_gutter {
+            (if x % 2 == 1 { x - 1 } else { x - 2 }, if x % 2 == 0 { x } else { x + 1 })
+        } else {
+            (x - 1, x)
+        };
+
+        right < self.cols.len()
+            && matches!(
+                (self.parent_index(left, y), self.parent_index(right, y)),
+                (Some(a), Some(b)) if a == b
+            )
+    }
 }
 
 /// Performs grid layout.
@@ -300,6 +1357,36 @@ pub struct GridLayouter<'a> {
     initial: Size,
     /// Frames for finished regions.
     finished: Vec<Frame>,
+    /// The height of the header, which is subtracted from the space available
+    /// to rows in followup regions if the header is repeated.
+    header_height: Abs,
+    /// The amount of rows in `lrows` which belong to a repeated header.
+    header_rows: usize,
+    /// The rows after the header which are repeated along with it and have
+    /// already been laid out.
+    repeated: Vec<usize>,
+    /// Whether the header is currently being laid out.
+    in_header: bool,
+    /// Whether the header is currently being repeated in a followup region.
+    repeating: bool,
+    /// Whether to balance the widths of auto columns.
+    balance: bool,
+    /// The minimum number of content rows to keep together on either side of
+    /// a region break.
+    min_rows: usize,
+    /// Whether lines are drawn between cells or around each cell.
+    stroke_mode: StrokeMode,
+    /// The minimum thickness of lines.
+    hairline: Abs,
+    /// The stroke of the rule below the header, overriding the lines there.
+    header_stroke: Option<FixedStroke>,
+    /// How the automatically sized columns deal with content that doesn't
+    /// fit, by column track.
+    modes: Vec<Option<ColumnMode>>,
+    /// Whether to remember the cells whose content does not fit into them.
+    track_overflow: bool,
+    /// The logical positions of the cells whose content did not fit.
+    overflowed: Vec<Axes<usize>>,
     /// The span of the grid element.
     span: Span,
 }
@@ -326,7 +1413,6 @@ impl<'a> GridLayouter<'a> {
     /// Create a new grid layouter.
     ///
     /// This prepares grid layout by unifying content and gutter tracks.
-    #[allow(clippy::too_many_arguments)]
     pub fn new(
         grid: &'a CellGrid,
         stroke: &'a Option<FixedStroke>,
@@ -350,84 +1436,607 @@ impl<'a> GridLayouter<'a> {
             lrows: vec![],
             initial: regions.size,
             finished: vec![],
+            header_height: Abs::zero(),
+            header_rows: 0,
+            repeated: vec![],
+            in_header: false,
+            repeating: false,
+            balance: false,
+            min_rows: 1,
+            stroke_mode: StrokeMode::Grid,
+            hairline: Abs::zero(),
+            header_stroke: None,
+            modes: vec![None; grid.cols.len()],
+            track_overflow: false,
+            overflowed: vec![],
             span,
         }
     }
 
-    /// Determines the columns sizes and then layouts the grid row-by-row.
-    pub fn layout(mut self, engine: &mut Engine) -> SourceResult<Fragment> {
-        self.measure_columns(engine)?;
+    /// Sets whether lines are drawn between cells or around each cell.
+    pub fn with_stroke_mode(mut self, stroke_mode: StrokeMode) -> Self {
+        self.stroke_mode = stroke_mode;
+        self
+    }
+
+    /// Distributes the width taken by the auto columns as evenly as possible
+    /// among them.
+    pub fn with_balance(mut self, balance: bool) -> Self {
+        self.balance = balance;
+        self
+    }
+
+    /// Keeps at least the given number of content rows together on either
+    /// side of a region break.
+    pub fn with_min_rows(mut self, min_rows: usize) -> Self {
+        self.min_rows = min_rows;
+        self
+    }
+
+    /// Draws lines thinner than the given thickness with that thickness, so
+    /// that they remain visible at low resolutions.
+    pub fn with_hairline(mut self, hairline: Abs) -> Self {
+        self.hairline = hairline;
+        self
+    }
+
+    /// Draws a rule with the given stroke between the header and the rows
+    /// below it in each region, over the lines otherwise drawn there.
+    pub fn with_header_stroke(mut self, stroke: Option<FixedStroke>) -> Self {
+        self.header_stroke = stroke;
+        self
+    }
+
+    /// Sets how the automatically sized columns deal with content that
+    /// doesn't fit. The modes are given by column, not counting gutter
+    /// columns, and default to sharing the space fairly.
+    pub fn with_column_modes(mut self, modes: &[Option<ColumnMode>]) -> Self {
+        let len = self.grid.cols.len();
+        for (t, mode) in self.modes.iter_mut().enumerate() {
+            let t = if self.grid.is_rtl { len - 1 - t } else { t };
+            if self.grid.has_gutter && t % 2 == 1 {
+                continue;
+            }
+            let x = if self.grid.has_gutter { t / 2 } else { t };
+            *mode = modes.get(x).copied().flatten();
+        }
+        self
+    }
+
+    /// Determines the columns sizes and then layouts the grid row-by-row.
+    pub fn layout(mut self, engine: &mut Engine) -> SourceResult<Fragment> {
+        self.layout_grid(engine)?;
+        Ok(Fragment::frames(self.finished))
+    }
+
+    /// Layouts the grid like [`layout`](Self::layout), but also returns the
+    /// logical positions of the cells whose content did not fit into them,
+    /// ordered by row.
+    pub fn layout_with_overflow(
+        mut self,
+        engine: &mut Engine,
+    ) -> SourceResult<(Fragment, Vec<Axes<usize>>)> {
+        self.track_overflow = true;
+        self.layout_grid(engine)?;
+        self.overflowed.sort_by_key(|pos| (pos.y, pos.x));
+        Ok((Fragment::frames(self.finished), self.overflowed))
+    }
+
+    /// Determines the columns sizes and then layouts and renders the rows.
+    fn layout_grid(&mut self, engine: &mut Engine) -> SourceResult<()> {
+        self.measure_columns(engine)?;
+
+        let header_end = self.grid.header.as_ref().map_or(0, |header| header.end);
+        let repeat_header = self.grid.header.as_ref().map_or(false, |h| h.repeat);
+        let grid = self.grid;
+        let repeated_rows = grid.header.as_ref().map_or(&[][..], |h| h.rows.as_slice());
+
+        for y in 0..self.grid.rows.len() {
+            // Skip to next region if current one is full, but only for content
+            // rows, not for gutter rows, and only if there is more in this
+            // region than a repeated header.
+            if self.regions.is_full()
+                && (!self.grid.has_gutter || y % 2 == 0)
+                && (self.header_rows == 0 || self.lrows.len() > self.header_rows)
+            {
+                self.finish_region(engine)?;
+            }
+
+            // Move rows to the next region early instead of leaving too few
+            // of them on either side of a region break.
+            if self.min_rows > 1 && (!self.grid.has_gutter || y % 2 == 0) {
+                self.keep_rows_together(engine, y, header_end)?;
+            }
+
+            // Move a group of kept rows to the next region as a whole if it
+            // doesn't fit into this one.
+            if let Some(group) = grid.kept.iter().find(|group| group.rows.start == y) {
+                self.keep_group_together(engine, group, header_end)?;
+            }
+
+            self.in_header = y < header_end;
+            self.layout_row(engine, y)?;
+
+            // Once the header is complete, remember its height so that rows
+            // in followup regions can take the repeated header into account.
+            if repeat_header && y + 1 == header_end {
+                self.header_height = self
+                    .lrows
+                    .iter()
+                    .map(|row| match row {
+                        Row::Frame(frame, _) => frame.height(),
+                        Row::Fr(..) => Abs::zero(),
+                    })
+                    .sum();
+            }
+
+            // Further rows are repeated along with the header once they have
+            // been laid out in full.
+            if repeat_header && repeated_rows.contains(&y) {
+                self.header_height += self.measure_row(engine, y)?;
+                self.repeated.push(y);
+            }
+        }
+
+        self.in_header = false;
+        self.finish_rows(engine)?;
+
+        self.render_fills_strokes(engine)
+    }
+
+    /// Layout the row with the given index.
+    fn layout_row(&mut self, engine: &mut Engine, y: usize) -> SourceResult<()> {
+        match self.grid.rows[y] {
+            Sizing::Auto | Sizing::Content(_) | Sizing::WeightedAuto => {
+                self.layout_auto_row(engine, y)?
+            }
+            Sizing::Rel(v) => self.layout_relative_row(engine, v, y)?,
+            Sizing::Fr(v) => self.lrows.push(Row::Fr(v, y)),
+        }
+
+        Ok(())
+    }
+
+    /// Finish the current region before the content row `y` if laying it out
+    /// here would leave fewer than `min_rows` body rows on either side of a
+    /// region break. Body rows are the rows from `start` on, i.e. those after
+    /// the header.
+    ///
+    /// Rows are measured as if they weren't broken across regions. Nothing
+    /// happens if the body has fewer than `min_rows` rows in total.
+    fn keep_rows_together(
+        &mut self,
+        engine: &mut Engine,
+        y: usize,
+        start: usize,
+    ) -> SourceResult<()> {
+        let step = if self.grid.has_gutter { 2 } else { 1 };
+        let len = self.grid.rows.len();
+        let total = (len.saturating_sub(start) + step - 1) / step;
+        if total < self.min_rows || self.regions.in_last() {
+            return Ok(());
+        }
+
+        // The grid starts in this region after other content. Move it to
+        // the next region if the header and the first rows don't fit.
+        if y == 0 && self.regions.size.y < self.regions.full {
+            let fitting = self.count_fitting_rows(engine, y, start, self.min_rows)?;
+            if fitting < self.min_rows {
+                self.finish_region(engine)?;
+            }
+            return Ok(());
+        }
+
+        // Keep enough rows for the next region, but only if this region
+        // keeps enough as well.
+        if y < start {
+            return Ok(());
+        }
+
+        let remaining = (len - y + step - 1) / step;
+        if remaining > self.min_rows {
+            return Ok(());
+        }
+
+        let placed = self
+            .lrows
+            .iter()
+            .map(|row| match row {
+                Row::Frame(_, y) | Row::Fr(_, y) => *y,
+            })
+            .filter(|&y| y >= start && (!self.grid.has_gutter || y % 2 == 0))
+            .count();
+
+        if placed >= self.min_rows
+            && self.count_fitting_rows(engine, y, start, remaining)? < remaining
+        {
+            self.finish_region(engine)?;
+        }
+
+        Ok(())
+    }
+
+    /// Finish the current region before a group of kept rows if the group
+    /// doesn't fit into the rest of it, but into the next region. A group
+    /// that doesn't fit into the next region either is broken across regions
+    /// like any other rows, with a warning. Body rows are the rows from
+    /// `start` on, i.e. those after the header.
+    ///
+    /// Rows are measured as if they weren't broken across regions.
+    fn keep_group_together(
+        &mut self,
+        engine: &mut Engine,
+        group: &KeptRows,
+        start: usize,
+    ) -> SourceResult<()> {
+        let mut height = Abs::zero();
+        for y in group.rows.clone() {
+            height += self.measure_row(engine, y)?;
+        }
+
+        if self.regions.size.y.fits(height) {
+            return Ok(());
+        }
+
+        // A repeated header takes up space in the next region as well.
+        let next = self.regions.iter().nth(1).map(|size| size.y - self.header_height);
+        if !self.regions.in_last() && next.map_or(false, |next| next.fits(height)) {
+            // Moving on only helps if this region already has body rows or
+            // the grid starts in it after other content.
+            let has_body = self.lrows[self.header_rows..].iter().any(|row| match row {
+                Row::Frame(_, y) | Row::Fr(_, y) => *y >= start,
+            });
+            let after_content =
+                self.lrows.is_empty() && self.regions.size.y < self.regions.full;
+            if has_body || after_content {
+                self.finish_region(engine)?;
+            }
+        } else {
+            engine.tracer.warn(warning!(
+                group.span, "rows kept together don't fit on one page";
+                hint: "they are split across pages instead"
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Count how many body rows, starting at row `y`, fit into the rest of
+    /// the current region, up to `limit`. Other rows in between are measured,
+    /// but not counted.
+    fn count_fitting_rows(
+        &mut self,
+        engine: &mut Engine,
+        y: usize,
+        start: usize,
+        limit: usize,
+    ) -> SourceResult<usize> {
+        let mut height = Abs::zero();
+        let mut count = 0;
+        for y in y..self.grid.rows.len() {
+            height += self.measure_row(engine, y)?;
+            if !self.regions.size.y.fits(height) {
+                break;
+            }
 
-        for y in 0..self.grid.rows.len() {
-            // Skip to next region if current one is full, but only for content
-            // rows, not for gutter rows.
-            if self.regions.is_full() && (!self.grid.has_gutter || y % 2 == 0) {
-                self.finish_region(engine)?;
+            if y >= start && (!self.grid.has_gutter || y % 2 == 0) {
+                count += 1;
+                if count >= limit {
+                    break;
+                }
             }
+        }
+
+        Ok(count)
+    }
 
-            match self.grid.rows[y] {
-                Sizing::Auto => self.layout_auto_row(engine, y)?,
-                Sizing::Rel(v) => self.layout_relative_row(engine, v, y)?,
-                Sizing::Fr(v) => self.lrows.push(Row::Fr(v, y)),
+    /// Measure the height of a row as if it was laid out into a single
+    /// region.
+    fn measure_row(&mut self, engine: &mut Engine, y: usize) -> SourceResult<Abs> {
+        match self.grid.rows[y] {
+            Sizing::Auto | Sizing::Content(_) | Sizing::WeightedAuto => {
+                let mut height = Abs::zero();
+                for x in 0..self.rcols.len() {
+                    let Some(cell) = self.cell(x, y) else { continue };
+                    let width = self.layout_extent(x, y, cell).1;
+                    let size = Size::new(width, self.regions.base().y);
+                    let pod = Regions::one(size, Axes::splat(false));
+                    let frame = cell.measure(engine, self.styles, pod)?.into_frame();
+                    height.set_max(content_height(&frame));
+                }
+                Ok(height)
+            }
+            Sizing::Rel(v) => {
+                Ok(v.resolve(self.styles).relative_to(self.regions.base().y))
             }
+            Sizing::Fr(_) => Ok(Abs::zero()),
         }
+    }
 
-        self.finish_region(engine)?;
+    /// Layout the header rows at the top of the current region, followed by
+    /// the further repeated rows laid out so far in their original order.
+    fn layout_header(&mut self, engine: &mut Engine, end: usize) -> SourceResult<()> {
+        let in_header = std::mem::replace(&mut self.in_header, true);
+        self.repeating = true;
+        for y in 0..end {
+            self.layout_row(engine, y)?;
+        }
+        self.repeating = false;
 
-        self.render_fills_strokes()?;
+        for i in 0..self.repeated.len() {
+            self.layout_row(engine, self.repeated[i])?;
+        }
 
-        Ok(Fragment::frames(self.finished))
+        self.in_header = in_header;
+        self.header_rows = self.lrows.len();
+
+        Ok(())
     }
 
     /// Add lines and backgrounds.
-    fn render_fills_strokes(&mut self) -> SourceResult<()> {
+    fn render_fills_strokes(&mut self, engine: &mut Engine) -> SourceResult<()> {
+        let hairline = self.hairline;
         for (frame, rows) in self.finished.iter_mut().zip(&self.rrows) {
             if self.rcols.is_empty() || rows.is_empty() {
                 continue;
             }
 
+            let height: Abs = rows.iter().map(|piece| piece.height).sum();
+            let header = self.grid.header_height(rows);
+
+            // Render the rule below the (repeated) header first, so that it
+            // ends up above the other lines.
+            if let Some(stroke) = &self.header_stroke {
+                if header > Abs::zero() && header < height {
+                    let stroke = thicken(stroke, hairline);
+                    let rule = Geometry::Line(Point::with_x(frame.width()));
+                    frame.prepend(
+                        Point::with_y(header),
+                        FrameItem::Shape(rule.stroked(stroke), self.span),
+                    );
+                }
+            }
+
             // Render table lines.
-            if let Some(stroke) = self.stroke {
+            if let (Some(stroke), StrokeMode::Grid) = (self.stroke, self.stroke_mode) {
+                let stroke = &thicken(stroke, hairline);
                 let thickness = stroke.thickness;
                 let half = thickness / 2.0;
-
-                // Render horizontal lines.
-                for offset in points(rows.iter().map(|piece| piece.height)) {
-                    let target = Point::with_x(frame.width() + thickness);
+                let width = frame.width();
+
+                // Render the outer border as a single closed path, so that its
+                // corners are joined according to the stroke's line join
+                // instead of overlapping.
+                let border = Geometry::Rect(Size::new(width, height));
+                frame.prepend(
+                    Point::zero(),
+                    FrameItem::Shape(border.stroked(stroke.clone()), self.span),
+                );
+
+                // Render inner horizontal lines. They end at the inner edge of
+                // the border to avoid drawing over it.
+                let offsets = points(rows.iter().map(|piece| piece.height));
+                for offset in offsets.skip(1).take(rows.len().saturating_sub(1)) {
+                    let target = Point::with_x(width - thickness);
                     let hline = Geometry::Line(target).stroked(stroke.clone());
                     frame.prepend(
-                        Point::new(-half, offset),
+                        Point::new(half, offset),
                         FrameItem::Shape(hline, self.span),
                     );
                 }
 
-                // Render vertical lines.
-                for offset in points(self.rcols.iter().copied()) {
-                    let target = Point::with_y(frame.height() + thickness);
-                    let vline = Geometry::Line(target).stroked(stroke.clone());
+                // Render inner vertical lines, interrupting them where they
+                // would cross a cell spanning multiple columns.
+                let mut segments = vec![];
+                for (x, offset) in points(self.rcols.iter().copied()).enumerate() {
+                    if x == 0 || x == self.rcols.len() {
+                        continue;
+                    }
+
+                    let mut dy = Abs::zero();
+                    let mut start = Some(Abs::zero());
+                    for row in rows {
+                        if self.grid.is_within_colspan(x, row.y) {
+                            if let Some(from) = start.take() {
+                                segments.push((from, dy));
+                            }
+                        } else if start.is_none() {
+                            start = Some(dy);
+                        }
+                        dy += row.height;
+                    }
+
+                    if let Some(from) = start {
+                        segments.push((from, dy));
+                    }
+
+                    for (from, to) in segments.drain(..) {
+                        // Segments end at the inner edge of the border and
+                        // cover the crossing with inner horizontal lines.
+                        let from = if from == Abs::zero() { half } else { from - half };
+                        let to = if to == height { height - half } else { to + half };
+                        let target = Point::with_y(to - from);
+                        let vline = Geometry::Line(target).stroked(stroke.clone());
+                        frame.prepend(
+                            Point::new(offset, from),
+                            FrameItem::Shape(vline, self.span),
+                        );
+                    }
+                }
+            }
+
+            // Render the lines of cells with individual strokes. Each edge
+            // between two cells is drawn by the cell above or to the left of
+            // it, see `resolve_edge` for which stroke it gets.
+            if let (None, StrokeMode::Grid) = (self.stroke, self.stroke_mode) {
+                let mut dy = Abs::zero();
+                for (i, row) in rows.iter().enumerate() {
+                    for x in 0..self.rcols.len() {
+                        let Some(cell) = self.grid.cell(x, row.y) else {
+                            continue;
+                        };
+
+                        let tracks = self.grid.colspan_tracks(x, cell.colspan.get());
+                        let dx: Abs = self.rcols[..tracks.start].iter().sum();
+                        let width: Abs = self.rcols[tracks.clone()].iter().sum();
+                        let height = row.height;
+
+                        // The top and left edges are drawn by the neighbouring
+                        // cells, unless there are none.
+                        let grid = self.grid;
+                        let above = i > 0
+                            && grid.entry_index(tracks.start, rows[i - 1].y).is_some();
+                        let left = tracks.start > 0
+                            && grid.entry_index(tracks.start - 1, row.y).is_some();
+
+                        let right = (tracks.end < self.rcols.len())
+                            .then(|| grid.cell(tracks.end, row.y))
+                            .flatten()
+                            .and_then(|right| right.stroke_overrides.left.clone());
+
+                        let sides = &cell.stroke_overrides;
+                        let mut edges = vec![(
+                            sides.right.clone(),
+                            right,
+                            Point::new(dx + width, dy),
+                            Point::with_y(height),
+                        )];
+
+                        // Below a cell spanning multiple columns, each cell may
+                        // override the top of its part of the bottom edge, so
+                        // the edge is split where the cells below change.
+                        let mut bottom: Vec<(Option<Option<FixedStroke>>, Abs, Abs)> =
+                            vec![];
+                        let mut offset = dx;
+                        for track in tracks.clone() {
+                            let below = rows
+                                .get(i + 1)
+                                .and_then(|next| grid.parent_index(track, next.y));
+                            let over = below
+                                .and_then(|index| grid.entries[index].as_cell())
+                                .and_then(|below| below.stroke_overrides.top.clone());
+                            match bottom.last_mut() {
+                                Some((prev, _, len))
+                                    if below.is_none() || *prev == over =>
+                                {
+                                    *len += self.rcols[track];
+                                }
+                                _ => bottom.push((over, offset, self.rcols[track])),
+                            }
+                            offset += self.rcols[track];
+                        }
+                        for (below, x, len) in bottom {
+                            edges.push((
+                                sides.bottom.clone(),
+                                below,
+                                Point::new(x, dy + height),
+                                Point::with_x(len),
+                            ));
+                        }
+
+                        let origin = Point::new(dx, dy);
+                        if !above {
+                            let top = sides.top.clone();
+                            edges.push((top, None, origin, Point::with_x(width)));
+                        }
+                        if !left {
+                            let left = sides.left.clone();
+                            edges.push((left, None, origin, Point::with_y(height)));
+                        }
+
+                        for (own, facing, pos, target) in edges {
+                            let Some(stroke) = resolve_edge(own, facing, &cell.stroke)
+                            else {
+                                continue;
+                            };
+                            let stroke = thicken(&stroke, hairline);
+                            let line = Geometry::Line(target).stroked(stroke);
+                            frame.prepend(pos, FrameItem::Shape(line, self.span));
+                        }
+                    }
+                    dy += row.height;
+                }
+            }
+
+            // Render a separate border around each cell.
+            if self.stroke_mode == StrokeMode::Cells {
+                for x in 0..self.rcols.len() {
+                    let mut dy = Abs::zero();
+                    for row in rows {
+                        let cell = self.grid.cell(x, row.y);
+                        let stroke = cell.and_then(|cell| cell.stroke.as_ref());
+                        if let (Some(cell), Some(stroke)) =
+                            (cell, self.stroke.as_ref().or(stroke))
+                        {
+                            let tracks = self.grid.colspan_tracks(x, cell.colspan.get());
+                            let dx = self.rcols[..tracks.start].iter().sum();
+                            let width = self.rcols[tracks].iter().sum();
+                            let size = Size::new(width, row.height);
+                            let stroke = thicken(stroke, hairline);
+                            let rect = Geometry::Rect(size).stroked(stroke);
+                            let pos = Point::new(dx, dy);
+                            frame.prepend(pos, FrameItem::Shape(rect, self.span));
+                        }
+                        dy += row.height;
+                    }
+                }
+            }
+
+            // Render the rules of an open table. Each region is closed at its
+            // top and bottom, a (repeated) header is separated by a lighter
+            // rule.
+            if let (Some(stroke), StrokeMode::Booktabs) = (self.stroke, self.stroke_mode)
+            {
+                let stroke = &thicken(stroke, hairline);
+                let width = frame.width();
+                let half = stroke.thickness / 2.0;
+                let rule = Geometry::Line(Point::with_x(width));
+
+                // A header stroke replaces the lighter rule.
+                if header > Abs::zero() && header < height && self.header_stroke.is_none()
+                {
+                    let mut thin = stroke.clone();
+                    thin.thickness = half.max(hairline);
+                    frame.prepend(
+                        Point::with_y(header),
+                        FrameItem::Shape(rule.clone().stroked(thin), self.span),
+                    );
+                }
+
+                let thick = rule.stroked(stroke.clone());
+                for y in [half, height - half] {
                     frame.prepend(
-                        Point::new(offset, -half),
-                        FrameItem::Shape(vline, self.span),
+                        Point::with_y(y),
+                        FrameItem::Shape(thick.clone(), self.span),
                     );
                 }
             }
 
-            // Render cell backgrounds.
-            let mut dx = Abs::zero();
-            for (x, &col) in self.rcols.iter().enumerate() {
+            // Render cell backgrounds, with the background content above the
+            // fill and below the lines.
+            for x in 0..self.rcols.len() {
                 let mut dy = Abs::zero();
                 for row in rows {
-                    let fill =
-                        self.grid.cell(x, row.y).and_then(|cell| cell.fill.clone());
-                    if let Some(fill) = fill {
+                    // Cells on other layers draw their backgrounds themselves.
+                    let cell = self.grid.cell(x, row.y).filter(|cell| cell.layer == 0);
+                    if let Some(cell) = cell {
+                        let tracks = self.grid.colspan_tracks(x, cell.colspan.get());
+                        let dx = self.rcols[..tracks.start].iter().sum();
+                        let width = self.rcols[tracks].iter().sum();
                         let pos = Point::new(dx, dy);
-                        let size = Size::new(col, row.height);
-                        let rect = Geometry::Rect(size).filled(fill);
-                        frame.prepend(pos, FrameItem::Shape(rect, self.span));
+                        let size = Size::new(width, row.height);
+                        if let Some(background) = &cell.background {
+                            let background =
+                                layout_background(engine, background, size, self.styles)?;
+                            frame.prepend_frame(pos, background);
+                        }
+                        if let Some(fill) = cell.fill.clone() {
+                            let rect = Geometry::Rect(size).filled(fill);
+                            frame.prepend(pos, FrameItem::Shape(rect, self.span));
+                        }
                     }
                     dy += row.height;
                 }
-                dx += col;
             }
         }
 
@@ -446,7 +2055,7 @@ impl<'a> GridLayouter<'a> {
         // fractional tracks.
         for (&col, rcol) in self.grid.cols.iter().zip(&mut self.rcols) {
             match col {
-                Sizing::Auto => {}
+                Sizing::Auto | Sizing::Content(_) | Sizing::WeightedAuto => {}
                 Sizing::Rel(v) => {
                     let resolved =
                         v.resolve(self.styles).relative_to(self.regions.base().x);
@@ -460,16 +2069,27 @@ impl<'a> GridLayouter<'a> {
         // Size that is not used by fixed-size columns.
         let available = self.regions.size.x - rel;
         if available >= Abs::zero() {
-            // Determine size of auto columns.
-            let (auto, count) = self.measure_auto_columns(engine, available)?;
+            // Determine size of auto columns. Without any, the cells don't
+            // need to be visited at all, which saves time for large tables
+            // with fixed and fractional columns.
+            let (auto, floors) = if self.grid.cols.iter().any(|col| col.fits_content()) {
+                self.measure_auto_columns(engine, available)?
+            } else {
+                (Abs::zero(), vec![])
+            };
 
-            // If there is remaining space, distribute it to fractional columns,
-            // otherwise shrink auto columns.
+            // If there is remaining space, distribute it to fractional columns
+            // or, if there are none, to weighted auto columns. Otherwise,
+            // shrink auto columns.
             let remaining = available - auto;
             if remaining >= Abs::zero() {
-                self.grow_fractional_columns(remaining, fr);
+                if fr.is_zero() {
+                    self.grow_weighted_columns(remaining);
+                } else {
+                    self.grow_fractional_columns(remaining, fr);
+                }
             } else {
-                self.shrink_auto_columns(available, count);
+                self.shrink_auto_columns(available, &floors);
             }
         }
 
@@ -480,46 +2100,154 @@ impl<'a> GridLayouter<'a> {
     }
 
     /// Measure the size that is available to auto columns.
+    ///
+    /// Also returns the minimum widths of cells spanning a single column,
+    /// which are kept when shrinking.
     fn measure_auto_columns(
         &mut self,
         engine: &mut Engine,
         available: Abs,
-    ) -> SourceResult<(Abs, usize)> {
+    ) -> SourceResult<(Abs, Vec<Abs>)> {
         let mut auto = Abs::zero();
-        let mut count = 0;
+
+        // The columns in logical order.
+        let columns = self.logical_columns();
+        for &x in &columns {
+            if self.grid.cols[x].fits_content() {
+                self.rcols[x] = Abs::zero();
+            }
+        }
+
+        // Cells spanning multiple columns including an auto column, with the
+        // range of tracks they span and their measured width.
+        let mut spanning = vec![];
+
+        // The minimum content widths of the auto columns, only measured when
+        // balancing.
+        let mut mins = vec![Abs::zero(); self.rcols.len()];
+
+        // The largest minimum widths of cells in each column.
+        let mut floors = vec![Abs::zero(); self.rcols.len()];
 
         // Determine size of auto columns by laying out all cells in those
-        // columns, measuring them and finding the largest one.
-        for (x, &col) in self.grid.cols.iter().enumerate() {
-            if col != Sizing::Auto {
-                continue;
+        // columns, measuring them and finding the largest one. We measure
+        // row-by-row so that cells are visited in the same order in which
+        // they are laid out afterwards.
+        for y in 0..self.grid.rows.len() {
+            // For relative rows, we can already resolve the correct base and
+            // for auto and fr we could only guess anyway.
+            let height = match self.grid.rows[y] {
+                Sizing::Rel(v) => {
+                    v.resolve(self.styles).relative_to(self.regions.base().y)
+                }
+                _ => self.regions.base().y,
+            };
+
+            for &x in &columns {
+                let Some(cell) = self.grid.cell(x, y) else { continue };
+                let tracks = self.grid.colspan_tracks(x, cell.colspan.get());
+                if !self.grid.cols[tracks.clone()].iter().any(|col| col.fits_content()) {
+                    continue;
+                }
+
+                // Cells spanning multiple columns may additionally use the
+                // space of the relative columns they span.
+                let fixed: Abs = tracks
+                    .clone()
+                    .filter(|&t| !self.grid.cols[t].fits_content())
+                    .map(|t| self.rcols[t])
+                    .sum();
+
+                let size = Size::new(available + fixed, height);
+                let pod = Regions::one(size, Axes::splat(false));
+                let frame = cell.measure(engine, self.styles, pod)?.into_frame();
+
+                // Cells with negative insets may measure to a negative size,
+                // which must not shrink the tracks.
+                let width = frame.width().max(cell.min_width).max(Abs::zero());
+                if tracks.len() == 1 {
+                    self.rcols[x].set_max(width);
+                    floors[x].set_max(cell.min_width);
+
+                    // The narrowest the cell can get without overflowing,
+                    // e.g. its longest word.
+                    if self.balance {
+                        let pod = Regions::one(Size::with_y(height), Axes::splat(false));
+                        let frame = cell.measure(engine, self.styles, pod)?.into_frame();
+                        mins[x].set_max(frame.width().max(cell.min_width));
+                    }
+                } else {
+                    spanning.push((tracks, width));
+                }
             }
+        }
 
-            let mut resolved = Abs::zero();
-            for y in 0..self.grid.rows.len() {
-                if let Some(cell) = self.grid.cell(x, y) {
-                    // For relative rows, we can already resolve the correct
-                    // base and for auto and fr we could only guess anyway.
-                    let height = match self.grid.rows[y] {
-                        Sizing::Rel(v) => {
-                            v.resolve(self.styles).relative_to(self.regions.base().y)
-                        }
-                        _ => self.regions.base().y,
-                    };
+        // Scale the columns fitting a share of their contents, but keep the
+        // minimum widths of their cells.
+        for &x in &columns {
+            if let Sizing::Content(ratio) = self.grid.cols[x] {
+                self.rcols[x] = ratio.of(self.rcols[x]).max(floors[x]);
+            }
+        }
 
-                    let size = Size::new(available, height);
-                    let pod = Regions::one(size, Axes::splat(false));
-                    let frame = cell.measure(engine, self.styles, pod)?.into_frame();
-                    resolved.set_max(frame.width());
+        // Grow the auto columns spanned by cells spanning multiple columns
+        // if they are not large enough yet. The extra space is given to the
+        // last spanned auto column.
+        for (tracks, width) in spanning {
+            let current: Abs = self.rcols[tracks.clone()].iter().sum();
+            let mut autos = tracks.filter(|&t| self.grid.cols[t].fits_content());
+            let target = if self.grid.is_rtl { autos.next() } else { autos.last() };
+            if let Some(t) = target {
+                if width > current {
+                    self.rcols[t] += width - current;
                 }
             }
+        }
+
+        if self.balance {
+            self.balance_auto_columns(available, &mins);
+        }
+
+        for (&col, &rcol) in self.grid.cols.iter().zip(&self.rcols) {
+            if col.fits_content() {
+                auto += rcol;
+            }
+        }
+
+        Ok((auto, floors))
+    }
+
+    /// Redistribute the width taken by auto columns so that they are as equal
+    /// as possible, while each column stays at least as wide as its minimum
+    /// content width. This minimizes the variance of the column widths.
+    ///
+    /// The total width stays the same, but is capped at the available space.
+    fn balance_auto_columns(&mut self, available: Abs, mins: &[Abs]) {
+        let mut columns: Vec<usize> = (0..self.rcols.len())
+            .filter(|&x| self.grid.cols[x] == Sizing::Auto)
+            .collect();
+
+        let total: Abs = columns.iter().map(|&x| self.rcols[x]).sum();
+        let mut remaining = total.min(available);
+
+        // Columns whose minimum exceeds the fair share keep their minimum,
+        // the remaining space is shared equally among the rest.
+        columns.sort_by(|&a, &b| mins[b].cmp(&mins[a]));
+        let mut rest = columns.as_slice();
+        while let [x, tail @ ..] = rest {
+            let fair = remaining / rest.len() as f64;
+            if mins[*x] <= fair {
+                break;
+            }
 
-            self.rcols[x] = resolved;
-            auto += resolved;
-            count += 1;
+            self.rcols[*x] = mins[*x];
+            remaining -= mins[*x];
+            rest = tail;
         }
 
-        Ok((auto, count))
+        for &x in rest {
+            self.rcols[x] = remaining / rest.len() as f64;
+        }
     }
 
     /// Distribute remaining space to fractional columns.
@@ -535,12 +2263,67 @@ impl<'a> GridLayouter<'a> {
         }
     }
 
-    /// Redistribute space to auto columns so that each gets a fair share.
-    fn shrink_auto_columns(&mut self, available: Abs, count: usize) {
+    /// Distribute remaining space to weighted auto columns in proportion to
+    /// their content widths. If none of them has any content, they share it
+    /// equally.
+    fn grow_weighted_columns(&mut self, remaining: Abs) {
+        if !remaining.is_finite() {
+            return;
+        }
+
+        let columns: Vec<usize> = (0..self.rcols.len())
+            .filter(|&x| self.grid.cols[x] == Sizing::WeightedAuto)
+            .collect();
+        if columns.is_empty() {
+            return;
+        }
+
+        let total: Abs = columns.iter().map(|&x| self.rcols[x]).sum();
+        for &x in &columns {
+            self.rcols[x] += if total > Abs::zero() {
+                remaining * (self.rcols[x] / total)
+            } else {
+                remaining / columns.len() as f64
+            };
+        }
+    }
+
+    /// Shrink the auto columns to fit into the available space.
+    ///
+    /// Columns in `Expand` mode keep their width. Columns in `Wrap` mode are
+    /// shrunk first and the remaining ones only if that is not enough.
+    fn shrink_auto_columns(&mut self, available: Abs, floors: &[Abs]) {
+        let mut available = available;
+        let mut wrapping = vec![];
+        let mut others = vec![];
+        for x in 0..self.rcols.len() {
+            if !self.grid.cols[x].fits_content() {
+                continue;
+            }
+            match self.modes[x] {
+                Some(ColumnMode::Expand) => available -= self.rcols[x],
+                Some(ColumnMode::Wrap) => wrapping.push(x),
+                None => others.push(x),
+            }
+        }
+
+        let rest: Abs = others.iter().map(|&x| self.rcols[x]).sum();
+        self.share_fairly(&wrapping, (available - rest).max(Abs::zero()), floors);
+
+        let wrapped: Abs = wrapping.iter().map(|&x| self.rcols[x]).sum();
+        self.share_fairly(&others, available - wrapped, floors);
+    }
+
+    /// Redistribute space to the given columns so that each gets a fair
+    /// share.
+    ///
+    /// Columns don't shrink below their floor, even if the grid then exceeds
+    /// the available space.
+    fn share_fairly(&mut self, columns: &[usize], available: Abs, floors: &[Abs]) {
         let mut last;
         let mut fair = -Abs::inf();
         let mut redistribute = available;
-        let mut overlarge = count;
+        let mut overlarge = columns.len();
         let mut changed = true;
 
         // Iteratively remove columns that don't need to be shrunk.
@@ -549,10 +2332,11 @@ impl<'a> GridLayouter<'a> {
             last = fair;
             fair = redistribute / (overlarge as f64);
 
-            for (&col, &rcol) in self.grid.cols.iter().zip(&self.rcols) {
-                // Remove an auto column if it is not overlarge (rcol <= fair),
-                // but also hasn't already been removed (rcol > last).
-                if col == Sizing::Auto && rcol <= fair && rcol > last {
+            for &x in columns {
+                // Remove a column if it is not overlarge (rcol <= fair), but
+                // also hasn't already been removed (rcol > last).
+                let rcol = self.rcols[x];
+                if rcol <= fair && rcol > last {
                     redistribute -= rcol;
                     overlarge -= 1;
                     changed = true;
@@ -561,9 +2345,9 @@ impl<'a> GridLayouter<'a> {
         }
 
         // Redistribute space fairly among overlarge columns.
-        for (&col, rcol) in self.grid.cols.iter().zip(&mut self.rcols) {
-            if col == Sizing::Auto && *rcol > fair {
-                *rcol = fair;
+        for &x in columns {
+            if self.rcols[x] > fair {
+                self.rcols[x] = fair.max(floors[x]);
             }
         }
     }
@@ -595,14 +2379,17 @@ impl<'a> GridLayouter<'a> {
 
         // Expand all but the last region.
         // Skip the first region if the space is eaten up by an fr row.
+        // Followup regions lose the space taken by a repeated header.
         let len = resolved.len();
-        for (region, target) in self
+        let header_height = self.repeated_header_height();
+        for (i, (region, target)) in self
             .regions
             .iter()
             .zip(&mut resolved[..len - 1])
+            .enumerate()
             .skip(self.lrows.iter().any(|row| matches!(row, Row::Fr(..))) as usize)
         {
-            target.set_max(region.y);
+            target.set_max(if i > 0 { region.y - header_height } else { region.y });
         }
 
         // Layout into multiple regions.
@@ -628,10 +2415,41 @@ impl<'a> GridLayouter<'a> {
     ) -> SourceResult<Option<Vec<Abs>>> {
         let mut resolved: Vec<Abs> = vec![];
 
-        for (x, &rcol) in self.rcols.iter().enumerate() {
-            if let Some(cell) = self.grid.cell(x, y) {
+        // Followup regions lose the space taken by a repeated header.
+        let header_height = self.repeated_header_height();
+        let backlog: Vec<Abs> =
+            self.regions.backlog.iter().map(|&h| h - header_height).collect();
+
+        for x in self.logical_columns() {
+            if let Some(cell) = self.cell(x, y) {
+                // A rotated body is laid out with the row's height as its
+                // width. Measure it against the full region so that it isn't
+                // wrapped just because the region is partially used up. If it
+                // doesn't fit into the rest of the region, skip the region.
+                if cell.rotated {
+                    let width = self.cell_extent(x, cell).1;
+                    let size = Size::new(width, self.regions.base().y);
+                    let pod = Regions::one(size, Axes::splat(false));
+                    let frame = cell.measure(engine, self.styles, pod)?.into_frame();
+                    let height = frame.height().max(Abs::zero());
+                    if can_skip
+                        && !self.regions.size.y.fits(height)
+                        && !self.regions.in_last()
+                    {
+                        return Ok(None);
+                    }
+
+                    match resolved.first_mut() {
+                        Some(first) => first.set_max(height),
+                        None => resolved.push(height),
+                    }
+                    continue;
+                }
+
                 let mut pod = self.regions;
-                pod.size.x = rcol;
+                pod.size.x = self.layout_extent(x, y, cell).1;
+                pod.backlog = &backlog;
+                pod.last = self.regions.last.map(|h| h - header_height);
 
                 let frames = cell.measure(engine, self.styles, pod)?.into_frames();
 
@@ -646,7 +2464,8 @@ impl<'a> GridLayouter<'a> {
                     }
                 }
 
-                let mut sizes = frames.iter().map(|frame| frame.height());
+                // Cells with negative insets may be smaller than nothing.
+                let mut sizes = frames.iter().map(content_height);
                 for (target, size) in resolved.iter_mut().zip(&mut sizes) {
                     target.set_max(size);
                 }
@@ -680,6 +2499,11 @@ impl<'a> GridLayouter<'a> {
             if self.grid.has_gutter && y % 2 == 1 {
                 return Ok(());
             }
+
+            // Don't produce regions containing only a repeated header.
+            if self.header_rows > 0 && self.lrows.len() == self.header_rows {
+                break;
+            }
         }
 
         self.push_row(frame, y);
@@ -699,22 +2523,25 @@ impl<'a> GridLayouter<'a> {
         }
 
         let mut output = Frame::soft(Size::new(self.width, height));
-        let mut pos = Point::zero();
+        let mut cells = vec![];
 
-        for (x, &rcol) in self.rcols.iter().enumerate() {
-            if let Some(cell) = self.grid.cell(x, y) {
-                let size = Size::new(rcol, height);
+        for x in self.logical_columns() {
+            if let Some(cell) = self.cell(x, y) {
+                let (offset, width) = self.layout_extent(x, y, cell);
+                let size = Size::new(width, height);
                 let mut pod = Regions::one(size, Axes::splat(true));
                 if self.grid.rows[y] == Sizing::Auto {
                     pod.full = self.regions.full;
                 }
-                let frame = cell.layout(engine, self.styles, pod)?.into_frame();
-                output.push_frame(pos, frame);
+                let mut frame = cell.layout(engine, self.styles, pod)?.into_frame();
+                self.check_overflow(engine, x, y, cell, size)?;
+                let dx = self.crop_spilled(x, cell, offset, &mut frame);
+                cells.push((cell, dx, frame));
             }
-
-            pos.x += rcol;
         }
 
+        self.push_cells(engine, &mut output, cells)?;
+
         Ok(output)
     }
 
@@ -738,32 +2565,203 @@ impl<'a> GridLayouter<'a> {
         pod.backlog = &heights[1..];
 
         // Layout the row.
-        let mut pos = Point::zero();
-        for (x, &rcol) in self.rcols.iter().enumerate() {
-            if let Some(cell) = self.grid.cell(x, y) {
-                pod.size.x = rcol;
+        let mut cells = vec![vec![]; outputs.len()];
+        for x in self.logical_columns() {
+            if let Some(cell) = self.cell(x, y) {
+                let (offset, width) = self.layout_extent(x, y, cell);
+                pod.size.x = width;
 
-                // Push the layouted frames into the individual output frames.
+                // Collect the layouted frames for the individual output frames.
                 let fragment = cell.layout(engine, self.styles, pod)?;
-                for (output, frame) in outputs.iter_mut().zip(fragment) {
-                    output.push_frame(pos, frame);
+                let size = Size::new(width, Abs::inf());
+                self.check_overflow(engine, x, y, cell, size)?;
+                for (cells, mut frame) in cells.iter_mut().zip(fragment) {
+                    let dx = self.crop_spilled(x, cell, offset, &mut frame);
+                    cells.push((cell, dx, frame));
                 }
             }
+        }
 
-            pos.x += rcol;
+        for (output, cells) in outputs.iter_mut().zip(cells) {
+            self.push_cells(engine, output, cells)?;
         }
 
         Ok(Fragment::frames(outputs))
     }
 
+    /// Remember the cell at column `x` in row `y` if its content does not fit
+    /// into the given size, but only if overflow is tracked.
+    ///
+    /// The content is measured with unlimited height, so that a cell in a row
+    /// of fixed height overflows when its content is taller than the row and
+    /// any cell overflows when its content is wider than its columns.
+    fn check_overflow(
+        &mut self,
+        engine: &mut Engine,
+        x: usize,
+        y: usize,
+        cell: &Cell,
+        size: Size,
+    ) -> SourceResult<()> {
+        if !self.track_overflow {
+            return Ok(());
+        }
+
+        let pod = Regions::one(Size::new(size.x, Abs::inf()), Axes::splat(false));
+        let natural = cell.measure(engine, self.styles, pod)?.into_frame().size();
+        if size.fits(natural) {
+            return Ok(());
+        }
+
+        let mut pos = Axes::new(x, y);
+        if self.grid.is_rtl {
+            pos.x = self.grid.cols.len() - 1 - pos.x;
+        }
+        if self.grid.has_gutter {
+            pos = pos.map(|v| v / 2);
+        }
+        if !self.overflowed.contains(&pos) {
+            self.overflowed.push(pos);
+        }
+
+        Ok(())
+    }
+
+    /// Push the frames of the cells in a row into the row's frame, ordered by
+    /// their layers. Cells on the same layer stay in logical order.
+    ///
+    /// Only their offsets follow the visual order of the columns. Exporters
+    /// write frames in the order of their items, so text extracted from the
+    /// exported document follows the logical order of the cells.
+    ///
+    /// The fill and background of a cell on a layer other than zero are drawn
+    /// together with the cell, so that they cover cells on lower layers.
+    fn push_cells(
+        &self,
+        engine: &mut Engine,
+        output: &mut Frame,
+        mut cells: Vec<(&Cell, Abs, Frame)>,
+    ) -> SourceResult<()> {
+        cells.sort_by_key(|(cell, ..)| cell.layer);
+        for (cell, dx, mut frame) in cells {
+            if cell.layer != 0 {
+                if let Some(background) = &cell.background {
+                    let background =
+                        layout_background(engine, background, frame.size(), self.styles)?;
+                    frame.prepend_frame(Point::zero(), background);
+                }
+                if let Some(fill) = cell.fill.clone() {
+                    let rect = Geometry::Rect(frame.size()).filled(fill);
+                    frame.prepend(Point::zero(), FrameItem::Shape(rect, self.span));
+                }
+            }
+
+            output.push_frame(Point::with_x(dx), frame);
+        }
+
+        Ok(())
+    }
+
+    /// The indices of all columns in logical order.
+    ///
+    /// Cells are always measured and laid out in logical row-major order,
+    /// even in RTL grids where the columns are visually reversed. This
+    /// guarantees that show rules and introspection (e.g. counters) inside
+    /// of cells observe cells in the order they were specified in.
+    fn logical_columns(&self) -> Vec<usize> {
+        let mut columns: Vec<_> = (0..self.rcols.len()).collect();
+        if self.grid.is_rtl {
+            columns.reverse();
+        }
+
+        columns
+    }
+
+    /// The horizontal offset and width of a cell placed at column `x`,
+    /// taking into account the columns it spans.
+    fn cell_extent(&self, x: usize, cell: &Cell) -> (Abs, Abs) {
+        let tracks = self.grid.colspan_tracks(x, cell.colspan.get());
+        let dx = self.rcols[..tracks.start].iter().sum();
+        let width = self.rcols[tracks].iter().sum();
+        (dx, width)
+    }
+
+    /// The cell in column `x` and row `y` as it is laid out in the current
+    /// region. A header repeated in a followup region shows the continued
+    /// variants of its cells, if they have one.
+    fn cell(&self, x: usize, y: usize) -> Option<&'a Cell> {
+        let grid: &'a CellGrid = self.grid;
+        let cell = grid.cell(x, y)?;
+        match &cell.continued {
+            Some(continued) if self.repeating => Some(continued),
+            _ => Some(cell),
+        }
+    }
+
+    /// The horizontal offset and width of the space a cell at column `x` in
+    /// row `y` is laid out in. This is the cell's own extent unless it spills
+    /// into the empty cells following it.
+    fn layout_extent(&self, x: usize, y: usize, cell: &Cell) -> (Abs, Abs) {
+        if !cell.spill {
+            return self.cell_extent(x, cell);
+        }
+
+        let tracks = self.grid.spill_tracks(x, y, cell.colspan.get());
+        let dx = self.rcols[..tracks.start].iter().sum();
+        let width = self.rcols[tracks].iter().sum();
+        (dx, width)
+    }
+
+    /// Crop the frame of a cell laid out at the given offset in the space
+    /// including the cells it spills into back to the cell's own extent. The
+    /// content stays where it is and overflows the frame. Returns the offset
+    /// at which to place the frame.
+    ///
+    /// Column widths, fills and strokes thus only depend on the cell's own
+    /// extent.
+    fn crop_spilled(&self, x: usize, cell: &Cell, offset: Abs, frame: &mut Frame) -> Abs {
+        let (dx, width) = self.cell_extent(x, cell);
+        if cell.spill {
+            frame.translate(Point::with_x(offset - dx));
+            frame.set_size(Size::new(width, frame.height()));
+        }
+        dx
+    }
+
+    /// The height of the repeated header, which is unavailable to rows in
+    /// followup regions. Zero while the header itself is laid out.
+    fn repeated_header_height(&self) -> Abs {
+        if self.in_header {
+            Abs::zero()
+        } else {
+            self.header_height
+        }
+    }
+
     /// Push a row frame into the current region.
     fn push_row(&mut self, frame: Frame, y: usize) {
         self.regions.size.y -= frame.height();
         self.lrows.push(Row::Frame(frame, y));
     }
 
-    /// Finish rows for one region.
+    /// Finish rows for one region and move on to the next one, repeating the
+    /// header if necessary.
     fn finish_region(&mut self, engine: &mut Engine) -> SourceResult<()> {
+        self.finish_rows(engine)?;
+        self.regions.next();
+        self.initial = self.regions.size;
+
+        if let Some(&Header { end, repeat: true, .. }) = self.grid.header.as_ref() {
+            if !self.in_header {
+                self.layout_header(engine, end)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Finish rows for the current region.
+    fn finish_rows(&mut self, engine: &mut Engine) -> SourceResult<()> {
         // Determine the height of existing rows in the region.
         let mut used = Abs::zero();
         let mut fr = Fr::zero();
@@ -805,13 +2803,43 @@ impl<'a> GridLayouter<'a> {
 
         self.finished.push(output);
         self.rrows.push(rrows);
-        self.regions.next();
-        self.initial = self.regions.size;
+        self.header_rows = 0;
 
         Ok(())
     }
 }
 
+/// The height of a cell's frame, including content placed below its bottom
+/// edge, but at least zero.
+///
+/// Inline equations are laid out with less height than they take up, so that
+/// they don't spread the lines of a paragraph apart. A tall one, like a
+/// fraction with a summation, may thus reach below the last line of a cell
+/// and beyond its inset. Rows are sized to include such content, so that it
+/// isn't cut off or overlapped by the next row.
+///
+/// Text only counts when its baseline is below the edge, so that the
+/// descenders and decorations of the last line of a cell don't change its
+/// height.
+fn content_height(frame: &Frame) -> Abs {
+    let mut height = frame.height().max(Abs::zero());
+    for (pos, item) in frame.items() {
+        let bottom = match item {
+            FrameItem::Group(group)
+                if group.transform.is_identity() && group.clip_path.is_none() =>
+            {
+                pos.y + content_height(&group.frame)
+            }
+            FrameItem::Text(text) if pos.y > frame.height() => {
+                pos.y - text.font.metrics().descender.at(text.size)
+            }
+            _ => continue,
+        };
+        height.set_max(bottom);
+    }
+    height
+}
+
 /// Turn an iterator of extents into an iterator of offsets before, in between,
 /// and after the extents, e.g. [10mm, 5mm] -> [0mm, 10mm, 15mm].
 fn points(extents: impl IntoIterator<Item = Abs>) -> impl Iterator<Item = Abs> {
@@ -821,3 +2849,164 @@ fn points(extents: impl IntoIterator<Item = Abs>) -> impl Iterator<Item = Abs> {
         offset
     })
 }
+
+/// Resolves the stroke of an edge drawn by a cell from all sources claiming
+/// it. In order of priority, these are:
+///
+/// 1. The cell's override for the side of the edge.
+/// 2. The override for the facing side of the cell on the other side of the
+///    edge, if there is one.
+/// 3. The cell's stroke, as given by the grid's stroke for its position.
+///
+/// An override of `none` removes the edge, even if a source of lower priority
+/// would draw it.
+fn resolve_edge(
+    own: Option<Option<FixedStroke>>,
+    facing: Option<Option<FixedStroke>>,
+    stroke: &Option<FixedStroke>,
+) -> Option<FixedStroke> {
+    own.or(facing).unwrap_or_else(|| stroke.clone())
+}
+
+/// Lays out the background content of a cell into the cell's size and clips
+/// it to the cell.
+fn layout_background(
+    engine: &mut Engine,
+    background: &Content,
+    size: Size,
+    styles: StyleChain,
+) -> SourceResult<Frame> {
+    let pod = Regions::one(size, Axes::splat(true));
+    let mut frame = background.layout(engine, styles, pod)?.into_frame();
+    frame.clip(Path::rect(size));
+    Ok(frame)
+}
+
+/// Thickens a stroke to at least the given thickness.
+fn thicken(stroke: &FixedStroke, min: Abs) -> FixedStroke {
+    let mut stroke = stroke.clone();
+    stroke.thickness.set_max(min);
+    stroke
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::foundations::{elem, Fold};
+    use crate::layout::{HAlign, VAlign};
+    use crate::visualize::Color;
+
+    /// A cell defined outside of the grid and table elements.
+    #[elem]
+    pub struct CustomCell {
+        /// The cell's body.
+        #[required]
+        pub body: Content,
+
+        /// The cell's fill, overriding the grid's one.
+        pub fill: Smart<Option<Paint>>,
+
+        /// The cell's alignment, folded with the grid's one.
+        pub align: Smart<Align>,
+
+        /// The amount of columns spanned by the cell.
+        #[default(NonZeroUsize::ONE)]
+        pub colspan: NonZeroUsize,
+    }
+
+    impl Default for CustomCell {
+        fn default() -> Self {
+            Self::new(Content::default())
+        }
+    }
+
+    impl ResolvableCell for CustomCell {
+        fn resolve_cell(
+            mut self,
+            _: usize,
+            _: usize,
+            fill: &Option<Paint>,
+            align: Smart<Align>,
+            _: Sides<Rel<Length>>,
+            styles: StyleChain,
+        ) -> Cell {
+            let fill = self.fill(styles).unwrap_or_else(|| fill.clone());
+            self.push_fill(Smart::Custom(fill.clone()));
+            self.push_align(match (self.align(styles), align) {
+                (Smart::Custom(inner), Smart::Custom(outer)) => {
+                    Smart::Custom(inner.fold(outer))
+                }
+                (inner, outer) => inner.or(outer),
+            });
+
+            let colspan = self.colspan(styles);
+            Cell { colspan, fill, ..Cell::from(self.pack()) }
+        }
+
+        fn colspan(&self, styles: StyleChain) -> NonZeroUsize {
+            self.colspan(styles)
+        }
+
+        fn body(&self) -> &Content {
+            self.body()
+        }
+
+        fn set_body(&mut self, body: Content) {
+            self.push_body(body);
+        }
+    }
+
+    #[test]
+    fn test_custom_cell_resolves_grid_properties() {
+        let styles = StyleChain::default();
+        let red: Paint = Color::RED.into();
+        let cell = CustomCell::new(TextElem::packed("A"))
+            .with_align(Smart::Custom(Align::RIGHT))
+            .resolve_cell(
+                0,
+                0,
+                &Some(red.clone()),
+                Smart::Custom(Align::BOTTOM),
+                Sides::default(),
+                styles,
+            );
+
+        assert_eq!(cell.fill, Some(red.clone()));
+        assert_eq!(cell.colspan, NonZeroUsize::ONE);
+        let elem = cell.body.to::<CustomCell>().unwrap();
+        assert_eq!(elem.fill(styles), Smart::Custom(Some(red)));
+        let align = Align::Both(HAlign::Right, VAlign::Bottom);
+        assert_eq!(elem.align(styles), Smart::Custom(align));
+    }
+
+    #[test]
+    fn test_custom_cell_overrides_fill() {
+        let styles = StyleChain::default();
+        let fill = Some(Color::RED.into());
+        let cell = CustomCell::new(Content::default())
+            .with_fill(Smart::Custom(None))
+            .with_colspan(NonZeroUsize::new(2).unwrap())
+            .resolve_cell(1, 0, &fill, Smart::Auto, Sides::default(), styles);
+
+        assert_eq!(cell.fill, None);
+        assert_eq!(cell.colspan.get(), 2);
+    }
+
+    #[test]
+    fn test_resolve_edge_priority() {
+        let stroke = |pt| FixedStroke { thickness: Abs::pt(pt), ..Default::default() };
+        let table = Some(stroke(1.0));
+        let own = Some(Some(stroke(2.0)));
+        let facing = Some(Some(stroke(3.0)));
+
+        // The cell's own override wins over the facing one, which wins over
+        // the table's stroke.
+        assert_eq!(resolve_edge(own.clone(), facing.clone(), &table), Some(stroke(2.0)));
+        assert_eq!(resolve_edge(None, facing.clone(), &table), Some(stroke(3.0)));
+        assert_eq!(resolve_edge(None, None, &table), table);
+
+        // A `none` override removes the edge.
+        assert_eq!(resolve_edge(Some(None), facing, &table), None);
+        assert_eq!(resolve_edge(None, Some(None), &table), None);
+    }
+}
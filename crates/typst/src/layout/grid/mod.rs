@@ -1,21 +1,33 @@
 mod layout;
 
-pub use self::layout::{Cell, CellGrid, Celled, GridLayouter, ResolvableCell};
+pub use self::layout::{
+    Aggregate, Cell, CellContext, CellGrid, CellProperties, Celled, GridLayouter,
+    IncompleteRow, NumberFormat, ResolvableCell, ResolvableGridChild,
+};
 
+use std::f64::consts::FRAC_PI_2;
 use std::num::NonZeroUsize;
+use std::sync::Arc;
 
+use ecow::{eco_format, EcoString};
 use smallvec::{smallvec, SmallVec};
 
-use crate::diag::{SourceResult, StrResult};
+use crate::diag::{bail, SourceResult, StrResult};
 use crate::engine::Engine;
 use crate::foundations::{
-    cast, elem, scope, Array, Content, Fold, NativeElement, Show, Smart, StyleChain,
-    Value,
+    cast, elem, scope, Array, Cast, Content, Dict, Fold, NativeElement, Show, Smart,
+    StyleChain, Value,
 };
 use crate::layout::{
-    Abs, Align, AlignElem, Axes, Fragment, Layout, Length, Regions, Rel, Sides, Sizing,
+    Abs, Align, AlignElem, Angle, Axes, Em, Fragment, Frame, FrameItem, Layout, Length,
+    Point, Ratio, Regions, Rel, RotateElem, Sides, Size, Sizing, Transform,
+};
+use crate::text::TextElem;
+use crate::util::{NonZeroExt, Numeric};
+use crate::visualize::{
+    Color, ColorSpace, Geometry, Gradient, LinearGradient, Paint, Path, RelativeTo,
+    Stroke,
 };
-use crate::visualize::{Paint, Stroke};
 
 /// Arranges content in a grid.
 ///
@@ -43,6 +55,21 @@ use crate::visualize::{Paint, Stroke};
 ///   with a fraction of `{1fr}`, they will each take up half of the remaining
 ///   space.
 ///
+/// - A share of the contents (e.g. `{(content: 80%)}`): The column is measured
+///   like an `{auto}` column and then scaled by the ratio, so that its cells'
+///   contents rewrap to the reduced width. It never gets narrower than the
+///   largest [minimum width]($table.cell.min-width) of its cells and, like an
+///   `{auto}` column, shrinks further if the grid exceeds the available space.
+///   Rows sized this way are treated like `{auto}` rows.
+///
+/// - `{"weighted-auto"}`: The column is sized like an `{auto}` column. If
+///   space is left over once all columns are sized, it is shared among the
+///   weighted columns in proportion to their content widths, so that wider
+///   columns grow more. Fixed and relative columns are resolved first and
+///   fractional columns take precedence: if there are any, they receive all of
+///   the remaining space instead. Rows sized this way are treated like
+///   `{auto}` rows.
+///
 /// To specify a single track, the array can be omitted in favor of a single
 /// value. To specify multiple `{auto}` tracks, enter the number of tracks
 /// instead of an array. For example, `columns:` `{3}` is equivalent to
@@ -61,6 +88,11 @@ use crate::visualize::{Paint, Stroke};
 /// `(column, row) => value`. You may also use a show rule on
 /// [`grid.cell`]($grid.cell) - see that element's examples for more information.
 ///
+/// Cells may span multiple columns through their [`colspan`]($grid.cell.colspan)
+/// field. The first rows of a grid can be marked as a
+/// [`grid.header`]($grid.header), which is repeated when the grid breaks across
+/// pages.
+///
 /// # Examples
 /// The example below demonstrates the different track sizing options.
 ///
@@ -116,7 +148,17 @@ pub struct GridElem {
 
     /// The gaps between rows & columns.
     ///
-    /// If there are more gutters than defined sizes, the last gutter is repeated.
+    /// An array of sizes defines each gap on its own, starting with the one
+    /// between the first and second track. If there are more gaps than
+    /// defined sizes, the sizes are repeated cyclically.
+    ///
+    /// ```example
+    /// #grid(
+    ///   columns: 4,
+    ///   column-gutter: (2pt, 12pt, 2pt),
+    ///   ..range(4).map(i => rect(width: 1em, fill: luma(200))[#i])
+    /// )
+    /// ```
     #[external]
     pub gutter: TrackSizings,
 
@@ -139,6 +181,10 @@ pub struct GridElem {
     /// passed the cells' column and row index, starting at zero. This can be
     /// used to implement striped grids.
     ///
+    /// Cells in a [row]($grid.row) with a `group` are additionally passed the
+    /// index of their band as a named `band` argument. This allows striping
+    /// groups of rows rather than individual rows.
+    ///
     /// ```example
     /// #grid(
     ///   fill: (col, row) => if calc.even(col + row) { luma(240) } else { white },
@@ -176,13 +222,48 @@ pub struct GridElem {
     /// Grids have no strokes by default, which can be changed by setting this
     /// option to the desired stroke.
     ///
-    /// _Note:_ Richer stroke customization for individual cells is not yet
-    /// implemented, but will be in the future. In the meantime, you can use the
-    /// third-party [tablex library](https://github.com/PgBiel/typst-tablex/).
+    /// The corners of the grid's outer border are joined according to the
+    /// stroke's [`join`]($stroke.join), which defaults to `{"miter"}`.
+    ///
+    /// ```example
+    /// #grid(
+    ///   columns: 2,
+    ///   inset: 5pt,
+    ///   stroke: (thickness: 4pt, join: "round"),
+    ///   [A], [B],
+    ///   [C], [D],
+    /// )
+    /// ```
+    ///
+    /// To vary the stroke from cell to cell, use a [table]($table). Its
+    /// [stroke]($table.stroke) can be given per cell and its cells can
+    /// override the stroke of individual sides.
     #[resolve]
     #[fold]
     pub stroke: Option<Stroke>,
 
+    /// Whether to balance the widths of `{auto}` columns.
+    ///
+    /// By default, each `{auto}` column is exactly as wide as its widest
+    /// cell. For tables with a lot of prose, this can lead to one column taking
+    /// up most of the space. When balancing, the width taken by the `{auto}`
+    /// columns is instead distributed as evenly as possible among them, while
+    /// keeping each column at least as wide as its longest unbreakable
+    /// content (e.g. its longest word). Fixed, relative and fractional columns
+    /// are not affected by balancing.
+    ///
+    /// ```example
+    /// #set page(width: 200pt)
+    /// #grid(
+    ///   columns: 2,
+    ///   gutter: 8pt,
+    ///   balance: true,
+    ///   [Short], lorem(20),
+    /// )
+    /// ```
+    #[default(false)]
+    pub balance: bool,
+
     /// How much to pad the cells' content.
     ///
     /// ```example
@@ -208,17 +289,25 @@ pub struct GridElem {
     #[default(Sides::splat(Abs::pt(0.0).into()))]
     pub inset: Sides<Option<Rel<Length>>>,
 
-    /// The contents of the grid cells.
+    /// The contents of the grid cells, plus an optional header.
     ///
-    /// The cells are populated in row-major order.
+    /// The cells are populated in row-major order. They are also shown and
+    /// laid out in this order (even in right-to-left grids), so show rules
+    /// and counters within cells always observe them row by row.
     #[variadic]
-    pub children: Vec<GridCell>,
+    pub children: Vec<GridChild>,
 }
 
 #[scope]
 impl GridElem {
     #[elem]
     type GridCell;
+
+    #[elem]
+    type GridHeader;
+
+    #[elem]
+    type GridRow;
 }
 
 impl Layout for GridElem {
@@ -240,24 +329,71 @@ impl Layout for GridElem {
 
         let tracks = Axes::new(columns.0.as_slice(), rows.0.as_slice());
         let gutter = Axes::new(column_gutter.0.as_slice(), row_gutter.0.as_slice());
-        let grid = CellGrid::resolve(
-            tracks,
-            gutter,
-            self.children(),
+        let children = self.children().iter().map(|child| match child {
+            GridChild::Header(header) => ResolvableGridChild::Header {
+                repeat: header.repeat(styles),
+                span: header.span(),
+                rows: vec![],
+                items: header.children().iter().cloned(),
+            },
+            GridChild::Row(row) => ResolvableGridChild::Row {
+                group: row.group(styles),
+                key: None,
+                keep: row.keep(styles),
+                span: row.span(),
+                items: row.children().iter().cloned(),
+            },
+            GridChild::Item(cell) => ResolvableGridChild::Item(cell.clone()),
+        });
+        let properties = CellProperties {
             fill,
+            stripes: &[],
             align,
+            stroke: &Celled::default(),
+            background: &Celled::default(),
             inset,
-            engine,
-            styles,
-        )?;
+            formats: &[],
+            decimals: &[],
+            column_keys: &[],
+            incomplete: IncompleteRow::Fill,
+        };
+        let grid =
+            CellGrid::resolve(tracks, gutter, children, &properties, engine, styles)?;
 
-        let layouter = GridLayouter::new(&grid, &stroke, regions, styles, self.span());
+        let layouter = GridLayouter::new(&grid, &stroke, regions, styles, self.span())
+            .with_balance(self.balance(styles));
 
         // Measure the columns and layout the grid row-by-row.
         layouter.layout(engine)
     }
 }
 
+/// How the lines of a grid or table are drawn.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash, Cast)]
+pub enum StrokeMode {
+    /// Lines are shared between adjacent cells, forming a connected grid.
+    #[default]
+    Grid,
+    /// Each cell is surrounded by its own border. Edges of adjacent cells
+    /// without gutter between them are drawn twice, doubling the line.
+    Cells,
+    /// Only horizontal rules are drawn: at the top and bottom of the table
+    /// and, with half the thickness, below the header.
+    Booktabs,
+}
+
+/// How an automatically sized column deals with content that doesn't fit.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Cast)]
+pub enum ColumnMode {
+    /// The column gives up its width first when the grid runs out of space,
+    /// wrapping its content before other columns have to.
+    Wrap,
+    /// The column keeps the width of its content. Only the other columns
+    /// wrap when the grid runs out of space, possibly letting the grid exceed
+    /// the available width.
+    Expand,
+}
+
 /// Track sizing definitions.
 #[derive(Debug, Default, Clone, Eq, PartialEq, Hash)]
 pub struct TrackSizings(pub SmallVec<[Sizing; 4]>);
@@ -266,8 +402,132 @@ cast! {
     TrackSizings,
     self => self.0.into_value(),
     sizing: Sizing => Self(smallvec![sizing]),
+    dict: Dict => Self(smallvec![content_sizing(dict)?]),
+    "weighted-auto" => Self(smallvec![Sizing::WeightedAuto]),
     count: NonZeroUsize => Self(smallvec![Sizing::Auto; count.get()]),
-    values: Array => Self(values.into_iter().map(Value::cast).collect::<StrResult<_>>()?),
+    values: Array => Self(
+        values
+            .into_iter()
+            .map(|value| match value {
+                Value::Dict(dict) => content_sizing(dict),
+                Value::Str(name) if name.as_str() == "weighted-auto" => {
+                    Ok(Sizing::WeightedAuto)
+                }
+                value => value.cast(),
+            })
+            .collect::<StrResult<_>>()?
+    ),
+}
+
+/// Casts a dictionary like `(content: 80%)` to a track that fits a share of
+/// its cells' contents.
+fn content_sizing(mut dict: Dict) -> StrResult<Sizing> {
+    let ratio: Ratio = dict.take("content")?.cast()?;
+    dict.finish(&["content"])?;
+    if ratio.get() < 0.0 {
+        bail!("content share must not be negative");
+    }
+    Ok(Sizing::Content(ratio))
+}
+
+/// Any child of a grid element.
+#[derive(Debug, Clone, PartialEq, Hash)]
+pub enum GridChild {
+    Header(GridHeader),
+    Row(GridRow),
+    Item(GridCell),
+}
+
+cast! {
+    GridChild,
+    self => match self {
+        Self::Header(header) => header.into_value(),
+        Self::Row(row) => row.into_value(),
+        Self::Item(cell) => cell.into_value(),
+    },
+    v: Content => v.into(),
+}
+
+impl From<Content> for GridChild {
+    fn from(value: Content) -> Self {
+        if let Some(header) = value.to::<GridHeader>() {
+            return GridChild::Header(header.clone());
+        }
+        if let Some(row) = value.to::<GridRow>() {
+            return GridChild::Row(row.clone());
+        }
+        GridChild::Item(GridCell::from(value))
+    }
+}
+
+/// A repeatable grid header.
+///
+/// The header occupies the first rows of the grid. If the grid breaks across
+/// multiple pages, the header is repeated at the top of each of them, unless
+/// `repeat` is set to `{false}`. It must be the first child of the grid.
+///
+/// ```example
+/// #set page(height: 8em)
+/// #grid(
+///   columns: 2,
+///   grid.header(
+///     [*Name*], [*Age*],
+///   ),
+///   ..range(6).map(n => ([Person #n], [#(20 + n)])).flatten()
+/// )
+/// ```
+#[elem(name = "header", title = "Grid Header")]
+pub struct GridHeader {
+    /// Whether this header should be repeated across pages.
+    #[default(true)]
+    pub repeat: bool,
+
+    /// The cells within the header.
+    #[variadic]
+    pub children: Vec<GridCell>,
+}
+
+/// A row of grid cells, optionally belonging to a group.
+///
+/// A row always starts on a new line of the grid, and the line is completed
+/// with empty cells if the row's cells don't fill it. Cells spanning more than
+/// the remaining columns move to the next line, which then also belongs to the
+/// row.
+///
+/// Consecutive rows with the same `group` form a _band._ Whenever the group
+/// changes from one row to the next, a new band starts. The band's index is
+/// passed to the grid's [`fill`]($grid.fill) function, so that whole groups of
+/// rows can be striped.
+///
+/// ```example
+/// #grid(
+///   columns: 2,
+///   inset: 4pt,
+///   fill: (x, y, band: none) => if band != none and calc.odd(band) {
+///     luma(230)
+///   },
+///   grid.row(group: "A")[Alice][Tea],
+///   grid.row(group: "A")[Alice][Cake],
+///   grid.row(group: "B")[Bob][Coffee],
+///   grid.row(group: "C")[Carol][Tea],
+///   grid.row(group: "C")[Carol][Scones],
+/// )
+/// ```
+#[elem(name = "row", title = "Grid Row")]
+pub struct GridRow {
+    /// The key of the group this row belongs to. Can be any value, rows are
+    /// grouped by comparing their keys for equality. If `{none}`, the row
+    /// doesn't belong to a group.
+    pub group: Option<Value>,
+
+    /// Whether to keep the row in one region with the other kept rows of its
+    /// group. See the [table row]($table.row.keep) for more information.
+    #[default(false)]
+    pub keep: bool,
+
+    /// The cells within the row.
+    #[variadic]
+    pub children: Vec<GridCell>,
 }
 
 /// A cell in the grid. Use this to either override grid properties for a
@@ -289,6 +549,18 @@ cast! {
 ///   [G], grid.cell(inset: 0pt)[H]
 /// )
 /// ```
+///
+/// A cell can also span multiple columns:
+///
+/// ```example
+/// #grid(
+///   columns: 3,
+///   fill: (x, y) => if y == 0 { aqua },
+///   inset: 5pt,
+///   grid.cell(colspan: 3, align: center)[Heading],
+///   [A], [B], [C],
+/// )
+/// ```
 #[elem(name = "cell", title = "Grid Cell", Show)]
 pub struct GridCell {
     /// The cell's body.
@@ -302,7 +574,30 @@ pub struct GridCell {
     align: Smart<Align>,
 
     /// The cell's inset override.
+    ///
+    /// The inset may be negative, in which case the cell's body bleeds past
+    /// the cell's edges. The cell's fill and stroke are not affected by this.
     inset: Smart<Sides<Option<Rel<Length>>>>,
+
+    /// The amount of columns spanned by this cell.
+    #[default(NonZeroUsize::ONE)]
+    colspan: NonZeroUsize,
+
+    /// The cell's layer.
+    ///
+    /// Within a row, cells on higher layers are drawn on top of cells on lower
+    /// layers, including their fills. Cells on the same layer are drawn in the
+    /// order they were specified in. This is useful when a cell's content
+    /// intentionally overlaps its neighbours, e.g. through a negative inset.
+    ///
+    /// ```example
+    /// #grid(
+    ///   columns: 2,
+    ///   grid.cell(inset: (right: -12pt), layer: 1)[Overlapping text],
+    ///   grid.cell(fill: yellow)[],
+    /// )
+    /// ```
+    layer: i64,
 }
 
 cast! {
@@ -341,13 +636,48 @@ impl ResolvableCell for GridCell {
             self.inset(styles).map_or(inset, |inner| inner.fold(inset)).map(Some),
         ));
 
-        Cell { body: self.pack(), fill }
+        let colspan = self.colspan(styles);
+        self.push_colspan(colspan);
+        let layer = self.layer(styles);
+        let empty = self.body().is_empty();
+
+        Cell {
+            body: self.pack(),
+            fill,
+            colspan,
+            layer,
+            stroke: None,
+            stroke_overrides: Sides::default(),
+            background: None,
+            rotated: false,
+            min_width: Abs::zero(),
+            spill: false,
+            empty,
+            continued: None,
+        }
+    }
+
+    fn colspan(&self, styles: StyleChain) -> NonZeroUsize {
+        self.colspan(styles)
+    }
+
+    fn body(&self) -> &Content {
+        self.body()
+    }
+
+    fn set_body(&mut self, body: Content) {
+        self.push_body(body);
     }
 }
 
 impl Show for GridCell {
     fn show(&self, _engine: &mut Engine, styles: StyleChain) -> SourceResult<Content> {
-        show_grid_cell(self.body().clone(), self.inset(styles), self.align(styles))
+        show_grid_cell(
+            self.body().clone(),
+            self.inset(styles),
+            self.align(styles),
+            CellDisplay::default(),
+        )
     }
 }
 
@@ -360,14 +690,54 @@ impl From<Content> for GridCell {
     }
 }
 
+/// How the body of a cell is displayed within the cell.
+#[derive(Debug, Clone)]
+pub struct CellDisplay {
+    /// The angle by which the body is rotated.
+    pub rotate: Angle,
+    /// Whether the body is shrunk to fit the cell.
+    pub shrink: bool,
+    /// How to treat a body which overflows a cell of fixed height.
+    pub overflow: CellOverflow,
+    /// The fill of the cell, which an ellipsis or fade is drawn with.
+    pub fill: Option<Paint>,
+    /// The step of the baseline grid the body's lines are snapped to.
+    pub baseline_grid: Option<Abs>,
+}
+
+impl Default for CellDisplay {
+    fn default() -> Self {
+        Self {
+            rotate: Angle::zero(),
+            shrink: false,
+            overflow: CellOverflow::Visible,
+            fill: None,
+            baseline_grid: None,
+        }
+    }
+}
+
 /// Function with common code to display a grid cell or table cell.
 pub fn show_grid_cell(
     mut body: Content,
     inset: Smart<Sides<Option<Rel<Length>>>>,
     align: Smart<Align>,
+    display: CellDisplay,
 ) -> SourceResult<Content> {
+    let CellDisplay { rotate, shrink, overflow, fill, baseline_grid } = display;
     let inset = inset.unwrap_or_default().map(Option::unwrap_or_default);
 
+    if !rotate.is_zero() {
+        // Rotate within the inset and let the rotation take part in layout,
+        // so that the cell is sized to the rotated body.
+        body = RotateElem::new(body).with_angle(rotate).with_reflow(true).pack();
+    }
+
+    if shrink {
+        // Shrink within the inset, so that the inset itself stays the same.
+        body = ShrinkElem::new(body).pack();
+    }
+
     if inset != Sides::default() {
         // Only pad if some inset is not 0pt.
         // Avoids a bug where using .padded() in any way inside Show causes
@@ -379,5 +749,316 @@ pub fn show_grid_cell(
         body = body.styled(AlignElem::set_alignment(alignment));
     }
 
+    if let Some(step) = baseline_grid.filter(|step| *step > Abs::zero()) {
+        body = BaselineGridElem::new(body, step).pack();
+    }
+
+    if overflow != CellOverflow::Visible {
+        body = OverflowElem::new(body, overflow, fill).pack();
+    }
+
     Ok(body)
 }
+
+/// How to treat content which overflows a cell of fixed height.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Cast)]
+pub enum CellOverflow {
+    /// The content is shown beyond the cell's bounds.
+    Visible,
+    /// The content is cut off at the cell's bounds.
+    Clip,
+    /// The content is cut off and an ellipsis is shown in the cell's bottom
+    /// right corner.
+    Ellipsis,
+    /// The content is cut off and fades out towards the cell's bottom edge.
+    Fade,
+}
+
+/// Handles the overflow of its body if it is laid out into a single region
+/// that is too short for it.
+///
+/// If the body may break into further regions, it is laid out as usual.
+#[elem(Layout)]
+pub struct OverflowElem {
+    /// The content whose overflow to handle.
+    #[required]
+    pub body: Content,
+
+    /// How to treat the overflow.
+    #[required]
+    pub mode: CellOverflow,
+
+    /// The fill behind the body, which an ellipsis or fade is drawn with.
+    #[required]
+    pub fill: Option<Paint>,
+}
+
+impl Layout for OverflowElem {
+    #[typst_macros::time(name = "overflow", span = self.span())]
+    fn layout(
+        &self,
+        engine: &mut Engine,
+        styles: StyleChain,
+        regions: Regions,
+    ) -> SourceResult<Fragment> {
+        let body = self.body();
+        let breakable = !regions.backlog.is_empty() || regions.last.is_some();
+        if breakable || !regions.size.y.is_finite() {
+            return body.layout(engine, styles, regions);
+        }
+
+        // Measure the natural height of the body.
+        let size = Size::new(regions.size.x, Abs::inf());
+        let pod = Regions::one(size, Axes::new(regions.expand.x, false));
+        let natural = body.measure(engine, styles, pod)?.into_frame().height();
+        if natural <= regions.size.y {
+            return body.layout(engine, styles, regions);
+        }
+
+        let mut frame = body.layout(engine, styles, regions)?.into_frame();
+        let size = Size::new(frame.width(), regions.size.y);
+        frame.set_size(size);
+        frame.clip(Path::rect(size));
+
+        // Indicators are drawn in the cell's fill or white if it has none.
+        let color = match self.fill() {
+            Some(Paint::Solid(color)) => *color,
+            _ => Color::WHITE,
+        };
+
+        match self.mode() {
+            CellOverflow::Visible | CellOverflow::Clip => {}
+            CellOverflow::Ellipsis => {
+                let pod = Regions::one(Size::splat(Abs::inf()), Axes::splat(false));
+                let mut ellipsis =
+                    TextElem::packed("…").layout(engine, styles, pod)?.into_frame();
+                ellipsis.fill(color.into());
+                let x = size.x - ellipsis.width();
+                let y = size.y - ellipsis.height();
+                frame.push_frame(Point::new(x, y), ellipsis);
+            }
+            CellOverflow::Fade => {
+                let height = Em::new(2.0).at(TextElem::size_in(styles)).min(size.y);
+                let gradient = Gradient::Linear(Arc::new(LinearGradient {
+                    stops: vec![
+                        (color.with_alpha(0.0), Ratio::zero()),
+                        (color, Ratio::one()),
+                    ],
+                    angle: Angle::rad(FRAC_PI_2),
+                    space: ColorSpace::Oklab,
+                    relative: Smart::Custom(RelativeTo::Self_),
+                    anti_alias: true,
+                }));
+                let rect = Geometry::Rect(Size::new(size.x, height));
+                let shape = rect.filled(gradient.into());
+                let pos = Point::with_y(size.y - height);
+                frame.push(pos, FrameItem::Shape(shape, self.span()));
+            }
+        }
+
+        Ok(Fragment::frame(frame))
+    }
+}
+
+/// Moves the first baseline of its body onto a grid of lines, counted from the
+/// top of the first region.
+///
+/// The body is moved by taking space from the top or, for bottom-aligned
+/// content, from the bottom of the first region. Subsequent lines follow the
+/// first one at their usual distance. A body without text is laid out as is.
+#[elem(Layout)]
+pub struct BaselineGridElem {
+    /// The content whose baseline to snap.
+    #[required]
+    pub body: Content,
+
+    /// The distance between the grid lines.
+    #[required]
+    pub step: Abs,
+}
+
+impl Layout for BaselineGridElem {
+    #[typst_macros::time(name = "baseline grid", span = self.span())]
+    fn layout(
+        &self,
+        engine: &mut Engine,
+        styles: StyleChain,
+        regions: Regions,
+    ) -> SourceResult<Fragment> {
+        let step = *self.step();
+        let fragment = self.body().layout(engine, styles, regions)?;
+        let Some(baseline) = fragment.iter().next().and_then(first_baseline) else {
+            return Ok(fragment);
+        };
+
+        let below = step * (baseline / step).ceil();
+        if below.approx_eq(baseline) {
+            return Ok(fragment);
+        }
+
+        // Taking space from the top moves top-aligned content down by the same
+        // amount, but centered content only by half of it.
+        let down = below - baseline;
+        let moved = self.layout_within(engine, styles, regions, down, Abs::zero())?;
+        let shift = moved
+            .iter()
+            .next()
+            .and_then(first_baseline)
+            .map_or(Abs::zero(), |moved| moved - baseline);
+        if shift.approx_eq(down) {
+            return Ok(moved);
+        }
+
+        let factor = shift / down;
+        if factor > 0.0 {
+            self.layout_within(engine, styles, regions, down / factor, Abs::zero())
+        } else {
+            // Bottom-aligned content can only move up to the previous line.
+            let up = baseline - step * (baseline / step).floor();
+            self.layout_within(engine, styles, regions, Abs::zero(), up)
+        }
+    }
+}
+
+impl BaselineGridElem {
+    /// Lays out the body with space taken from the top and bottom of the first
+    /// region, but keeps the size of the first frame as if there were none.
+    fn layout_within(
+        &self,
+        engine: &mut Engine,
+        styles: StyleChain,
+        regions: Regions,
+        top: Abs,
+        bottom: Abs,
+    ) -> SourceResult<Fragment> {
+        let mut pod = regions;
+        pod.size.y = (pod.size.y - top - bottom).max(Abs::zero());
+        let mut fragment = self.body().layout(engine, styles, pod)?;
+        if let Some(frame) = fragment.iter_mut().next() {
+            let mut size = frame.size();
+            size.y += top;
+            if regions.expand.y {
+                size.y += bottom;
+            }
+            frame.translate(Point::with_y(top));
+            frame.set_size(size);
+        }
+        Ok(fragment)
+    }
+}
+
+/// The topmost baseline of the text in a frame, if it has any.
+fn first_baseline(frame: &Frame) -> Option<Abs> {
+    frame
+        .items()
+        .filter_map(|(pos, item)| match item {
+            FrameItem::Text(_) => Some(pos.y),
+            FrameItem::Group(group) if group.transform.is_identity() => {
+                first_baseline(&group.frame).map(|y| pos.y + y)
+            }
+            _ => None,
+        })
+        .reduce(Abs::min)
+}
+
+/// A number in a column aligned at the decimal separator.
+///
+/// The number is right-aligned and padded on the right, so that its decimal
+/// separator lines up with those of the widest fractional part in the column.
+/// Whole numbers end where the separator would be.
+#[elem(Layout)]
+pub struct DecimalElem {
+    /// The part of the number before the decimal separator.
+    #[required]
+    pub integer: EcoString,
+
+    /// The decimal separator and the digits after it. Empty for whole numbers.
+    #[required]
+    pub fraction: EcoString,
+
+    /// The fractional parts of all numbers in the column.
+    #[required]
+    pub fractions: Vec<EcoString>,
+}
+
+impl Layout for DecimalElem {
+    #[typst_macros::time(name = "decimal", span = self.span())]
+    fn layout(
+        &self,
+        engine: &mut Engine,
+        styles: StyleChain,
+        regions: Regions,
+    ) -> SourceResult<Fragment> {
+        let pod = Regions::one(Size::splat(Abs::inf()), Axes::splat(false));
+        let mut measure = |text: &EcoString| -> SourceResult<Abs> {
+            if text.is_empty() {
+                return Ok(Abs::zero());
+            }
+            let frame = TextElem::packed(text.clone()).measure(engine, styles, pod)?;
+            Ok(frame.into_frame().width())
+        };
+
+        let mut widest = Abs::zero();
+        for fraction in self.fractions() {
+            widest.set_max(measure(fraction)?);
+        }
+        let pad = widest - measure(self.fraction())?;
+
+        let text = eco_format!("{}{}", self.integer(), self.fraction());
+        let number = TextElem::packed(text).layout(engine, styles, pod)?.into_frame();
+        let natural = number.width() + pad;
+        let width = if regions.expand.x { regions.size.x.max(natural) } else { natural };
+
+        let mut frame = Frame::soft(Size::new(width, number.height()));
+        frame.set_baseline(number.baseline());
+        frame.push_frame(Point::with_x(width - natural), number);
+        Ok(Fragment::frame(frame))
+    }
+}
+
+/// The smallest factor by which a cell's body is shrunk to fit its width.
+const MIN_SHRINK: f64 = 0.5;
+
+/// Scales its body down uniformly if it is wider than the available space.
+///
+/// The body is measured without wrapping. If it is too wide, it is scaled down
+/// to fit, but at most to [`MIN_SHRINK`]. Beyond that, it wraps at the minimum
+/// scale.
+#[elem(Layout)]
+pub struct ShrinkElem {
+    /// The content to shrink.
+    #[required]
+    pub body: Content,
+}
+
+impl Layout for ShrinkElem {
+    #[typst_macros::time(name = "shrink", span = self.span())]
+    fn layout(
+        &self,
+        engine: &mut Engine,
+        styles: StyleChain,
+        regions: Regions,
+    ) -> SourceResult<Fragment> {
+        let body = self.body();
+        let available = regions.size.x;
+
+        // Measure the natural width of the body.
+        let size = Size::new(Abs::inf(), regions.base().y);
+        let pod = Regions::one(size, Axes::splat(false));
+        let natural = body.measure(engine, styles, pod)?.into_frame().width();
+        if !available.is_finite() || natural <= available {
+            return body.layout(engine, styles, regions);
+        }
+
+        // Lay out the body at the width it will have after scaling, so that
+        // it only wraps if it doesn't fit at the minimum scale.
+        let scale = (available / natural).max(MIN_SHRINK);
+        let size = Size::new(available / scale, Abs::inf());
+        let pod = Regions::one(size, Axes::splat(false));
+        let mut frame = body.layout(engine, styles, pod)?.into_frame();
+        let scaled = frame.size() * scale;
+        frame.transform(Transform::scale(Ratio::new(scale), Ratio::new(scale)));
+        frame.set_size(scaled);
+        Ok(Fragment::frame(frame))
+    }
+}
@@ -254,6 +254,12 @@ pub struct Library {
     /// The default style properties (for page size, font selection, and
     /// everything else configurable via set and show rules).
     pub styles: Styles,
+    /// The schemes of URLs that links may lead to, compared
+    /// case-insensitively. With `None`, all schemes are allowed. Links to
+    /// URLs with other schemes show their body without leading anywhere.
+    /// Relative URLs, which have no scheme, are allowed through the empty
+    /// scheme `""`.
+    pub url_schemes: Option<Vec<EcoString>>,
 }
 
 impl Library {
@@ -276,6 +282,7 @@ impl Default for Library {
 #[derive(Debug, Clone, Default)]
 pub struct LibraryBuilder {
     inputs: Option<Dict>,
+    url_schemes: Option<Option<Vec<EcoString>>>,
 }
 
 impl LibraryBuilder {
@@ -285,12 +292,28 @@ impl LibraryBuilder {
         self
     }
 
+    /// Configure the schemes of URLs that links may lead to, or allow all
+    /// schemes with `None`.
+    ///
+    /// Relative URLs, which have no scheme, are allowed through the empty
+    /// scheme `""`. By default, only relative URLs and `http`, `https`,
+    /// `mailto` and `tel` are allowed. This keeps links in untrusted documents
+    /// from running scripts through `javascript:` URLs or embedding content
+    /// through `data:` URLs.
+    pub fn with_url_schemes(mut self, schemes: Option<Vec<EcoString>>) -> Self {
+        self.url_schemes = Some(schemes);
+        self
+    }
+
     /// Consumes the builder and returns a `Library`.
     pub fn build(self) -> Library {
         let math = math::module();
         let inputs = self.inputs.unwrap_or_default();
         let global = global(math.clone(), inputs);
-        Library { global, math, styles: Styles::new() }
+        let url_schemes = self.url_schemes.unwrap_or_else(|| {
+            Some(["", "http", "https", "mailto", "tel"].map(EcoString::from).into())
+        });
+        Library { global, math, styles: Styles::new(), url_schemes }
     }
 }
 
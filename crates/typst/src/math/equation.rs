@@ -267,8 +267,8 @@ impl Count for EquationElem {
 }
 
 impl LocalName for EquationElem {
-    fn local_name(lang: Lang, region: Option<Region>) -> &'static str {
-        match lang {
+    fn translate(lang: Lang, region: Option<Region>) -> Option<&'static str> {
+        Some(match lang {
             Lang::ALBANIAN => "Ekuacion",
             Lang::ARABIC => "معادلة",
             Lang::BOKMÅL => "Ligning",
@@ -298,8 +298,9 @@ impl LocalName for EquationElem {
             Lang::UKRAINIAN => "Рівняння",
             Lang::VIETNAMESE => "Phương trình",
             Lang::JAPANESE => "式",
-            Lang::ENGLISH | _ => "Equation",
-        }
+            Lang::ENGLISH => "Equation",
+            _ => return None,
+        })
     }
 }
 
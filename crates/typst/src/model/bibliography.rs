@@ -29,7 +29,8 @@ use crate::foundations::{
 };
 use crate::introspection::{Introspector, Locatable, Location};
 use crate::layout::{
-    BlockElem, Em, GridCell, GridElem, HElem, PadElem, Sizing, TrackSizings, VElem,
+    BlockElem, Em, GridCell, GridChild, GridElem, HElem, PadElem, Sizing, TrackSizings,
+    VElem,
 };
 use crate::model::{
     CitationForm, CiteGroup, Destination, FootnoteElem, HeadingElem, LinkElem, ParElem,
@@ -239,8 +240,10 @@ impl Show for BibliographyElem {
             if references.iter().any(|(prefix, _)| prefix.is_some()) {
                 let mut cells = vec![];
                 for (prefix, reference) in references {
-                    cells.push(GridCell::new(prefix.clone().unwrap_or_default()));
-                    cells.push(GridCell::new(reference.clone()));
+                    cells.push(GridChild::Item(GridCell::new(
+                        prefix.clone().unwrap_or_default(),
+                    )));
+                    cells.push(GridChild::Item(GridCell::new(reference.clone())));
                 }
 
                 seq.push(VElem::new(row_gutter).with_weakness(3).pack());
@@ -279,8 +282,8 @@ impl Finalize for BibliographyElem {
 }
 
 impl LocalName for BibliographyElem {
-    fn local_name(lang: Lang, region: Option<Region>) -> &'static str {
-        match lang {
+    fn translate(lang: Lang, region: Option<Region>) -> Option<&'static str> {
+        Some(match lang {
             Lang::ALBANIAN => "Bibliografi",
             Lang::ARABIC => "المراجع",
             Lang::BOKMÅL => "Bibliografi",
@@ -310,8 +313,9 @@ impl LocalName for BibliographyElem {
             Lang::UKRAINIAN => "Бібліографія",
             Lang::VIETNAMESE => "Tài liệu tham khảo",
             Lang::JAPANESE => "参考文献",
-            Lang::ENGLISH | _ => "Bibliography",
-        }
+            Lang::ENGLISH => "Bibliography",
+            _ => return None,
+        })
     }
 }
 
@@ -947,11 +951,14 @@ impl ElemRenderer<'_> {
 
         if let Some(prefix) = suf_prefix {
             const COLUMN_GUTTER: Em = Em::new(0.65);
-            content = GridElem::new(vec![GridCell::new(prefix), GridCell::new(content)])
-                .spanned(self.span)
-                .with_columns(TrackSizings(smallvec![Sizing::Auto; 2]))
-                .with_column_gutter(TrackSizings(smallvec![COLUMN_GUTTER.into()]))
-                .pack();
+            content = GridElem::new(vec![
+                GridChild::Item(GridCell::new(prefix)),
+                GridChild::Item(GridCell::new(content)),
+            ])
+            .spanned(self.span)
+            .with_columns(TrackSizings(smallvec![Sizing::Auto; 2]))
+            .with_column_gutter(TrackSizings(smallvec![COLUMN_GUTTER.into()]))
+            .pack();
         }
 
         match elem.display {
@@ -14,7 +14,9 @@ use crate::introspection::{
     Count, Counter, CounterKey, CounterUpdate, Locatable, Location,
 };
 use crate::layout::{Align, BlockElem, Em, HAlign, Length, PlaceElem, VAlign, VElem};
-use crate::model::{Numbering, NumberingPattern, Outlinable, Refable, Supplement};
+use crate::model::{
+    Numbering, NumberingPattern, Outlinable, Refable, Supplement, TableElem,
+};
 use crate::syntax::Spanned;
 use crate::text::{Lang, Region, TextElem};
 use crate::util::NonZeroExt;
@@ -306,6 +308,8 @@ impl Show for FigureElem {
 
         // Build the caption, if any.
         if let Some(caption) = self.caption(styles) {
+            // A table's own caption row would double up with this caption.
+            realized = realized.styled(TableElem::set_in_captioned_figure(true));
             let v = VElem::weak(self.gap(styles).into()).pack();
             realized = if caption.position(styles) == VAlign::Bottom {
                 realized + v + caption.pack()
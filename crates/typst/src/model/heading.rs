@@ -248,8 +248,8 @@ impl Outlinable for HeadingElem {
 }
 
 impl LocalName for HeadingElem {
-    fn local_name(lang: Lang, region: Option<Region>) -> &'static str {
-        match lang {
+    fn translate(lang: Lang, region: Option<Region>) -> Option<&'static str> {
+        Some(match lang {
             Lang::ALBANIAN => "Kapitull",
             Lang::ARABIC => "الفصل",
             Lang::BOKMÅL => "Kapittel",
@@ -280,7 +280,8 @@ impl LocalName for HeadingElem {
             Lang::UKRAINIAN => "Розділ",
             Lang::VIETNAMESE => "Phần", // TODO: This may be wrong.
             Lang::JAPANESE => "節",
-            Lang::ENGLISH | _ => "Section",
-        }
+            Lang::ENGLISH => "Section",
+            _ => return None,
+        })
     }
 }
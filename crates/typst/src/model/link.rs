@@ -1,13 +1,28 @@
-use ecow::{eco_format, EcoString};
+use std::num::NonZeroUsize;
+use std::str::FromStr;
+use std::sync::Arc;
 
-use crate::diag::{At, SourceResult};
+use ecow::{eco_format, EcoString, EcoVec};
+use indexmap::IndexMap;
+use smallvec::smallvec;
+
+use crate::diag::{
+    bail, warning, At, Severity, SourceDiagnostic, SourceResult, StrResult,
+};
 use crate::engine::Engine;
 use crate::foundations::{
-    cast, elem, Content, Label, NativeElement, Repr, Show, Smart, StyleChain,
+    cast, dict, elem, func, scope, Array, Cast, Content, Dict, Fold, Func, IntoValue,
+    Label, NativeElement, Repr, Selector, Show, Smart, StyleChain, Synthesize, Value,
 };
-use crate::introspection::Location;
-use crate::layout::Position;
+use crate::introspection::{Counter, CounterKey, Locatable, Location, Meta, MetaElem};
+use crate::layout::{Abs, Length, Point, Position};
+use crate::model::{
+    reference_number, reference_text, FigureElem, HeadingElem, NumberingPattern, Refable,
+    Supplement,
+};
+use crate::syntax::Span;
 use crate::text::{Hyphenate, TextElem};
+use crate::util::Numeric;
 
 /// Links to a URL or a location in the document.
 ///
@@ -29,7 +44,7 @@ use crate::text::{Hyphenate, TextElem};
 /// # Syntax
 /// This function also has dedicated syntax: Text that starts with `http://` or
 /// `https://` is automatically turned into a link.
-#[elem(Show)]
+#[elem(scope, Locatable, Synthesize, Show)]
 pub struct LinkElem {
     /// The destination the link points to.
     ///
@@ -50,6 +65,36 @@ pub struct LinkElem {
     ///   - A dictionary with a `page` key of type [integer]($int) and `x` and
     ///     `y` coordinates of type [length]($length). Pages are counted from
     ///     one, and the coordinates are relative to the page's top left corner.
+    ///     If the page doesn't exist, a warning is emitted and the link points
+    ///     to the last page instead.
+    ///
+    ///     With `{page-numbering: "display"}`, the `page` is instead the page
+    ///     number as it is displayed, like `{"iv"}` or `{"12"}`, and the link
+    ///     goes to the first page whose [page counter]($counter) shows that
+    ///     number in the page's [numbering]($page.numbering). This is useful
+    ///     when the page numbers restart, e.g. after a front matter numbered
+    ///     with roman numerals. If there is no such page, an error is raised.
+    ///
+    ///   - The string `{"page-top"}` or `{"page-bottom"}` to link to the top or
+    ///     bottom edge of the page the link itself is on. If the link's body
+    ///     is broken across pages, the page it starts on is used. This is
+    ///     useful for "back to top" links in a page header or footer.
+    ///
+    ///   - The string `{"next-heading"}` or `{"prev-heading"}` to link to the
+    ///     first [heading]($heading) after or the last one before the link
+    ///     itself. The [`level`]($link.level) restricts this to headings of a
+    ///     specific level. If there is no such heading, the link is handled
+    ///     like a link to a missing label.
+    ///
+    /// - URLs without a scheme, like `{"about.html#team"}` or the bare fragment
+    ///   `{"#intro"}`, are kept as they are, so that they stay relative to the
+    ///   document. In PDF, a bare fragment leads to the element with the label
+    ///   of the same name, like `<intro>`. If there is none, a warning is
+    ///   emitted and the link leads nowhere. Other relative URLs are resolved
+    ///   by the PDF viewer, relative to the PDF file. Links to other PDF
+    ///   files, like `{"manual.pdf#page=2"}` or
+    ///   `{"manual.pdf#nameddest=intro"}`, directly open the given page or
+    ///   named destination.
     ///
     /// ```example
     /// = Introduction <intro>
@@ -57,11 +102,21 @@ pub struct LinkElem {
     /// #link(<intro>)[Go to intro] \
     /// #link((page: 1, x: 0pt, y: 0pt))[
     ///   Go to top
-    /// ]
+    /// ] \
+    /// #link("page-top")[Back to top]
     /// ```
     #[required]
     #[parse(
-        let dest = args.expect::<LinkTarget>("destination")?;
+        let dest = match args.expect::<LinkTarget>("destination")? {
+            LinkTarget::Heading(anchor, _) => {
+                LinkTarget::Heading(anchor, args.named("level")?)
+            }
+            LinkTarget::Label(label) => match args.named("sub")? {
+                Some(sub) => LinkTarget::Subfigure(label, sub),
+                None => LinkTarget::Label(label),
+            },
+            dest => dest,
+        };
         dest.clone()
     )]
     pub dest: LinkTarget,
@@ -70,15 +125,276 @@ pub struct LinkElem {
     ///
     /// If `dest` is an URL string, the parameter can be omitted. In this case,
     /// the URL will be shown as the link.
+    ///
+    /// If `dest` is a label, the parameter can be omitted as well. The link
+    /// then displays the same text as a [reference]($ref) to the labelled
+    /// element would, e.g. "Figure 1", but keeps linking to the exact
+    /// element. For `{"next-heading"}` and `{"prev-heading"}`, the body
+    /// defaults to the body of the heading the link goes to.
+    ///
+    /// If the body is an [image]($image), the whole box it takes up in the
+    /// layout is clickable. This includes transparent parts of the image as
+    /// well as space left around it due to its [`fit`]($image.fit).
+    ///
+    /// ```example
+    /// #figure(
+    ///   rect[Hello],
+    ///   caption: [A rectangle],
+    /// ) <rect>
+    ///
+    /// As shown in #link(<rect>).
+    /// ```
     #[required]
     #[parse(match &dest {
         LinkTarget::Dest(Destination::Url(url)) => match args.eat()? {
             Some(body) => body,
             None => body_from_url(url),
         },
+        LinkTarget::Label(_) | LinkTarget::Subfigure(..) | LinkTarget::Heading(..) => {
+            args.eat()?.unwrap_or_default()
+        }
         _ => args.expect("body")?,
     })]
     pub body: Content,
+
+    /// The level of the headings which `{"next-heading"}` and
+    /// `{"prev-heading"}` consider. By default, headings of all levels are
+    /// considered.
+    ///
+    /// ```example
+    /// = Introduction
+    /// == Motivation
+    /// #link("next-heading", level: 1)[Skip to the next chapter] \
+    /// #link("next-heading")
+    ///
+    /// == Outline
+    /// = Background
+    /// ```
+    #[external]
+    pub level: NonZeroUsize,
+
+    /// Which of the figures nested in a labelled figure to link to, instead
+    /// of the labelled figure itself.
+    ///
+    /// The nested figures are designated in the order in which they appear in
+    /// the figure, either by letter (`{"a"}`, `{"b"}`, ...) or by number
+    /// (`{"1"}`, `{"2"}`, ...). Without a body, the link shows the text of a
+    /// reference to the nested figure.
+    ///
+    /// ```example
+    /// #figure(
+    ///   grid(
+    ///     columns: 2,
+    ///     gutter: 1em,
+    ///     figure(rect[A], caption: [First], supplement: none),
+    ///     figure(rect[B], caption: [Second], supplement: none),
+    ///   ),
+    ///   caption: [Two rectangles],
+    /// ) <rects>
+    ///
+    /// See the #link(<rects>, sub: "b")[second rectangle].
+    /// ```
+    #[external]
+    pub sub: EcoString,
+
+    /// The supplement of the text shown for a label without a body.
+    ///
+    /// This works like the [supplement of a reference]($ref.supplement): It
+    /// replaces the supplement of the labelled element, e.g. "Section", and
+    /// can be a function receiving the element. With `{none}`, only the
+    /// number is shown. A link with an explicit body ignores the supplement.
+    ///
+    /// ```example
+    /// #set heading(numbering: "1.")
+    /// = Introduction <intro>
+    /// See #link(<intro>) or
+    /// #link(<intro>, supplement: [§]).
+    /// ```
+    #[borrowed]
+    pub supplement: Smart<Option<Supplement>>,
+
+    /// Whether to hyphenate the link's body.
+    ///
+    /// By default, links are not hyphenated. When set to `{auto}`, the
+    /// surrounding [text's hyphenation]($text.hyphenate) applies.
+    ///
+    /// This is the only text property a link sets on its own. All others, like
+    /// the fill or weight, come from the surrounding styles, including
+    /// show-set rules on links. As the link's setting applies closer to its
+    /// body than such a rule, hyphenation of links is controlled with this
+    /// parameter rather than with `{show link: set text(hyphenate: true)}`.
+    /// Hyphenation set within the body, e.g. with
+    /// `{text(hyphenate: true)[...]}`, is more specific still and keeps
+    /// applying to its part of the body.
+    ///
+    /// ```example
+    /// #show link: set text(fill: blue, weight: "bold")
+    /// #set link(hyphenate: auto)
+    /// #set text(hyphenate: true)
+    /// #block(width: 50pt)[#link("https://typst.app")[extraordinarily]]
+    /// ```
+    #[default(Smart::Custom(false))]
+    pub hyphenate: Smart<bool>,
+
+    /// Whether to allow line breaks after the separators `/`, `.` and `-` of a
+    /// URL shown as the link's body.
+    ///
+    /// This keeps long URLs from overflowing without inserting visible
+    /// hyphens. It only applies to links to URLs whose body is plain text,
+    /// which is the case if the body is omitted.
+    ///
+    /// ```example
+    /// #set page(width: 120pt)
+    /// #set link(break-url: true)
+    /// https://example.com/a/rather/long/path-to-a/page.html
+    /// ```
+    #[default(false)]
+    pub break_url: bool,
+
+    /// A function rewriting the URLs that links lead to.
+    ///
+    /// The function receives the URL of a link to a URL as a string and must
+    /// return the URL to lead to instead. It is called once for each such
+    /// link, while the link's body, including a URL shown as the body, stays
+    /// as is. Links within the document are not affected. This way, the same
+    /// source can, for example, point to different servers depending on a
+    /// set rule at its top.
+    ///
+    /// ```example
+    /// #set link(transform: url => url.replace(
+    ///   "https://docs.example.com",
+    ///   "https://staging.example.com",
+    /// ))
+    ///
+    /// #link("https://docs.example.com/setup")
+    /// ```
+    #[default(None)]
+    pub transform: Option<Func>,
+
+    /// How far above its target a link within the document leads.
+    ///
+    /// Viewers scroll to the target of a link, so an offset keeps content
+    /// covered at the top of the view, like a repeated page header, from
+    /// hiding it. The position moves up by this length, but not beyond the top
+    /// of the page. It has no effect on links to URLs.
+    ///
+    /// ```example
+    /// #set link(offset: 2em)
+    /// #link(<intro>)[Back to the start]
+    ///
+    /// = Introduction <intro>
+    /// ```
+    #[resolve]
+    pub offset: Length,
+
+    /// Content to append to links to URLs, like an arrow marking them as
+    /// external.
+    ///
+    /// The indicator is part of the link's clickable body and takes on its
+    /// text styles, including those of show-set rules on links. No line break
+    /// happens between the body and the indicator. Links within the document
+    /// never receive it.
+    ///
+    /// ```example
+    /// #show link: set text(fill: blue)
+    /// #set link(indicator: sym.arrow.tr)
+    ///
+    /// = Introduction <intro>
+    /// Visit #link("https://typst.app")[Typst]
+    /// or go to the #link(<intro>)[intro].
+    /// ```
+    #[default(None)]
+    pub indicator: Option<Content>,
+
+    /// Whether a link to a label that doesn't exist in the document is an
+    /// error.
+    ///
+    /// By default, such a link shows its body without leading anywhere and a
+    /// warning naming the label is emitted at the link. Like this, all broken
+    /// links of a document are reported at once. When set to `{true}`, broken
+    /// links fail the compilation instead.
+    ///
+    /// ```example
+    /// See the #link(<appendix>)[appendix].
+    /// ```
+    #[default(false)]
+    pub strict: bool,
+
+    /// Labels which stand for other labels when linked to.
+    ///
+    /// Each key names an alias and its value is the label the alias leads
+    /// to. This keeps links to a section working after its label was
+    /// renamed. An alias may lead to another alias, in which case the links
+    /// follow the aliases until they arrive at a label that isn't an alias.
+    /// Aliases which lead back to themselves are an error. Multiple set rules
+    /// add to the aliases, with later rules taking precedence.
+    ///
+    /// ```example
+    /// #set link(aliases: (setup: <installation>))
+    ///
+    /// = Installation <installation>
+    /// See the #link(<setup>)[setup].
+    /// ```
+    #[fold]
+    pub aliases: LinkAliases,
+
+    /// How far the clickable area of the link extends beyond its body on each
+    /// side.
+    ///
+    /// This makes small links easier to hit, for example on touch screens,
+    /// without moving any visible content. Where the areas of two links
+    /// overlap, the one that comes first in the document wins.
+    ///
+    /// ```example
+    /// #set link(hit-padding: 4pt)
+    /// Pages #link("https://typst.app")[1],
+    /// #link("https://typst.app")[2] and
+    /// #link("https://typst.app")[3].
+    /// ```
+    #[resolve]
+    pub hit_padding: Length,
+
+    /// Whether the link leads anywhere.
+    ///
+    /// When set to `{false}`, only the link's body is shown. It isn't
+    /// clickable and isn't exempt from [hyphenation]($link.hyphenate), and the
+    /// link's target isn't resolved, so a link to a missing label doesn't
+    /// warn or fail. This strips all links from a document, e.g. for a
+    /// printed draft, with a single set rule.
+    ///
+    /// ```example
+    /// #set link(enabled: false)
+    /// Visit #link("https://typst.app")[Typst].
+    /// ```
+    #[default(true)]
+    pub enabled: bool,
+
+    /// The destination as a dictionary, for telling apart the kinds of
+    /// destinations in show rules and queries.
+    ///
+    /// The dictionary's `kind` is one of `{"url"}`, `{"label"}`,
+    /// `{"position"}`, `{"location"}`, `{"anchor"}` and `{"heading"}` and its
+    /// `value` is the [`dest`]($link.dest) as given. Links to nested figures
+    /// additionally have a `sub` and links to headings a `level`, if given.
+    ///
+    /// ```example
+    /// #show link: it => {
+    ///   if it.target.kind == "url" [#it (external)] else { it }
+    /// }
+    ///
+    /// = Introduction <intro>
+    /// #link("https://typst.app")[Typst] \
+    /// #link(<intro>)[Introduction]
+    /// ```
+    #[synthesized]
+    pub target: Dict,
+}
+
+impl Synthesize for LinkElem {
+    fn synthesize(&mut self, _: &mut Engine, _: StyleChain) -> SourceResult<()> {
+        self.push_target(self.dest().describe());
+        Ok(())
+    }
 }
 
 impl LinkElem {
@@ -89,22 +405,467 @@ impl LinkElem {
     }
 }
 
+#[scope]
+impl LinkElem {
+    /// Builds a URL with a query string from parameters.
+    ///
+    /// The keys and values of the parameters are percent-encoded as query
+    /// components according to RFC 3986, so they may contain arbitrary
+    /// characters. The parameters are appended to any query the base URL
+    /// already has, but before its fragment.
+    ///
+    /// ```example
+    /// #let url = link.url(
+    ///   "https://duckduckgo.com/",
+    ///   params: (q: "typst & friends", page: 2),
+    /// )
+    /// #raw(url) \
+    /// #link(url)[Search]
+    /// ```
+    #[func]
+    pub fn url(
+        /// The URL to add the query parameters to.
+        base: EcoString,
+        /// The query parameters. Values can be strings, numbers or booleans.
+        /// If a value is an array, the key is repeated for each of its items.
+        #[named]
+        #[default]
+        params: Dict,
+    ) -> StrResult<LinkTarget> {
+        let (url, fragment) = match base.split_once('#') {
+            Some((url, fragment)) => (url, Some(fragment)),
+            None => (base.as_str(), None),
+        };
+
+        let mut query = EcoString::new();
+        for (key, value) in params {
+            let values = match value {
+                Value::Array(array) => array.into_iter().collect(),
+                value => vec![value],
+            };
+
+            for value in values {
+                if !query.is_empty() {
+                    query.push('&');
+                }
+                encode_query(&key, &mut query);
+                query.push('=');
+                encode_query(&query_value(&key, value)?, &mut query);
+            }
+        }
+
+        let mut result = EcoString::from(url);
+        if !query.is_empty() {
+            if !result.contains('?') {
+                result.push('?');
+            } else if !result.ends_with(['?', '&']) {
+                result.push('&');
+            }
+            result.push_str(&query);
+        }
+        if let Some(fragment) = fragment {
+            result.push('#');
+            result.push_str(fragment);
+        }
+
+        Ok(LinkTarget::Dest(Destination::Url(result)))
+    }
+
+    /// Resolves a link target to the page and position it points to.
+    ///
+    /// This performs the same lookup as a link with the given destination, so
+    /// you can, for instance, display the page number a link leads to. Labels
+    /// are resolved to the element they are attached to and locations to their
+    /// position in the document. Page and heading anchors like `{"page-top"}`
+    /// are resolved relative to the given location.
+    ///
+    /// Returns a dictionary with the following keys:
+    /// - `page`: The number of the page the target is on.
+    /// - `position`: The target's position, as returned by
+    ///   [`location.position`]($location.position).
+    /// - `number`: For a label of a numbered element, its number as shown by a
+    ///   reference to it, so that phrases like "Equation (3) on page 12" can be
+    ///   built from a single call. Otherwise, `{none}`.
+    ///
+    /// Fails if the target doesn't exist or is a URL.
+    ///
+    /// ```example
+    /// #set math.equation(numbering: "(1)")
+    /// $ E = m c^2 $ <energy>
+    ///
+    /// #locate(loc => {
+    ///   let target = link.resolve(<energy>, loc)
+    ///   [See equation #target.number on page #target.page.]
+    /// })
+    /// ```
+    #[func]
+    pub fn resolve(
+        /// The engine.
+        engine: &mut Engine,
+        /// The callsite span.
+        span: Span,
+        /// The link target to resolve. Can be anything a link can point to,
+        /// except for a URL.
+        target: LinkTarget,
+        /// The location to resolve the target from. Its value only matters for
+        /// page and heading anchors, which are relative to this location. It is
+        /// required for the same reasons as for [`query`]($query.location).
+        location: Location,
+    ) -> SourceResult<Dict> {
+        let pos = match target.resolve(engine, location, span)? {
+            Destination::Url(_) => bail!(span, "cannot resolve a URL to a position"),
+            Destination::Position(pos) => pos,
+            Destination::Location(loc) => engine.introspector.position(loc),
+        };
+
+        // Only labels of numbered elements have a number.
+        let mut number = Value::None;
+        if let LinkTarget::Label(label) = target {
+            let elem = engine.introspector.query_label(label).at(span)?;
+            let elem = elem.clone().into_inner();
+            let numbered = elem
+                .with::<dyn Refable>()
+                .map_or(false, |refable| refable.numbering().is_some());
+            if numbered {
+                number = reference_number(engine, &elem, span)?.into_value();
+            }
+        }
+
+        Ok(dict! {
+            "number" => number,
+            "page" => pos.page,
+            "position" => Dict::from(pos),
+        })
+    }
+
+    /// Lists the external links in the document.
+    ///
+    /// Returns an array with one dictionary for each distinct URL that is
+    /// linked to, in the order in which the URLs first appear in the document.
+    /// Each dictionary has the following keys:
+    /// - `url`: The URL, as written in its first link.
+    /// - `body`: The body of the URL's first link.
+    /// - `pages`: The numbers of all pages that link to the URL.
+    ///
+    /// URLs which only differ by a trailing slash are considered the same.
+    /// Links to labels or positions within the document are not included.
+    ///
+    /// Like [`query`]($query), this function needs to be called within a
+    /// [`locate`]($locate) call.
+    ///
+    /// ```example
+    /// See #link("https://typst.app")[Typst] and
+    /// #link("https://github.com/typst")[GitHub].
+    ///
+    /// #locate(loc => {
+    ///   for entry in link.all(loc) [
+    ///     - #entry.body: #entry.url (p. #entry.pages.join(", "))
+    ///   ]
+    /// })
+    /// ```
+    #[func]
+    pub fn all(
+        /// The engine.
+        engine: &mut Engine,
+        /// Can be an arbitrary location, as its value is irrelevant for the
+        /// function's return value. It is required for the same reasons as for
+        /// [`query`]($query).
+        location: Location,
+        /// Whether to group the links by the domain of their URL. If `{true}`,
+        /// the result is a dictionary mapping from each domain to an array of
+        /// the links to it, with the domains in order of their first
+        /// appearance.
+        #[named]
+        #[default(false)]
+        by_domain: bool,
+    ) -> Value {
+        let _ = location;
+
+        // Collect the distinct URLs with their first link and all pages.
+        type Entry<'a> = (&'a EcoString, &'a Content, Vec<NonZeroUsize>);
+        let mut entries: IndexMap<Destination, Entry> = IndexMap::new();
+        let selector = Selector::Elem(LinkElem::elem(), None);
+        let elems = engine.introspector.query(&selector);
+        for elem in &elems {
+            let Some(link) = elem.to::<LinkElem>() else {
+                continue;
+            };
+            let LinkTarget::Dest(dest @ Destination::Url(url)) = link.dest() else {
+                continue;
+            };
+
+            let page = engine.introspector.page(elem.location().unwrap());
+            let (_, _, pages) = entries
+                .entry(dest.canonical())
+                .or_insert_with(|| (url, link.body(), vec![]));
+            if !pages.contains(&page) {
+                pages.push(page);
+            }
+        }
+
+        let entries = entries.into_values().map(|(url, body, pages)| {
+            let entry = dict! {
+                "url" => url.clone(),
+                "body" => body.clone(),
+                "pages" => pages,
+            };
+            (url, entry.into_value())
+        });
+
+        if !by_domain {
+            return entries.map(|(_, entry)| entry).collect::<Array>().into_value();
+        }
+
+        let mut grouped: IndexMap<&str, Array> = IndexMap::new();
+        for (url, entry) in entries {
+            grouped.entry(domain(url)).or_default().push(entry);
+        }
+
+        grouped
+            .into_iter()
+            .map(|(domain, links)| (domain.into(), links.into_value()))
+            .collect::<Dict>()
+            .into_value()
+    }
+}
+
+/// Converts the value of a query parameter to text.
+fn query_value(key: &str, value: Value) -> StrResult<EcoString> {
+    Ok(match value {
+        Value::Str(text) => text.as_str().into(),
+        Value::Int(_) | Value::Float(_) | Value::Bool(_) => value.repr(),
+        value => bail!(
+            "expected string, number or boolean for parameter `{key}`, found {}",
+            value.ty()
+        ),
+    })
+}
+
+/// Percent-encodes a query component, leaving only the unreserved characters
+/// of RFC 3986 as they are.
+fn encode_query(text: &str, out: &mut EcoString) {
+    for byte in text.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~') {
+            out.push(byte as char);
+        } else {
+            out.push_str(&eco_format!("%{byte:02X}"));
+        }
+    }
+}
+
 impl Show for LinkElem {
     #[typst_macros::time(name = "link", span = self.span())]
-    fn show(&self, engine: &mut Engine, _: StyleChain) -> SourceResult<Content> {
-        let body = self.body().clone();
-        let linked = match self.dest() {
-            LinkTarget::Dest(dest) => body.linked(dest.clone()),
-            LinkTarget::Label(label) => engine
+    fn show(&self, engine: &mut Engine, styles: StyleChain) -> SourceResult<Content> {
+        // A disabled link neither leads anywhere nor styles its body, so its
+        // target isn't even resolved.
+        if !self.enabled(styles) {
+            return Ok(self.body().clone());
+        }
+
+        let mut body = self.body().clone();
+        if let LinkTarget::Dest(dest @ Destination::Url(_)) = self.dest() {
+            if self.break_url(styles) {
+                if let Some(text) = body.to::<TextElem>() {
+                    body = TextElem::packed(url_breaks(text.text()));
+                }
+            }
+
+            if let Some(fragment) = dest.fragment() {
+                check_fragment(engine, fragment, self.span());
+            }
+
+            // The word joiner keeps the indicator on the body's line.
+            if let Some(indicator) = self.indicator(styles) {
+                body += TextElem::packed('\u{2060}') + indicator;
+            }
+        }
+
+        let offset = self.offset(styles);
+        let strict = self.strict(styles);
+        let aliases = self.aliases(styles);
+        let supplement = self.supplement(styles);
+        let hit_padding = self.hit_padding(styles);
+        let mut linked = match self.dest() {
+            LinkTarget::Dest(Destination::Url(url)) => {
+                let url = match self.transform(styles) {
+                    Some(func) => {
+                        func.call(engine, [url.clone()])?.cast().at(func.span())?
+                    }
+                    None => url.clone(),
+                };
+                let dest = Destination::Url(url);
+                if check_scheme(engine, &dest, self.span()) {
+                    padded_link(body, dest, hit_padding)
+                } else {
+                    body
+                }
+            }
+            target => engine
                 .delayed(|engine| {
-                    let elem = engine.introspector.query_label(*label).at(self.span())?;
-                    let dest = Destination::Location(elem.location().unwrap());
-                    Ok(Some(body.clone().linked(dest)))
+                    let target = &aliases.apply(target).at(self.span())?;
+                    let location = self.location().unwrap();
+                    let mut dest = match target.resolve(engine, location, self.span()) {
+                        Ok(dest) => dest,
+                        Err(errors) if !strict => {
+                            warn_unresolved(engine, errors);
+                            return Ok(None);
+                        }
+                        Err(errors) => return Err(errors),
+                    };
+                    if !offset.is_zero() {
+                        dest = offset_destination(engine, dest, offset);
+                    }
+
+                    // Without a body, display the text of a reference or the
+                    // heading's body.
+                    let mut body = body.clone();
+                    let span = self.span();
+                    match target {
+                        LinkTarget::Label(label) if body.is_empty() => {
+                            let elem =
+                                engine.introspector.query_label(*label).at(span)?;
+                            let elem = elem.clone().into_inner();
+                            body = reference_text(engine, elem, supplement, span)?;
+                        }
+                        LinkTarget::Subfigure(label, sub) if body.is_empty() => {
+                            let elem = find_subfigure(engine, *label, sub).at(span)?;
+                            body = reference_text(engine, elem, supplement, span)?;
+                        }
+                        LinkTarget::Heading(anchor, level) if body.is_empty() => {
+                            let heading =
+                                anchor.find(engine, location, *level).at(span)?;
+                            body = heading.body().clone();
+                        }
+                        _ => {}
+                    }
+
+                    Ok(Some(padded_link(body, dest, hit_padding)))
                 })
                 .unwrap_or(body),
         };
 
-        Ok(linked.styled(TextElem::set_hyphenate(Hyphenate(Smart::Custom(false)))))
+        // Unless set to `auto`, this overrides the text's hyphenation. Only
+        // this property is set here, so that all other text styles, including
+        // those of show-set rules on links, still apply to the body. It is set
+        // on the outside of the body, so that hyphenation set within the body
+        // still takes precedence.
+        if let Smart::Custom(hyphenate) = self.hyphenate(styles) {
+            let hyphenate = Hyphenate(Smart::Custom(hyphenate));
+            linked = linked.styled(TextElem::set_hyphenate(hyphenate));
+        }
+
+        Ok(linked)
+    }
+}
+
+/// Links the body to the destination, with a clickable area that extends
+/// beyond the frames of the body by the padding on each side.
+fn padded_link(body: Content, dest: Destination, padding: Abs) -> Content {
+    body.styled(MetaElem::set_data(smallvec![Meta::Link(dest, padding)]))
+}
+
+/// Warns if no element has the label named by a link's bare fragment.
+fn check_fragment(engine: &mut Engine, fragment: &str, span: Span) {
+    if engine.introspector.known_pages().is_none() {
+        return;
+    }
+
+    let selector = Selector::Label(Label::new(fragment));
+    if engine.introspector.query(&selector).is_empty() {
+        engine.tracer.warn(warning!(
+            span, "no label `<{}>` matches the fragment of this link", fragment;
+            hint: "the link leads nowhere in PDF"
+        ));
+    }
+}
+
+/// Whether the embedder allows links to the URL's scheme. Warns if not.
+fn check_scheme(engine: &mut Engine, dest: &Destination, span: Span) -> bool {
+    let Some(allowed) = &engine.world.library().url_schemes else { return true };
+    if is_scheme_allowed(allowed, dest) {
+        return true;
+    }
+
+    match dest.scheme() {
+        Some(scheme) => engine.tracer.warn(warning!(
+            span, "links to `{}:` URLs are not allowed", scheme;
+            hint: "the link's body is shown without a link"
+        )),
+        None => engine.tracer.warn(warning!(
+            span, "relative links are not allowed";
+            hint: "the link's body is shown without a link"
+        )),
+    }
+    false
+}
+
+/// Whether the URL's scheme is in the list of allowed schemes. Relative URLs
+/// are allowed through the empty scheme.
+fn is_scheme_allowed(allowed: &[EcoString], dest: &Destination) -> bool {
+    let Destination::Url(_) = dest else { return true };
+    let scheme = dest.scheme().unwrap_or_default();
+    allowed.iter().any(|allowed| allowed.eq_ignore_ascii_case(scheme))
+}
+
+/// Reports the errors of a link that could not be resolved as warnings.
+///
+/// Nothing is reported while the document's pages aren't known yet, as the
+/// link's target may only appear once the document has been laid out.
+fn warn_unresolved(engine: &mut Engine, errors: EcoVec<SourceDiagnostic>) {
+    if engine.introspector.known_pages().is_none() {
+        return;
+    }
+
+    for mut error in errors {
+        error.severity = Severity::Warning;
+        error.hint("the link's body is shown without a link");
+        engine.tracer.warn(error);
+    }
+}
+
+/// Moves a destination within the document up by the given offset, stopping at
+/// the top of its page.
+fn offset_destination(engine: &Engine, dest: Destination, offset: Abs) -> Destination {
+    let mut pos = match dest {
+        Destination::Url(_) => return dest,
+        Destination::Position(pos) => pos,
+        Destination::Location(loc) => engine.introspector.position(loc),
+    };
+    pos.point.y = (pos.point.y - offset).max(Abs::zero());
+    Destination::Position(pos)
+}
+
+/// Inserts invisible break opportunities after the separators of a URL.
+///
+/// Runs of separators like the `//` after the scheme are kept together.
+fn url_breaks(url: &str) -> EcoString {
+    let is_separator = |c: char| matches!(c, '/' | '.' | '-');
+    let mut text = EcoString::new();
+    let mut chars = url.chars().peekable();
+    while let Some(c) = chars.next() {
+        text.push(c);
+        if is_separator(c) && chars.peek().map_or(false, |&next| !is_separator(next)) {
+            text.push('\u{200B}');
+        }
+    }
+    text
+}
+
+/// Extracts the domain from a URL, without port and user information.
+///
+/// For URLs without an authority, like `mailto:` links, this is the part after
+/// the `@`, if any, and the scheme otherwise.
+fn domain(url: &str) -> &str {
+    match url.split_once("://") {
+        Some((_, rest)) => {
+            let authority = rest.split(['/', '?', '#']).next().unwrap_or(rest);
+            let host = authority.rsplit('@').next().unwrap_or(authority);
+            host.split(':').next().unwrap_or(host)
+        }
+        None => match url.rsplit_once('@') {
+            Some((_, host)) => host,
+            None => url.split(':').next().unwrap_or(url),
+        },
     }
 }
 
@@ -122,18 +883,354 @@ fn body_from_url(url: &EcoString) -> Content {
 pub enum LinkTarget {
     Dest(Destination),
     Label(Label),
+    Subfigure(Label, EcoString),
+    Anchor(PageAnchor),
+    Heading(HeadingAnchor, Option<NonZeroUsize>),
+    Page(EcoString, Point),
 }
 
 cast! {
     LinkTarget,
     self => match self {
         Self::Dest(v) => v.into_value(),
-        Self::Label(v) => v.into_value(),
+        Self::Label(v) | Self::Subfigure(v, _) => v.into_value(),
+        Self::Anchor(v) => v.into_value(),
+        Self::Heading(v, _) => v.into_value(),
+        Self::Page(page, point) => dict! {
+            "page" => page,
+            "x" => point.x,
+            "y" => point.y,
+            "page-numbering" => PageNumbering::Display,
+        }.into_value(),
     },
+    // Must come before destinations, which accept any string as a URL and
+    // any dictionary as a position.
+    v: PageAnchor => Self::Anchor(v),
+    v: HeadingAnchor => Self::Heading(v, None),
+    v: Dict => page_position(v)?,
     v: Destination => Self::Dest(v),
     v: Label => Self::Label(v),
 }
 
+impl LinkTarget {
+    /// Describes the target as a dictionary with its `kind` and `value` and
+    /// further details depending on the kind.
+    pub fn describe(&self) -> Dict {
+        let kind = match self {
+            Self::Dest(Destination::Url(_)) => "url",
+            Self::Dest(Destination::Position(_)) | Self::Page(..) => "position",
+            Self::Dest(Destination::Location(_)) => "location",
+            Self::Label(_) | Self::Subfigure(..) => "label",
+            Self::Anchor(_) => "anchor",
+            Self::Heading(..) => "heading",
+        };
+
+        let mut dict = dict! { "kind" => kind, "value" => self.clone() };
+        match self {
+            Self::Subfigure(_, sub) => {
+                dict.insert("sub".into(), sub.clone().into_value())
+            }
+            Self::Heading(_, Some(level)) => {
+                dict.insert("level".into(), (*level).into_value())
+            }
+            _ => {}
+        }
+        dict
+    }
+
+    /// Resolve the target into a destination, looking up labels in the
+    /// document. Page and heading anchors are resolved relative to the given
+    /// location.
+    pub fn resolve(
+        &self,
+        engine: &mut Engine,
+        location: Location,
+        span: Span,
+    ) -> SourceResult<Destination> {
+        match self {
+            Self::Dest(Destination::Position(pos)) => {
+                Ok(Destination::Position(check_page(engine, *pos, span)))
+            }
+            Self::Dest(dest) => Ok(dest.clone()),
+            Self::Label(label) => {
+                let elem = engine.introspector.query_label(*label).at(span)?;
+                Ok(Destination::Location(elem.location().unwrap()))
+            }
+            Self::Subfigure(label, sub) => {
+                let elem = find_subfigure(engine, *label, sub).at(span)?;
+                Ok(Destination::Location(elem.location().unwrap()))
+            }
+            Self::Anchor(anchor) => {
+                let page = engine.introspector.page(location);
+                let y = match anchor {
+                    PageAnchor::PageTop => Abs::zero(),
+                    PageAnchor::PageBottom => engine.introspector.page_size(page).y,
+                };
+                Ok(Destination::Position(Position { page, point: Point::with_y(y) }))
+            }
+            Self::Heading(anchor, level) => {
+                let heading = anchor.find(engine, location, *level).at(span)?;
+                Ok(Destination::Location(heading.location().unwrap()))
+            }
+            Self::Page(number, point) => {
+                let page = find_page(engine, number, span)?;
+                Ok(Destination::Position(Position { page, point: *point }))
+            }
+        }
+    }
+}
+
+/// Labels which stand for other labels in links.
+#[derive(Debug, Default, Clone, PartialEq, Hash)]
+pub struct LinkAliases(Vec<(Label, Label)>);
+
+impl LinkAliases {
+    /// Replaces an alias in a link target with the label it leads to.
+    fn apply(&self, target: &LinkTarget) -> StrResult<LinkTarget> {
+        Ok(match target {
+            LinkTarget::Label(label) => LinkTarget::Label(self.resolve(*label)?),
+            LinkTarget::Subfigure(label, sub) => {
+                LinkTarget::Subfigure(self.resolve(*label)?, sub.clone())
+            }
+            _ => target.clone(),
+        })
+    }
+
+    /// Follows the aliases from the given label to a label that isn't an
+    /// alias.
+    fn resolve(&self, label: Label) -> StrResult<Label> {
+        let mut chain = vec![label];
+        let mut current = label;
+        while let Some(&(_, next)) = self.0.iter().find(|(alias, _)| *alias == current) {
+            let cyclic = chain.contains(&next);
+            chain.push(next);
+            if cyclic {
+                let chain: Vec<_> = chain.iter().map(Label::repr).collect();
+                bail!("label aliases form a cycle: {}", chain.join(" -> "));
+            }
+            current = next;
+        }
+        Ok(current)
+    }
+}
+
+cast! {
+    LinkAliases,
+    self => self
+        .0
+        .into_iter()
+        .map(|(alias, label)| (alias.as_str().into(), label.into_value()))
+        .collect::<Dict>()
+        .into_value(),
+    v: Dict => Self(
+        v.into_iter()
+            .map(|(alias, label)| Ok((Label::new(alias.as_str()), label.cast()?)))
+            .collect::<StrResult<_>>()?,
+    ),
+}
+
+impl Fold for LinkAliases {
+    type Output = Self;
+
+    fn fold(mut self, outer: Self::Output) -> Self::Output {
+        self.0.extend(outer.0);
+        self
+    }
+}
+
+/// How the `page` of a `(page:, x:, y:)` link destination is counted.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Cast)]
+pub enum PageNumbering {
+    /// The physical page, counted from one.
+    Physical,
+    /// The page number as it is displayed on the page.
+    Display,
+}
+
+/// Casts a position dictionary, whose page is either physical or displayed
+/// depending on its `page-numbering` key.
+fn page_position(mut dict: Dict) -> StrResult<LinkTarget> {
+    let numbering = match dict.take("page-numbering") {
+        Ok(value) => value.cast()?,
+        Err(_) => PageNumbering::Physical,
+    };
+
+    if numbering == PageNumbering::Physical {
+        return Ok(LinkTarget::Dest(Destination::Position(dict.into_value().cast()?)));
+    }
+
+    let page = match dict.take("page")? {
+        Value::Int(n) => eco_format!("{n}"),
+        value => value.cast()?,
+    };
+    let x: Length = dict.take("x")?.cast()?;
+    let y: Length = dict.take("y")?.cast()?;
+    dict.finish(&["page", "x", "y", "page-numbering"])?;
+    Ok(LinkTarget::Page(page, Point::new(x.abs, y.abs)))
+}
+
+/// Finds the first physical page whose page number is displayed as the given
+/// text. Pages without a numbering count as numbered with `{"1"}`, like in the
+/// outline.
+fn find_page(
+    engine: &mut Engine,
+    number: &str,
+    span: Span,
+) -> SourceResult<NonZeroUsize> {
+    let counter = Counter::new(CounterKey::Page);
+    for page in 1..=engine.introspector.pages().get() {
+        let page = NonZeroUsize::new(page).unwrap();
+        let numbering = engine
+            .introspector
+            .page_numbering_at(page)
+            .cloned()
+            .unwrap_or_else(|| NumberingPattern::from_str("1").unwrap().into());
+        let displayed = counter.at_page(engine, page)?.display(engine, &numbering)?;
+        if displayed.plain_text() == number {
+            return Ok(page);
+        }
+    }
+    bail!(span, "there is no page numbered `{number}`")
+}
+
+/// Finds the figure nested in the figure with the given label that the
+/// designation refers to, e.g. `"b"` or `"2"` for the second one.
+fn find_subfigure(engine: &Engine, label: Label, sub: &str) -> StrResult<Content> {
+    let elem = engine.introspector.query_label(label)?;
+    let Some(figure) = elem.to::<FigureElem>() else {
+        bail!("label `{}` does not refer to a figure", label.repr());
+    };
+
+    let index = match sub.parse::<usize>() {
+        Ok(n) => n.checked_sub(1),
+        Err(_) => match sub.as_bytes() {
+            [c] if c.is_ascii_alphabetic() => {
+                Some((c.to_ascii_lowercase() - b'a') as usize)
+            }
+            _ => bail!("invalid subfigure `{sub}`, expected a letter or a number"),
+        },
+    };
+
+    // The nested figures are recognized by their spans among the figures
+    // following the labelled one, as they only get locations when they are
+    // laid out.
+    let spans: Vec<Span> = figure
+        .body()
+        .query(Selector::Elem(FigureElem::elem(), None))
+        .iter()
+        .map(Content::span)
+        .collect();
+    let selector = Selector::After {
+        selector: Arc::new(Selector::Elem(FigureElem::elem(), None)),
+        start: Arc::new(Selector::Location(elem.location().unwrap())),
+        inclusive: false,
+    };
+    let subfigures: Vec<_> = engine
+        .introspector
+        .query(&selector)
+        .into_iter()
+        .filter(|subfigure| spans.contains(&subfigure.span()))
+        .take(spans.len())
+        .collect();
+
+    match index.and_then(|i| subfigures.get(i)) {
+        Some(subfigure) => Ok(subfigure.clone().into_inner()),
+        None if subfigures.is_empty() => {
+            bail!("figure `{}` has no subfigures", label.repr())
+        }
+        None => bail!(
+            "figure `{}` has no subfigure `{sub}`, it has {}",
+            label.repr(),
+            subfigures.len(),
+        ),
+    }
+}
+
+/// Clamps a position to the last page of the document, warning if it points
+/// past it. Positions are left as is while the page count isn't known yet.
+fn check_page(engine: &mut Engine, mut pos: Position, span: Span) -> Position {
+    let Some(pages) = engine.introspector.known_pages() else {
+        return pos;
+    };
+    if pos.page > pages {
+        engine.tracer.warn(warning!(
+            span, "link points to page {}, but the document only has {} page(s)",
+            pos.page, pages;
+            hint: "the link will point to the last page instead"
+        ));
+        pos.page = pages;
+    }
+    pos
+}
+
+/// A position on the current page, which a link can go to.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Cast)]
+pub enum PageAnchor {
+    /// The top edge of the page.
+    PageTop,
+    /// The bottom edge of the page.
+    PageBottom,
+}
+
+/// A heading before or after a link, which the link can go to.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Cast)]
+pub enum HeadingAnchor {
+    /// The first heading after the link.
+    NextHeading,
+    /// The last heading before the link.
+    PrevHeading,
+}
+
+impl HeadingAnchor {
+    /// Find the heading this anchor refers to from the given location,
+    /// optionally only considering headings of the given level.
+    fn find(
+        self,
+        engine: &Engine,
+        location: Location,
+        level: Option<NonZeroUsize>,
+    ) -> StrResult<HeadingElem> {
+        let selector = Arc::new(Selector::Elem(HeadingElem::elem(), None));
+        let here = Arc::new(Selector::Location(location));
+        let selector = match self {
+            Self::NextHeading => {
+                Selector::After { selector, start: here, inclusive: false }
+            }
+            Self::PrevHeading => {
+                Selector::Before { selector, end: here, inclusive: false }
+            }
+        };
+
+        let headings = engine.introspector.query(&selector);
+        let mut headings = headings
+            .iter()
+            .filter_map(|elem| elem.to::<HeadingElem>())
+            .filter(|heading| {
+                level.map_or(true, |level| heading.level(StyleChain::default()) == level)
+            });
+        let heading = match self {
+            Self::NextHeading => headings.next(),
+            Self::PrevHeading => headings.last(),
+        };
+
+        match (heading, level) {
+            (Some(heading), _) => Ok(heading.clone()),
+            (None, None) => bail!("there is no {} heading", self.direction()),
+            (None, Some(level)) => {
+                bail!("there is no {} heading of level {level}", self.direction())
+            }
+        }
+    }
+
+    /// The direction in which to look for the heading, for error messages.
+    fn direction(self) -> &'static str {
+        match self {
+            Self::NextHeading => "next",
+            Self::PrevHeading => "previous",
+        }
+    }
+}
+
 impl From<Destination> for LinkTarget {
     fn from(dest: Destination) -> Self {
         Self::Dest(dest)
@@ -151,6 +1248,56 @@ pub enum Destination {
     Location(Location),
 }
 
+impl Destination {
+    /// Normalize the destination so that equivalent destinations compare and
+    /// hash equal.
+    ///
+    /// Positions are rounded to a fixed precision and a trailing slash is
+    /// removed from URLs without query or fragment. This is meant for
+    /// de-duplicating destinations, the normalized URL should not be used as
+    /// the actual link target.
+    pub fn canonical(&self) -> Self {
+        match self {
+            Self::Url(url) => {
+                let trimmed = if url.contains(['?', '#']) {
+                    url.as_str()
+                } else {
+                    url.strip_suffix('/').unwrap_or(url)
+                };
+                Self::Url(trimmed.into())
+            }
+            Self::Position(pos) => Self::Position(pos.canonical()),
+            Self::Location(loc) => Self::Location(*loc),
+        }
+    }
+
+    /// The fragment of a URL that consists of nothing but a fragment, like
+    /// `#intro`, without the leading `#`.
+    pub fn fragment(&self) -> Option<&str> {
+        match self {
+            Self::Url(url) => url.strip_prefix('#').filter(|name| !name.is_empty()),
+            _ => None,
+        }
+    }
+
+    /// Whether this is a URL without a scheme, like `about.html#team` or a
+    /// bare fragment. Such URLs are relative to the document.
+    pub fn is_relative(&self) -> bool {
+        matches!(self, Self::Url(_)) && self.scheme().is_none()
+    }
+
+    /// The scheme of a URL, like `https` or `mailto`, without the colon.
+    pub fn scheme(&self) -> Option<&str> {
+        let Self::Url(url) = self else { return None };
+        let (scheme, _) = url.split_once(':')?;
+        let valid = scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+            && scheme
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'));
+        valid.then_some(scheme)
+    }
+}
+
 impl Repr for Destination {
     fn repr(&self) -> EcoString {
         eco_format!("{self:?}")
@@ -168,3 +1315,71 @@ cast! {
     v: Position => Self::Position(v),
     v: Location => Self::Location(v),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pos(x: f64, y: f64) -> Destination {
+        Destination::Position(Position {
+            page: NonZeroUsize::new(1).unwrap(),
+            point: Point::new(Abs::raw(x), Abs::raw(y)),
+        })
+    }
+
+    fn url(s: &str) -> Destination {
+        Destination::Url(s.into())
+    }
+
+    #[test]
+    fn test_canonical_url() {
+        assert_eq!(url("https://typst.app/").canonical(), url("https://typst.app"));
+        assert_eq!(url("https://typst.app").canonical(), url("https://typst.app"));
+        assert_eq!(
+            url("https://typst.app/?q=/").canonical(),
+            url("https://typst.app/?q=/")
+        );
+        assert_ne!(url("https://typst.app/a").canonical(), url("https://typst.app/b"));
+    }
+
+    #[test]
+    fn test_relative_url() {
+        assert!(url("about.html#team").is_relative());
+        assert!(url("#intro").is_relative());
+        assert!(url("./a:b").is_relative());
+        assert!(!url("https://typst.app").is_relative());
+        assert!(!url("mailto:hello@typst.app").is_relative());
+        assert_eq!(url("#intro").fragment(), Some("intro"));
+        assert_eq!(url("#").fragment(), None);
+        assert_eq!(url("about.html#team").fragment(), None);
+    }
+
+    #[test]
+    fn test_url_scheme() {
+        assert_eq!(url("https://typst.app").scheme(), Some("https"));
+        assert_eq!(url("JavaScript:alert(1)").scheme(), Some("JavaScript"));
+        assert_eq!(url("svn+ssh://host").scheme(), Some("svn+ssh"));
+        assert_eq!(url("./a:b").scheme(), None);
+        assert_eq!(url("#intro").scheme(), None);
+    }
+
+    #[test]
+    fn test_scheme_allowlist() {
+        let default: Vec<EcoString> = vec!["".into(), "https".into()];
+        let restricted: Vec<EcoString> = vec!["https".into()];
+        assert!(is_scheme_allowed(&default, &url("HTTPS://typst.app")));
+        assert!(is_scheme_allowed(&default, &url("about.html")));
+        assert!(is_scheme_allowed(&default, &url("#intro")));
+        assert!(!is_scheme_allowed(&default, &url("javascript:alert(1)")));
+        assert!(is_scheme_allowed(&restricted, &url("https://typst.app")));
+        assert!(!is_scheme_allowed(&restricted, &url("about.html")));
+        assert!(!is_scheme_allowed(&restricted, &url("#intro")));
+        assert!(is_scheme_allowed(&restricted, &pos(0.0, 0.0)));
+    }
+
+    #[test]
+    fn test_canonical_position() {
+        assert_eq!(pos(0.1 + 0.2, -0.0).canonical(), pos(0.3, 0.0).canonical());
+        assert_ne!(pos(0.3, 0.0).canonical(), pos(0.4, 0.0).canonical());
+    }
+}
@@ -259,8 +259,8 @@ impl Finalize for OutlineElem {
 }
 
 impl LocalName for OutlineElem {
-    fn local_name(lang: Lang, region: Option<Region>) -> &'static str {
-        match lang {
+    fn translate(lang: Lang, region: Option<Region>) -> Option<&'static str> {
+        Some(match lang {
             Lang::ALBANIAN => "Përmbajtja",
             Lang::ARABIC => "المحتويات",
             Lang::BOKMÅL => "Innhold",
@@ -291,8 +291,9 @@ impl LocalName for OutlineElem {
             Lang::UKRAINIAN => "Зміст",
             Lang::VIETNAMESE => "Mục lục",
             Lang::JAPANESE => "目次",
-            Lang::ENGLISH | _ => "Contents",
-        }
+            Lang::ENGLISH => "Contents",
+            _ => return None,
+        })
     }
 }
 
@@ -11,6 +11,7 @@ use crate::math::EquationElem;
 use crate::model::{
     BibliographyElem, CiteElem, Destination, Figurable, FootnoteElem, Numbering,
 };
+use crate::syntax::Span;
 use crate::text::TextElem;
 
 /// A reference to a label or bibliography.
@@ -180,60 +181,84 @@ impl Show for RefElem {
                 return Ok(FootnoteElem::with_label(target).spanned(span).pack());
             }
 
-            let elem = elem.clone();
-            let refable = elem
-                .with::<dyn Refable>()
-                .ok_or_else(|| {
-                    if elem.can::<dyn Figurable>() {
-                        eco_format!(
-                            "cannot reference {} directly, try putting it into a figure",
-                            elem.func().name()
-                        )
-                    } else {
-                        eco_format!("cannot reference {}", elem.func().name())
-                    }
-                })
-                .at(span)?;
+            let loc = elem.location().unwrap();
+            let supplement = self.supplement(styles);
+            let content = reference_text(engine, elem.clone(), supplement, span)?;
+            Ok(content.linked(Destination::Location(loc)))
+        }))
+    }
+}
 
-            let numbering = refable
-                .numbering()
-                .ok_or_else(|| {
-                    eco_format!(
-                        "cannot reference {} without numbering",
-                        elem.func().name()
-                    )
-                })
-                .hint(eco_format!(
-                    "you can enable {} numbering with `#set {}(numbering: \"1.\")`",
-                    elem.func().name(),
-                    if elem.func() == EquationElem::elem() {
-                        "math.equation"
-                    } else {
-                        elem.func().name()
-                    }
-                ))
-                .at(span)?;
+/// Derive the text a reference to an element displays: The element's
+/// supplement followed by its number.
+///
+/// This is also used by links to labels without a body.
+pub(crate) fn reference_text(
+    engine: &mut Engine,
+    elem: Content,
+    supplement: &Smart<Option<Supplement>>,
+    span: Span,
+) -> SourceResult<Content> {
+    let numbers = reference_number(engine, &elem, span)?;
+    let refable = elem.with::<dyn Refable>().unwrap();
+    let supplement = match supplement {
+        Smart::Auto => refable.supplement(),
+        Smart::Custom(None) => Content::empty(),
+        Smart::Custom(Some(supplement)) => supplement.resolve(engine, [elem])?,
+    };
 
-            let loc = elem.location().unwrap();
-            let numbers = refable
-                .counter()
-                .at(engine, loc)?
-                .display(engine, &numbering.trimmed())?;
+    let mut content = numbers;
+    if !supplement.is_empty() {
+        content = supplement + TextElem::packed("\u{a0}") + content;
+    }
 
-            let supplement = match self.supplement(styles).as_ref() {
-                Smart::Auto => refable.supplement(),
-                Smart::Custom(None) => Content::empty(),
-                Smart::Custom(Some(supplement)) => supplement.resolve(engine, [elem])?,
-            };
+    Ok(content)
+}
 
-            let mut content = numbers;
-            if !supplement.is_empty() {
-                content = supplement + TextElem::packed("\u{a0}") + content;
+/// Derive the number a reference to an element displays, in the element's
+/// numbering.
+///
+/// Fails if the element can't be referenced or isn't numbered.
+pub(crate) fn reference_number(
+    engine: &mut Engine,
+    elem: &Content,
+    span: Span,
+) -> SourceResult<Content> {
+    let refable = elem
+        .with::<dyn Refable>()
+        .ok_or_else(|| {
+            if elem.can::<dyn Figurable>() {
+                eco_format!(
+                    "cannot reference {} directly, try putting it into a figure",
+                    elem.func().name()
+                )
+            } else {
+                eco_format!("cannot reference {}", elem.func().name())
             }
+        })
+        .at(span)?;
 
-            Ok(content.linked(Destination::Location(loc)))
-        }))
-    }
+    let numbering = refable
+        .numbering()
+        .ok_or_else(|| {
+            eco_format!("cannot reference {} without numbering", elem.func().name())
+        })
+        .hint(eco_format!(
+            "you can enable {} numbering with `#set {}(numbering: \"1.\")`",
+            elem.func().name(),
+            if elem.func() == EquationElem::elem() {
+                "math.equation"
+            } else {
+                elem.func().name()
+            }
+        ))
+        .at(span)?;
+
+    let loc = elem.location().unwrap();
+    refable
+        .counter()
+        .at(engine, loc)?
+        .display(engine, &numbering.trimmed())
 }
 
 impl RefElem {
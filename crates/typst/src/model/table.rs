@@ -1,15 +1,35 @@
-use crate::diag::SourceResult;
+use std::num::NonZeroUsize;
+use std::str::FromStr;
+
+use comemo::Track;
+use ecow::{eco_format, EcoString};
+use smallvec::smallvec;
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::diag::{bail, warning, At, SourceResult};
 use crate::engine::Engine;
+use crate::eval::Tracer;
 use crate::foundations::{
-    cast, elem, scope, Content, Fold, NativeElement, Show, Smart, StyleChain,
+    array, cast, dict, elem, scope, Args, Array, Cast, Construct, Content, Dict,
+    ElementFields, Fold, Func, Guard, IntoValue, Label, NativeElement, Resolve, Show,
+    Smart, StyleChain, Styles, Synthesize, Value,
 };
+use crate::introspection::{Locatable, Locator, Meta};
 use crate::layout::{
-    show_grid_cell, Abs, Align, Axes, Cell, CellGrid, Celled, Fragment, GridLayouter,
-    Layout, Length, Regions, Rel, ResolvableCell, Sides, TrackSizings,
+    show_grid_cell, Abs, Aggregate, Align, Angle, Axes, Cell, CellDisplay, CellGrid,
+    CellOverflow, CellProperties, Celled, ColumnMode, Fr, Fragment, Frame, FrameItem,
+    GridLayouter, IncompleteRow, Layout, Length, NumberFormat, Point, Ratio, Regions,
+    Rel, ResolvableCell, ResolvableGridChild, Sides, Size, Sizing, StrokeMode,
+    TrackSizings, Transform, VAlign,
 };
-use crate::model::Figurable;
-use crate::text::{Lang, LocalName, Region};
-use crate::visualize::{Paint, Stroke};
+use crate::model::{Figurable, FigureCaption, FigureElem, Numbering, NumberingPattern};
+use crate::syntax::Spanned;
+use crate::text::{
+    number_separators, Lang, LinebreakElem, LocalName, Region, SpaceElem, SuperElem,
+    TextDir, TextElem,
+};
+use crate::util::{NonZeroExt, Numeric};
+use crate::visualize::{Paint, Path, Stroke};
 
 /// A table of items.
 ///
@@ -26,7 +46,28 @@ use crate::visualize::{Paint, Stroke};
 /// more information.
 ///
 /// To give a table a caption and make it [referenceable]($ref), put it into a
-/// [figure]($figure).
+/// [figure]($figure) or pass a [`caption`]($table.caption) to the table
+/// directly. Alternatively, a [`table.title`]($table.title) can be placed
+/// among the table's children to show a title in a row of the table itself.
+///
+/// Header cells can be grouped into a [`table.header`]($table.header), which is
+/// repeated on every page the table spans. Together with cells spanning
+/// multiple columns, this allows for multi-level column headers:
+///
+/// ```example
+/// #table(
+///   columns: 5,
+///   align: center,
+///   table.header(
+///     [],
+///     table.cell(colspan: 2)[*2022*],
+///     table.cell(colspan: 2)[*2023*],
+///     [*Region*], [*H1*], [*H2*], [*H1*], [*H2*],
+///   ),
+///   [North], [12], [14], [15], [17],
+///   [South], [9], [11], [10], [13],
+/// )
+/// ```
 ///
 /// # Example
 /// ```example
@@ -47,12 +88,48 @@ use crate::visualize::{Paint, Stroke};
 ///   [$a$: edge length]
 /// )
 /// ```
-#[elem(scope, Layout, LocalName, Figurable)]
+#[elem(scope, Locatable, Synthesize, Show, Layout, LocalName, Figurable)]
 pub struct TableElem {
     /// The column sizes. See the [grid documentation]($grid) for more
     /// information on track sizing.
+    ///
+    /// If no columns are given, the table has as many `{auto}`-sized columns
+    /// as the cells of its [header]($table.header) span. Without a header, the
+    /// first [row]($table.row) determines the number of columns. Explicit
+    /// columns always take precedence.
+    ///
+    /// ```example
+    /// #table(
+    ///   table.header[*Name*][*Age*],
+    ///   [Alice], [29],
+    ///   [Bob], [35],
+    /// )
+    /// ```
+    ///
+    /// With a dictionary like `{(auto-fit: 4cm)}`, the table has as many
+    /// equally wide columns as fit into the available width, each at least as
+    /// wide as given. The cells flow into these columns and the last row is
+    /// filled with empty cells if it is incomplete, regardless of
+    /// [`on-incomplete-row`]($table.on-incomplete-row). The number of columns
+    /// is determined by the width of the region the table starts in and kept
+    /// when the table breaks across pages of different widths.
+    ///
+    /// ```example
+    /// #set page(width: 240pt)
+    /// #table(
+    ///   columns: (auto-fit: 60pt),
+    ///   ..range(1, 8).map(n => [Card #n]),
+    /// )
+    /// ```
     #[borrowed]
-    pub columns: TrackSizings,
+    #[parse(
+        let rows_data = parse_rows_data(args)?;
+        args.named("columns")?.or_else(|| {
+            let count = rows_data.as_ref()?.first().map_or(0, Vec::len);
+            Some(TableColumns::Tracks(TrackSizings(smallvec![Sizing::Auto; count])))
+        })
+    )]
+    pub columns: TableColumns,
 
     /// The row sizes. See the [grid documentation]($grid) for more information
     /// on track sizing.
@@ -79,11 +156,91 @@ pub struct TableElem {
     #[borrowed]
     pub row_gutter: TrackSizings,
 
+    /// Groups the columns by widening the gap after every `size` columns to
+    /// `gutter`.
+    ///
+    /// The group gutter takes precedence over the
+    /// [`column-gutter`]($table.column-gutter) at the boundaries between
+    /// groups, while the gaps within a group keep the column gutter. If the
+    /// number of columns isn't a multiple of the group size, the last group
+    /// just ends with the last column.
+    ///
+    /// ```example
+    /// #table(
+    ///   columns: 7,
+    ///   group-columns: (size: 3, gutter: 8pt),
+    ///   [*Q*], ..range(1, 7).map(n => [#n]),
+    ///   [*A*], ..range(6).map(n => [#(n * 12)]),
+    /// )
+    /// ```
+    pub group_columns: Option<ColumnGroups>,
+
+    /// Collapses the table into a list of label-value pairs if the region it
+    /// is laid out in is narrower than this width.
+    ///
+    /// The collapsed table has two columns. Each cell turns into a row of its
+    /// own, with the cell itself on the right and the label of its column on
+    /// the left. The labels are the cells of the table's
+    /// [header]($table.header) or, without a header, the numbers of the
+    /// columns starting at one. A cell spanning multiple columns is labelled
+    /// by its first column. The header and footer as well as the settings of
+    /// individual [columns]($table.column) are dropped from the collapsed
+    /// table, while the rest of its look is kept.
+    ///
+    /// ```example
+    /// #set table(columns: 3, collapse-below: 200pt)
+    /// #let data = (
+    ///   table.header[*City*][*Country*][*Population*],
+    ///   [Tokyo], [Japan], [37M],
+    ///   [Delhi], [India], [32M],
+    /// )
+    ///
+    /// #table(..data)
+    /// #block(width: 150pt, table(..data))
+    /// ```
+    pub collapse_below: Option<Length>,
+
+    /// What to do if the table is wider than the available space, e.g.
+    /// because its fixed columns add up to more than the width of the page.
+    ///
+    /// - `{"visible"}`: The table extends past the edge of the available
+    ///   space.
+    /// - `{"warn"}`: Like `{"visible"}`, but a warning is emitted.
+    /// - `{"clip"}`: The table is cut off at the edge of the available space.
+    /// - `{"shrink"}`: The whole table is scaled down uniformly, so that it
+    ///   fits the available width.
+    ///
+    /// ```example
+    /// #table(
+    ///   columns: (80pt,) * 4,
+    ///   overflow: "shrink",
+    ///   [North], [East], [South], [West],
+    /// )
+    /// ```
+    #[default(TableOverflow::Visible)]
+    pub overflow: TableOverflow,
+
     /// How to fill the cells.
     ///
     /// This can be a color or a function that returns a color. The function is
     /// passed the cells' column and row index, starting at zero. This can be
-    /// used to implement striped tables.
+    /// used to implement striped tables, though [`stripes`]($table.stripes)
+    /// are easier to use for tables with a header. Cells in a
+    /// [row]($table.row) with a `group` are additionally passed the index of
+    /// their band as a named `band` argument.
+    ///
+    /// Besides colors, cells can be filled with [gradients]($gradient) and
+    /// [patterns]($pattern). By default, these are relative to each cell's own
+    /// bounds, so that a gradient spans every cell from edge to edge. With
+    /// `relative: "parent"`, they are relative to the whole table instead.
+    ///
+    /// ```example
+    /// #table(
+    ///   columns: 3,
+    ///   fill: (col, _) => gradient.linear(white, (red, green, blue).at(col)),
+    ///   [A], [B], [C],
+    /// )
+    /// ```
     ///
     /// ```example
     /// #table(
@@ -102,6 +259,53 @@ pub struct TableElem {
     #[borrowed]
     pub fill: Celled<Option<Paint>>,
 
+    /// Fills to stripe the rows of the table's body with.
+    ///
+    /// The fills are cycled through row by row, starting with the first row
+    /// after the [header]($table.header). Unlike a [`fill`]($table.fill)
+    /// function, which counts rows from the top of the table, this leaves the
+    /// header and [footer]($table.footer) alone. Their cells keep the `fill`,
+    /// as do all cells while there are no stripes.
+    ///
+    /// ```example
+    /// #table(
+    ///   columns: 2,
+    ///   stripes: (luma(230), none),
+    ///   table.header[*Fruit*][*Price*],
+    ///   [Apple], [1.20],
+    ///   [Banana], [0.80],
+    ///   [Cherry], [3.10],
+    /// )
+    /// ```
+    #[borrowed]
+    pub stripes: Vec<Option<Paint>>,
+
+    /// Content to draw behind the cells, for example a pattern or a small
+    /// chart.
+    ///
+    /// This can be content for all cells or a function of the form
+    /// `(column, row) => content` for individual cells, which may also return
+    /// `{none}`. The background is laid out into the cell's full size,
+    /// including its inset, and clipped to it. If the cell breaks across
+    /// pages, the background is laid out anew for each part.
+    ///
+    /// The background is drawn above the cell's [fill]($table.fill), but below
+    /// the table's lines and the cell's body.
+    ///
+    /// ```example
+    /// #table(
+    ///   columns: 3,
+    ///   background: (x, y) => if x == 2 {
+    ///     align(bottom, rect(width: 100%, height: 30%, fill: aqua))
+    ///   },
+    ///   [Item], [Count], [Share],
+    ///   [Apples], [12], [30%],
+    ///   [Pears], [28], [70%],
+    /// )
+    /// ```
+    #[borrowed]
+    pub background: Celled<Option<Content>>,
+
     /// How to align the cells' content.
     ///
     /// This can either be a single alignment, an array of alignments
@@ -120,20 +324,246 @@ pub struct TableElem {
     #[borrowed]
     pub align: Celled<Smart<Align>>,
 
+    /// How to align the cells' content vertically, unless their alignment
+    /// already has a vertical component.
+    ///
+    /// Cells in rows taller than their content are otherwise aligned to the
+    /// top. With `{horizon}`, all cells are centered vertically within their
+    /// row, while a vertical alignment given through
+    /// [`align`]($table.align) or to a [cell]($table.cell.align) still takes
+    /// precedence.
+    ///
+    /// ```example
+    /// #table(
+    ///   columns: 3,
+    ///   valign: horizon,
+    ///   [Short], lorem(8), table.cell(align: bottom)[Bottom],
+    /// )
+    /// ```
+    pub valign: Smart<VAlign>,
+
     /// How to [stroke]($stroke) the cells.
     ///
-    /// Strokes can be disabled by setting this to `{none}`.
+    /// Strokes can be disabled by setting this to `{none}`. This only removes
+    /// the table's own lines: Sides for which cells set a
+    /// [stroke]($table.cell.stroke) are still drawn, so that individual
+    /// lines can be added to a table without lines. The corners of the
+    /// table's outer border are joined according to the stroke's
+    /// [`join`]($stroke.join).
+    ///
+    /// Like the fill, the stroke can also be an array of strokes for each
+    /// column or a function that receives a cell's column and row and returns
+    /// its stroke. In that case, each edge between two cells is drawn with the
+    /// stroke of the cell above or to the left of it. The outer edges of the
+    /// table are drawn with the stroke of the cell they belong to.
+    ///
+    /// ```example
+    /// #table(
+    ///   columns: 3,
+    ///   stroke: (x, y) => if x == y { 2pt + red } else { 0.5pt },
+    ///   [1], [2], [3],
+    ///   [4], [5], [6],
+    ///   [7], [8], [9],
+    /// )
+    /// ```
+    #[borrowed]
+    #[default(Celled::Value(Some(Stroke::default())))]
+    pub stroke: Celled<Option<Stroke>>,
+
+    /// Whether the stroke is drawn as lines shared between cells, as a
+    /// separate border around each cell or only as horizontal rules.
+    ///
+    /// With `{"cells"}`, combined with a gutter, cells look like separate
+    /// cards. Without a gutter, the borders of adjacent cells touch, so
+    /// shared edges are effectively drawn twice as thick.
+    ///
+    /// ```example
+    /// #table(
+    ///   columns: 3,
+    ///   gutter: 4pt,
+    ///   stroke-mode: "cells",
+    ///   [A], [B], [C],
+    ///   [D], [E], [F],
+    /// )
+    /// ```
+    ///
+    /// With `{"booktabs"}`, the table is drawn open in the style of academic
+    /// publications: There are only rules at the top and bottom and a lighter
+    /// rule below the [header]($table.header), if there is one. This requires
+    /// a single stroke, which determines the thickness of the outer rules.
+    ///
+    /// ```example
+    /// #table(
+    ///   columns: 3,
+    ///   stroke-mode: "booktabs",
+    ///   table.header[Name][Size][Age],
+    ///   [Alice], [1.62m], [29],
+    ///   [Bob], [1.80m], [35],
+    /// )
+    /// ```
+    #[default(StrokeMode::Grid)]
+    pub stroke_mode: StrokeMode,
+
+    /// The stroke of a rule between the [header]($table.header) and the rows
+    /// below it.
+    ///
+    /// The rule is drawn over the line otherwise drawn there, while all other
+    /// lines keep the table's [`stroke`]($table.stroke). Where the header is
+    /// repeated on a new page, the rule is repeated below it. Tables without
+    /// a header have no such rule.
+    ///
+    /// ```example
+    /// #table(
+    ///   columns: 2,
+    ///   header-stroke: 1.5pt,
+    ///   table.header[*Name*][*Age*],
+    ///   [Alice], [29],
+    ///   [Bob], [35],
+    /// )
+    /// ```
+    pub header_stroke: Option<Stroke>,
+
+    /// The minimum thickness with which the table's lines are drawn.
+    ///
+    /// Very thin lines may vanish or look uneven when the document is viewed
+    /// or printed at a low resolution. Lines whose stroke is thinner than this
+    /// are drawn with this thickness instead, while their positions stay the
+    /// same. By default, lines are drawn exactly as thick as their stroke.
     ///
-    /// _Note:_ Richer stroke customization for individual cells is not yet
-    /// implemented, but will be in the future. In the meantime, you can use the
-    /// third-party [tablex library](https://github.com/PgBiel/typst-tablex/).
+    /// ```example
+    /// #table(
+    ///   columns: 2,
+    ///   stroke: 0.05pt,
+    ///   hairline: 0.25pt,
+    ///   [A], [B],
+    ///   [C], [D],
+    /// )
+    /// ```
     #[resolve]
-    #[fold]
-    #[default(Some(Stroke::default()))]
-    pub stroke: Option<Stroke>,
+    pub hairline: Length,
+
+    /// The distance between the lines of a baseline grid for the cells.
+    ///
+    /// If set, the first line of text in each cell is moved down onto the
+    /// next line of a grid, counted from the top of the cell's row. Text in
+    /// cells with different insets then still lines up across the row.
+    /// Subsequent lines of a cell follow the first one at the usual
+    /// [leading]($par.leading). Centered content moves half as far and
+    /// bottom-aligned content moves up to the previous grid line instead.
+    /// Cells without any text are left as they are.
+    ///
+    /// ```example
+    /// #table(
+    ///   columns: 2,
+    ///   baseline-grid: 14pt,
+    ///   inset: (x: 5pt),
+    ///   table.cell(inset: (top: 2pt))[Small],
+    ///   table.cell(inset: (top: 6pt))[Large],
+    /// )
+    /// ```
+    #[resolve]
+    pub baseline_grid: Option<Length>,
+
+    /// What to do if the last row of the table's body has fewer cells than
+    /// there are columns, for example because of an odd number of cells.
+    ///
+    /// - `{"fill"}`: The row is filled up with empty cells.
+    /// - `{"error"}`: The table fails with an error at the row's last cell.
+    /// - `{"drop"}`: The row and its cells are left out.
+    ///
+    /// Cells spanning multiple columns count towards all of their columns.
+    /// This only concerns the body, the rows of a [header]($table.header) and
+    /// [footer]($table.footer) are always filled up.
+    ///
+    /// ```example
+    /// #table(
+    ///   columns: 2,
+    ///   on-incomplete-row: "drop",
+    ///   [A], [B],
+    ///   [C], [D],
+    ///   [Leftover],
+    /// )
+    /// ```
+    #[default(IncompleteRow::Fill)]
+    pub on_incomplete_row: IncompleteRow,
+
+    /// How to number the [notes]($table.cell.note) of the table's cells.
+    ///
+    /// The notes are numbered per table, starting at one, in the order in
+    /// which their cells are given, regardless of where the cells end up.
+    /// Each noted cell is marked with its number as a superscript and the
+    /// notes are listed below the table, in front of a bottom
+    /// [title]($table.title).
+    ///
+    /// ```example
+    /// #table(
+    ///   columns: 2,
+    ///   note-numbering: "*",
+    ///   [Year], [Revenue],
+    ///   [2022], table.cell(note: [Estimated.])[120],
+    ///   [2023], table.cell(note: [Restated.])[135],
+    /// )
+    /// ```
+    #[default(Numbering::Pattern(NumberingPattern::from_str("a").unwrap()))]
+    pub note_numbering: Numbering,
+
+    /// Whether to balance the widths of `{auto}` columns. See the
+    /// [grid documentation]($grid.balance) for more information.
+    ///
+    /// ```example
+    /// #set page(width: 200pt)
+    /// #table(
+    ///   columns: 2,
+    ///   balance: true,
+    ///   [Short], lorem(20),
+    /// )
+    /// ```
+    #[default(false)]
+    pub balance: bool,
+
+    /// The minimum number of rows to keep together when the table breaks
+    /// across pages.
+    ///
+    /// If a page break would leave fewer rows than this at the bottom of a
+    /// page or at the top of the next one, the break happens earlier. Header
+    /// rows don't count towards this, but the header is kept together with
+    /// the first rows. This has no effect if the table has fewer rows in
+    /// total.
+    ///
+    /// ```example
+    /// #set page(height: 120pt)
+    /// #v(50pt)
+    /// #table(
+    ///   columns: 2,
+    ///   min-rows-together: 2,
+    ///   ..range(8).map(str),
+    /// )
+    /// ```
+    #[default(NonZeroUsize::ONE)]
+    pub min_rows_together: NonZeroUsize,
+
+    /// How densely to lay out the table.
+    ///
+    /// Each density comes with a preset [inset]($table.inset) for the cells:
+    /// `{"compact"}` pads them by `{2.5pt}`, `{"comfortable"}` by `{5pt}` and
+    /// `{"spacious"}` by `{8pt}`. Sides of the inset that are set explicitly
+    /// take precedence over the preset.
+    ///
+    /// ```example
+    /// #set table(columns: 2)
+    /// #table(density: "compact", [A], [B], [C], [D])
+    /// #table(density: "spacious", [A], [B], [C], [D])
+    /// ```
+    pub density: TableDensity,
 
     /// How much to pad the cells' content.
     ///
+    /// If only some sides are given, the others keep their previous value.
+    /// For instance, `{set table(inset: (x: 8pt))}` only changes the
+    /// horizontal inset, while the vertical inset stays at the preset of the
+    /// table's [density]($table.density) or whatever an earlier set rule
+    /// configured.
+    ///
     /// ```example
     /// #table(
     ///   inset: 10pt,
@@ -152,18 +582,178 @@ pub struct TableElem {
     /// )
     /// ```
     #[fold]
-    #[default(Sides::splat(Abs::pt(5.0).into()))]
+    #[default(Sides::splat(TableDensity::default().inset()))]
     pub inset: Sides<Option<Rel<Length>>>,
 
-    /// The contents of the table cells.
+    /// The table's caption.
+    ///
+    /// If given, the table is automatically wrapped in a [figure]($figure)
+    /// with this caption. This is equivalent to putting the table into a
+    /// figure yourself: The figure is numbered among the other tables and a
+    /// label attached to the table refers to the figure.
+    ///
+    /// ```example
+    /// #table(
+    ///   columns: 2,
+    ///   caption: [Planets],
+    ///   [Mercury], [Venus],
+    /// ) <planets>
+    ///
+    /// See @planets.
+    /// ```
+    pub caption: Option<FigureCaption>,
+
+    /// The table's cells as an array of rows, each of which is an array of
+    /// cells.
+    ///
+    /// This is an alternative to passing the cells one after another, which
+    /// can't get out of step with the number of columns. All rows must have
+    /// the same length. If `columns` isn't given, the table has as many
+    /// `{auto}`-sized columns as there are cells in a row. The cells follow
+    /// any cells passed directly, like a header.
+    ///
+    /// ```example
+    /// #let data = (
+    ///   ([Mercury], [0.38]),
+    ///   ([Venus], [0.91]),
+    /// )
+    ///
+    /// #table(
+    ///   table.header[*Planet*][*Gravity*],
+    ///   rows-data: data,
+    /// )
+    /// ```
+    #[external]
+    pub rows_data: Vec<Vec<Content>>,
+
+    /// Whether the table is in a figure with a caption. In that case, a
+    /// title row of the table is not shown, so that the caption and title
+    /// don't double up.
+    #[internal]
+    #[default(false)]
+    pub in_captioned_figure: bool,
+
+    /// The label of a captioned table, which is handed on to the figure the
+    /// table is shown as.
+    #[internal]
+    #[synthesized]
+    #[default(None)]
+    pub figure_label: Option<Label>,
+
+    /// The cells whose content did not fit into them, as `(x, y)` pairs of
+    /// their column and row. A title at the top of the table counts as its
+    /// first row.
+    ///
+    /// A cell overflows when its content is wider than its columns or taller
+    /// than a row of fixed height. This field is only filled in on tables
+    /// retrieved with a [query]($query), where it turns silent overflow into
+    /// something you can test for.
+    ///
+    /// ```example
+    /// #table(
+    ///   columns: 2,
+    ///   rows: 2em,
+    ///   [Short], [Far too \ long],
+    /// ) <prices>
+    ///
+    /// #locate(loc => {
+    ///   query(<prices>, loc).first().overflowed
+    /// })
+    /// ```
+    #[synthesized]
+    #[default(vec![])]
+    pub overflowed: Vec<Array>,
+
+    /// The contents of the table cells, plus an optional header.
+    ///
+    /// The cells are populated, shown and laid out in row-major order. See
+    /// the [grid documentation]($grid.children) for more information.
     #[variadic]
-    pub children: Vec<TableCell>,
+    #[parse({
+        let mut children: Vec<TableChild> = args.all()?;
+        children.extend(rows_data.into_iter().flatten().flatten().map(TableChild::from));
+        children
+    })]
+    pub children: Vec<TableChild>,
 }
 
 #[scope]
 impl TableElem {
     #[elem]
     type TableCell;
+
+    #[elem]
+    type TableHeader;
+
+    #[elem]
+    type TableRow;
+
+    #[elem]
+    type TableColumn;
+
+    #[elem]
+    type TableFooter;
+
+    #[elem]
+    type TableTitle;
+}
+
+/// Parses the `rows-data` argument, making sure that all rows have the same
+/// length.
+fn parse_rows_data(args: &mut Args) -> SourceResult<Option<Vec<Vec<Content>>>> {
+    let Some(Spanned { v: rows, span }) =
+        args.named::<Spanned<Vec<Vec<Content>>>>("rows-data")?
+    else {
+        return Ok(None);
+    };
+
+    let count = rows.first().map_or(0, Vec::len);
+    if let Some((i, row)) = rows.iter().enumerate().find(|(_, row)| row.len() != count) {
+        bail!(
+            span,
+            "row {i} has {} cell(s), but the first row has {count}",
+            row.len();
+            hint: "all rows of `rows-data` must have the same length"
+        );
+    }
+
+    Ok(Some(rows))
+}
+
+impl Synthesize for TableElem {
+    fn synthesize(&mut self, _: &mut Engine, styles: StyleChain) -> SourceResult<()> {
+        // A captioned table is shown as a figure, so its label must refer to
+        // the figure instead of the table.
+        if self.caption(styles).is_some() {
+            let label = self.label.take();
+            self.push_figure_label(label);
+        }
+        Ok(())
+    }
+}
+
+impl Show for TableElem {
+    #[typst_macros::time(name = "table", span = self.span())]
+    fn show(&self, _: &mut Engine, styles: StyleChain) -> SourceResult<Content> {
+        let Some(caption) = self.caption(styles) else {
+            return Ok(self.clone().pack().guarded(Guard::Base(Self::elem())));
+        };
+
+        // A table with a caption is shorthand for a table in a figure.
+        let table = self
+            .clone()
+            .with_caption(None)
+            .pack()
+            .guarded(Guard::Base(Self::elem()));
+        let mut realized = FigureElem::new(table)
+            .with_caption(Some(caption))
+            .spanned(self.span())
+            .pack();
+        if let Some(label) = *self.figure_label() {
+            realized = realized.labelled(label);
+        }
+        Ok(realized)
+    }
 }
 
 impl Layout for TableElem {
@@ -174,37 +764,545 @@ impl Layout for TableElem {
         styles: StyleChain,
         regions: Regions,
     ) -> SourceResult<Fragment> {
-        let inset = self.inset(styles);
+        // In a narrow region, the table is laid out as label-value pairs.
+        if let Some(width) = self.collapse_below(styles) {
+            if regions.size.x.is_finite() && regions.size.x < width.resolve(styles) {
+                return self.collapsed(styles).layout(engine, styles, regions);
+            }
+        }
+
+        let available = regions.size.x;
+        let overflow = self.overflow(styles);
+        if overflow == TableOverflow::Shrink && available.is_finite() {
+            let width = fragment_width(&self.measure_grid(engine, styles, regions)?);
+            if available.fits(width) {
+                return self.layout_grid(engine, styles, regions);
+            }
+
+            // Lay the table out in regions that are scaled up as much as it
+            // is scaled down afterwards, so that it still fills them.
+            let scale = available / width;
+            let mut backlog = vec![];
+            let pod = regions.map(&mut backlog, |size| size / scale);
+            let mut fragment = self.layout_grid(engine, styles, pod)?;
+            let scale = available / fragment_width(&fragment);
+            for frame in fragment.iter_mut() {
+                let scaled = frame.size() * scale;
+                let ratio = Ratio::new(scale);
+                frame.transform(Transform::scale(ratio, ratio));
+                frame.set_size(scaled);
+            }
+            return Ok(fragment);
+        }
+
+        let mut fragment = self.layout_grid(engine, styles, regions)?;
+        if !available.is_finite() || available.fits(fragment_width(&fragment)) {
+            return Ok(fragment);
+        }
+
+        match overflow {
+            TableOverflow::Visible | TableOverflow::Shrink => {}
+            TableOverflow::Warn => engine.tracer.warn(warning!(
+                self.span(),
+                "table is wider than the available space";
+                hint: "set its `overflow` to `\"shrink\"` or `\"clip\"` to make it fit"
+            )),
+            TableOverflow::Clip => {
+                for frame in fragment.iter_mut() {
+                    let size = Size::new(available, frame.height());
+                    frame.set_size(size);
+                    frame.clip(Path::rect(size));
+                }
+            }
+        }
+
+        Ok(fragment)
+    }
+}
+
+/// The width of the widest frame of a fragment.
+fn fragment_width(fragment: &Fragment) -> Abs {
+    fragment.iter().map(Frame::width).fold(Abs::zero(), Abs::max)
+}
+
+impl TableElem {
+    /// Lays out the table's cells in a grid without side effects, to measure
+    /// it. Warnings and delayed errors are dropped, so that only the actual
+    /// layout reports them.
+    fn measure_grid(
+        &self,
+        engine: &mut Engine,
+        styles: StyleChain,
+        regions: Regions,
+    ) -> SourceResult<Fragment> {
+        let mut tracer = Tracer::new();
+        let mut locator = Locator::chained(engine.locator.track());
+        let mut engine = Engine {
+            world: engine.world,
+            route: engine.route.clone(),
+            introspector: engine.introspector,
+            locator: &mut locator,
+            tracer: tracer.track_mut(),
+        };
+        self.layout_grid(&mut engine, styles, regions)
+    }
+
+    /// The inset of the cells. Sides that are neither given to the table nor
+    /// set by a set rule fall back to the preset of the table's density.
+    fn resolved_inset(&self, styles: StyleChain) -> Sides<Rel<Length>> {
+        let preset = self.density(styles).inset();
+        styles.get_fold::<Sides<Option<Rel<Length>>>>(
+            Self::elem(),
+            <Self as ElementFields>::Fields::Inset as u8,
+            self.inset.as_ref(),
+            || Sides::splat(preset),
+        )
+    }
+
+    /// Lays out the table's cells in a grid.
+    fn layout_grid(
+        &self,
+        engine: &mut Engine,
+        styles: StyleChain,
+        regions: Regions,
+    ) -> SourceResult<Fragment> {
+        let inset = self.resolved_inset(styles);
         let align = self.align(styles);
         let columns = self.columns(styles);
         let rows = self.rows(styles);
         let column_gutter = self.column_gutter(styles);
         let row_gutter = self.row_gutter(styles);
         let fill = self.fill(styles);
-        let stroke = self.stroke(styles).map(Stroke::unwrap_or_default);
+        let stroke = self.stroke(styles);
 
-        let tracks = Axes::new(columns.0.as_slice(), rows.0.as_slice());
-        let gutter = Axes::new(column_gutter.0.as_slice(), row_gutter.0.as_slice());
-        let grid = CellGrid::resolve(
-            tracks,
-            gutter,
-            self.children(),
+        // A single stroke is drawn as a whole, per-cell strokes are resolved
+        // along with the cells.
+        let uniform = match stroke {
+            Celled::Value(stroke) => stroke
+                .clone()
+                .map(|stroke| stroke.resolve(styles).unwrap_or_default()),
+            _ => None,
+        };
+
+        // Without explicit columns, there are as many auto columns as the
+        // header or the first row spans. With a minimum width, there are as
+        // many columns as fit into the region.
+        let inferred = match columns {
+            TableColumns::Tracks(tracks) if tracks.0.is_empty() => {
+                vec![Sizing::Auto; self.inferred_columns(styles)]
+            }
+            TableColumns::Tracks(_) => vec![],
+            TableColumns::AutoFit(min) => {
+                self.fit_columns(min.resolve(styles), &column_gutter.0, regions, styles)?
+            }
+        };
+        let tracks = Axes::new(
+            match columns {
+                TableColumns::Tracks(tracks) if inferred.is_empty() => {
+                    tracks.0.as_slice()
+                }
+                _ => &inferred,
+            },
+            rows.0.as_slice(),
+        );
+
+        // Widen the gaps between groups of columns.
+        let grouped = self
+            .group_columns(styles)
+            .map(|groups| groups.gutters(&column_gutter.0, tracks.x.len().max(1)));
+        let gutter = Axes::new(
+            grouped.as_deref().unwrap_or(column_gutter.0.as_slice()),
+            row_gutter.0.as_slice(),
+        );
+        self.check_rows(tracks.x.len().max(1), styles)?;
+
+        // Cells which opt into it pass this table's look on to nested tables.
+        let mut parent = Styles::new();
+        parent.set(TableCell::set_parent_stroke(stroke.clone()));
+        parent.set(TableCell::set_parent_inset(inset.map(Some)));
+        parent.set(TableCell::set_parent_align(align.clone()));
+
+        // The cells snap to the table's baseline grid when they are shown.
+        let baseline_grid = self.baseline_grid(styles).map(Into::into);
+        parent.set(TableElem::set_baseline_grid(baseline_grid));
+
+        // The cells take the table's vertical alignment when they are resolved.
+        parent.set(TableElem::set_valign(self.valign(styles)));
+        let styles = styles.chain(&parent);
+
+        // Collect the number formats of the columns.
+        let mut formats = vec![None; tracks.x.len().max(1)];
+        let mut decimals = vec![None; formats.len()];
+        let mut modes = vec![None; formats.len()];
+        let mut keys = vec![None; formats.len()];
+        let (thousands, decimal) =
+            number_separators(TextElem::lang_in(styles), TextElem::region_in(styles));
+        for child in self.children() {
+            let TableChild::Column(column) = child else {
+                continue;
+            };
+            let x = *column.x();
+            let Some(slot) = formats.get_mut(x) else {
+                bail!(
+                    column.span(),
+                    "column index {x} is out of range";
+                    hint: "the table only has {} column(s)", tracks.x.len().max(1)
+                );
+            };
+            let decimal = column.decimal_separator(styles).unwrap_or(decimal);
+            *slot = column.format(styles).map(|format| match format {
+                ColumnFormat::Func(func) => NumberFormat::Func(func),
+                ColumnFormat::Number => NumberFormat::Separators {
+                    thousands: column
+                        .thousands_separator(styles)
+                        .unwrap_or_else(|| thousands.into()),
+                    decimal,
+                },
+            });
+            decimals[x] =
+                (column.align(styles) == Some(ColumnAlign::Decimal)).then_some(decimal);
+            modes[x] = column.mode(styles).as_custom();
+            keys[x] = column.key(styles);
+        }
+
+        // Turn the title, if any, into a cell spanning all columns.
+        let mut titles = self.children().iter().filter_map(|child| match child {
+            TableChild::Title(title) => Some(title),
+            _ => None,
+        });
+        let title = titles.next();
+        if let Some(extra) = titles.next() {
+            bail!(extra.span(), "table cannot have more than one title");
+        }
+        let title = title.filter(|_| !self.in_captioned_figure(styles)).map(|title| {
+            let cell = TableCell::new(title.body().clone())
+                .with_colspan(NonZeroUsize::new(tracks.x.len().max(1)).unwrap())
+                .with_align(Smart::Custom(title.align(styles)))
+                .with_continued(title.continued_body(styles))
+                .spanned(title.span());
+            (title.position(styles), ResolvableGridChild::Title(cell))
+        });
+        let (top, bottom) = match title {
+            Some((VAlign::Bottom, child)) => (None, Some(child)),
+            Some((_, child)) => (Some(child), None),
+            None => (None, None),
+        };
+
+        // Mark the cells with notes and list the notes in a cell spanning all
+        // columns below the body.
+        let annotated = self.annotate_notes(engine, styles)?;
+        let (children, notes) = match &annotated {
+            Some((children, notes)) => (children.as_slice(), Some(notes)),
+            None => (self.children().as_slice(), None),
+        };
+        let notes = notes.map(|notes| {
+            let lines = notes.iter().enumerate().flat_map(|(i, note)| {
+                let linebreak = (i > 0).then(|| LinebreakElem::new().pack());
+                linebreak.into_iter().chain([note.clone()])
+            });
+            let cell = TableCell::new(Content::sequence(lines))
+                .with_colspan(NonZeroUsize::new(tracks.x.len().max(1)).unwrap())
+                .with_align(Smart::Custom(Align::START))
+                .spanned(self.span());
+            ResolvableGridChild::Title(cell)
+        });
+
+        let children = children.iter().filter_map(|child| match child {
+            TableChild::Header(header) => Some(ResolvableGridChild::Header {
+                repeat: header.repeat(styles),
+                span: header.span(),
+                rows: header.rows(styles).clone(),
+                items: header.children().iter().cloned(),
+            }),
+            TableChild::Row(row) => Some(ResolvableGridChild::Row {
+                group: row.group(styles),
+                key: row.key(styles),
+                keep: row.keep(styles),
+                span: row.span(),
+                items: row.children().iter().cloned(),
+            }),
+            TableChild::Footer(footer) => Some(ResolvableGridChild::Footer {
+                aggregates: match footer.aggregate(styles) {
+                    Some(Aggregates::All(v)) => vec![Some(v); formats.len()],
+                    Some(Aggregates::Columns(aggregates)) => aggregates,
+                    None => vec![],
+                },
+                items: footer.children().iter().cloned(),
+            }),
+            TableChild::Column(_) | TableChild::Title(_) => None,
+            TableChild::Item(cell) => Some(ResolvableGridChild::Item(cell.clone())),
+        });
+        let properties = CellProperties {
             fill,
+            stripes: self.stripes(styles),
             align,
+            stroke,
+            background: self.background(styles),
             inset,
+            formats: &formats,
+            decimals: &decimals,
+            column_keys: &keys,
+            incomplete: match columns {
+                TableColumns::Tracks(_) => self.on_incomplete_row(styles),
+                TableColumns::AutoFit(_) => IncompleteRow::Fill,
+            },
+        };
+        let grid = CellGrid::resolve(
+            tracks,
+            gutter,
+            top.into_iter().chain(children).chain(notes).chain(bottom),
+            &properties,
             engine,
             styles,
         )?;
 
-        let layouter = GridLayouter::new(&grid, &stroke, regions, styles, self.span());
+        // Overrides of individual sides are only drawn with per-cell strokes.
+        let uniform = match self.stroke_mode(styles) {
+            StrokeMode::Grid if grid.has_stroke_overrides() => None,
+            _ => uniform,
+        };
+
+        let layouter = GridLayouter::new(&grid, &uniform, regions, styles, self.span())
+            .with_stroke_mode(self.stroke_mode(styles))
+            .with_balance(self.balance(styles))
+            .with_min_rows(self.min_rows_together(styles).get())
+            .with_hairline(self.hairline(styles))
+            .with_header_stroke(
+                self.header_stroke(styles)
+                    .map(|stroke| stroke.resolve(styles).unwrap_or_default()),
+            )
+            .with_column_modes(&modes);
+
+        let (mut fragment, overflowed) = layouter.layout_with_overflow(engine)?;
+
+        // Make the overflowing cells queryable. The table's metadata in its
+        // first frame takes precedence over the one attached during
+        // realization, which follows the table.
+        if self.location().is_some() {
+            let mut elem = self.clone();
+            elem.push_overflowed(
+                overflowed.into_iter().map(|pos| array![pos.x, pos.y]).collect(),
+            );
+            if let Some(frame) = fragment.iter_mut().next() {
+                let meta = Meta::Elem(elem.pack());
+                frame.prepend(Point::zero(), FrameItem::Meta(meta, Size::zero()));
+            }
+        }
+
+        Ok(fragment)
+    }
+
+    /// Marks each cell that has a note with the note's number and collects
+    /// the notes in the order in which their cells are given. Returns `None`
+    /// if no cell has a note.
+    fn annotate_notes(
+        &self,
+        engine: &mut Engine,
+        styles: StyleChain,
+    ) -> SourceResult<Option<(Vec<TableChild>, Vec<Content>)>> {
+        let numbering = self.note_numbering(styles);
+        let mut notes = vec![];
+        let mut annotate = |cell: &TableCell| -> SourceResult<TableCell> {
+            let Some(note) = cell.note(styles) else {
+                return Ok(cell.clone());
+            };
+            let marker = numbering.apply(engine, &[notes.len() + 1])?.display();
+            notes.push(SuperElem::new(marker.clone()).pack() + note);
+            let sup = SuperElem::new(marker).spanned(cell.span()).pack();
+            Ok(cell.clone().with_body(cell.body().clone() + sup))
+        };
+
+        let mut children = Vec::with_capacity(self.children().len());
+        for child in self.children() {
+            children.push(match child {
+                TableChild::Item(cell) => TableChild::Item(annotate(cell)?),
+                TableChild::Header(header) => {
+                    let cells = header.children().iter().map(&mut annotate);
+                    let cells = cells.collect::<SourceResult<_>>()?;
+                    TableChild::Header(header.clone().with_children(cells))
+                }
+                TableChild::Row(row) => {
+                    let cells = row.children().iter().map(&mut annotate);
+                    let cells = cells.collect::<SourceResult<_>>()?;
+                    TableChild::Row(row.clone().with_children(cells))
+                }
+                TableChild::Footer(footer) => {
+                    let cells = footer.children().iter().map(&mut annotate);
+                    let cells = cells.collect::<SourceResult<_>>()?;
+                    TableChild::Footer(footer.clone().with_children(cells))
+                }
+                TableChild::Column(_) | TableChild::Title(_) => child.clone(),
+            });
+        }
+
+        Ok((!notes.is_empty()).then_some((children, notes)))
+    }
+
+    /// Turns the table into two columns of label-value pairs, one for each of
+    /// its cells.
+    fn collapsed(&self, styles: StyleChain) -> Self {
+        let columns = match self.columns(styles) {
+            TableColumns::Tracks(tracks) if !tracks.0.is_empty() => tracks.0.len(),
+            _ => self.inferred_columns(styles),
+        }
+        .max(1);
+
+        // The labels come from the first row of the header, if any.
+        let mut labels: Vec<Content> =
+            (1..=columns).map(|n| TextElem::packed(eco_format!("{n}"))).collect();
+        let header = self.children().iter().find_map(|child| match child {
+            TableChild::Header(header) => Some(header.children()),
+            _ => None,
+        });
+        let mut x = 0;
+        for cell in header.into_iter().flatten() {
+            if x >= columns {
+                break;
+            }
+            labels[x] = cell.body().clone();
+            x += cell.colspan(styles).get();
+        }
+
+        let pair = |cell: &TableCell, x: &mut usize| {
+            let label = TableCell::new(labels[*x % columns].clone()).spanned(cell.span());
+            *x += cell.colspan(styles).get();
+            [
+                TableChild::Item(label),
+                TableChild::Item(cell.clone().with_colspan(NonZeroUsize::ONE)),
+            ]
+        };
+
+        let mut children = vec![];
+        let mut x = 0;
+        for child in self.children() {
+            match child {
+                TableChild::Item(cell) => children.extend(pair(cell, &mut x)),
+                TableChild::Row(row) => {
+                    // A row starts at the first column.
+                    if x % columns != 0 {
+                        x += columns - x % columns;
+                    }
+                    for cell in row.children() {
+                        children.extend(pair(cell, &mut x));
+                    }
+                }
+                TableChild::Title(_) => children.push(child.clone()),
+                _ => {}
+            }
+        }
+
+        self.clone()
+            .with_columns(TableColumns::Tracks(TrackSizings(smallvec![
+                Sizing::Auto,
+                Sizing::Fr(Fr::one()),
+            ])))
+            .with_rows(TrackSizings(smallvec![]))
+            .with_group_columns(None)
+            .with_collapse_below(None)
+            .with_children(children)
+    }
+
+    /// Checks that each row spans exactly the table's columns if all cells of
+    /// the table are in rows.
+    fn check_rows(&self, columns: usize, styles: StyleChain) -> SourceResult<()> {
+        if self
+            .children()
+            .iter()
+            .any(|child| matches!(child, TableChild::Item(_)))
+        {
+            return Ok(());
+        }
+
+        let rows = self.children().iter().filter_map(|child| match child {
+            TableChild::Row(row) => Some(row),
+            _ => None,
+        });
+        for (i, row) in rows.enumerate() {
+            let span: usize =
+                row.children().iter().map(|cell| cell.colspan(styles).get()).sum();
+            if span != columns {
+                bail!(
+                    row.span(),
+                    "row {i} spans {span} column(s), but the table has {columns}";
+                    hint: "add or remove cells or change their colspans"
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// As many equally wide columns of at least the given width as fit into
+    /// the width of the region, including the gutters between them.
+    fn fit_columns(
+        &self,
+        min: Abs,
+        gutter: &[Sizing],
+        regions: Regions,
+        styles: StyleChain,
+    ) -> SourceResult<Vec<Sizing>> {
+        if min <= Abs::zero() {
+            bail!(self.span(), "minimum column width must be positive");
+        }
+
+        // In a region of infinite width, each cell gets its own column: The
+        // loose cells share a single row, while headers, rows and footers
+        // keep their own.
+        let width = regions.size.x;
+        if !width.is_finite() {
+            let span = |cells: &[TableCell]| -> usize {
+                cells.iter().map(|cell| cell.colspan(styles).get()).sum()
+            };
+            let mut loose = 0;
+            let mut widest = 0;
+            for child in self.children() {
+                match child {
+                    TableChild::Item(cell) => loose += cell.colspan(styles).get(),
+                    TableChild::Header(header) => {
+                        widest = widest.max(span(header.children()))
+                    }
+                    TableChild::Row(row) => widest = widest.max(span(row.children())),
+                    TableChild::Footer(footer) => {
+                        widest = widest.max(span(footer.children()))
+                    }
+                    TableChild::Column(_) | TableChild::Title(_) => {}
+                }
+            }
+            let count = loose.max(widest).max(1);
+            return Ok(vec![Sizing::Rel(min.into()); count]);
+        }
+
+        // Only fixed gutters take space away from the columns.
+        let gap = match gutter.first() {
+            Some(Sizing::Rel(rel)) => rel.resolve(styles).relative_to(width),
+            _ => Abs::zero(),
+        };
+        let count = ((width + gap) / (min + gap)).floor().max(1.0) as usize;
+        Ok(vec![Sizing::Fr(Fr::one()); count])
+    }
 
-        layouter.layout(engine)
+    /// The number of columns spanned by the cells of the header or, without
+    /// one, of the first row.
+    fn inferred_columns(&self, styles: StyleChain) -> usize {
+        let header = self.children().iter().find_map(|child| match child {
+            TableChild::Header(header) => Some(header.children()),
+            _ => None,
+        });
+        let row = || {
+            self.children().iter().find_map(|child| match child {
+                TableChild::Row(row) => Some(row.children()),
+                _ => None,
+            })
+        };
+        header
+            .or_else(row)
+            .map_or(0, |cells| cells.iter().map(|cell| cell.colspan(styles).get()).sum())
     }
 }
 
 impl LocalName for TableElem {
-    fn local_name(lang: Lang, _: Option<Region>) -> &'static str {
-        match lang {
+    fn translate(lang: Lang, _: Option<Region>) -> Option<&'static str> {
+        Some(match lang {
             Lang::ALBANIAN => "Tabel",
             Lang::ARABIC => "جدول",
             Lang::BOKMÅL => "Tabell",
@@ -233,13 +1331,645 @@ impl LocalName for TableElem {
             Lang::UKRAINIAN => "Таблиця",
             Lang::VIETNAMESE => "Bảng",
             Lang::JAPANESE => "表",
-            Lang::ENGLISH | _ => "Table",
-        }
+            Lang::ENGLISH => "Table",
+            _ => return None,
+        })
     }
 }
 
 impl Figurable for TableElem {}
 
+/// The columns of a table.
+#[derive(Debug, Clone, PartialEq, Hash)]
+pub enum TableColumns {
+    /// The sizes of the columns.
+    Tracks(TrackSizings),
+    /// As many equally wide columns as fit, each at least this wide.
+    AutoFit(Length),
+}
+
+impl Default for TableColumns {
+    fn default() -> Self {
+        Self::Tracks(TrackSizings::default())
+    }
+}
+
+cast! {
+    TableColumns,
+    self => match self {
+        Self::Tracks(tracks) => tracks.into_value(),
+        Self::AutoFit(min) => dict! { "auto-fit" => min }.into_value(),
+    },
+    mut dict: Dict => match dict.take("auto-fit") {
+        Ok(min) => {
+            dict.finish(&["auto-fit"])?;
+            Self::AutoFit(min.cast()?)
+        }
+        Err(_) => Self::Tracks(Value::Dict(dict).cast()?),
+    },
+    v: TrackSizings => Self::Tracks(v),
+}
+
+/// Groups of columns separated by a wider gutter.
+#[derive(Debug, Copy, Clone, PartialEq, Hash)]
+pub struct ColumnGroups {
+    /// The number of columns in each group.
+    pub size: NonZeroUsize,
+    /// The gutter between two groups.
+    pub gutter: Sizing,
+}
+
+impl ColumnGroups {
+    /// The gutters between the given number of columns, with this group's
+    /// gutter at the boundaries between groups and the given gutters
+    /// elsewhere.
+    fn gutters(&self, gutter: &[Sizing], count: usize) -> Vec<Sizing> {
+        // Without a boundary, there is nothing to widen.
+        if count <= self.size.get() {
+            return gutter.to_vec();
+        }
+
+        (0..count - 1)
+            .map(|x| {
+                if (x + 1) % self.size.get() == 0 {
+                    self.gutter
+                } else if gutter.is_empty() {
+                    Sizing::Rel(Rel::zero())
+                } else {
+                    gutter[x % gutter.len()]
+                }
+            })
+            .collect()
+    }
+}
+
+cast! {
+    ColumnGroups,
+    self => dict! { "size" => self.size, "gutter" => self.gutter }.into_value(),
+    mut dict: Dict => {
+        let size = dict.take("size")?.cast()?;
+        let gutter = dict.take("gutter")?.cast()?;
+        dict.finish(&["size", "gutter"])?;
+        Self { size, gutter }
+    },
+}
+
+/// Any child of a table element.
+#[derive(Debug, Clone, PartialEq, Hash)]
+pub enum TableChild {
+    Header(TableHeader),
+    Row(TableRow),
+    Column(TableColumn),
+    Footer(TableFooter),
+    Title(TableTitle),
+    Item(TableCell),
+}
+
+cast! {
+    TableChild,
+    self => match self {
+        Self::Header(header) => header.into_value(),
+        Self::Row(row) => row.into_value(),
+        Self::Column(column) => column.into_value(),
+        Self::Footer(footer) => footer.into_value(),
+        Self::Title(title) => title.into_value(),
+        Self::Item(cell) => cell.into_value(),
+    },
+    v: Content => v.into(),
+}
+
+impl From<Content> for TableChild {
+    fn from(value: Content) -> Self {
+        if let Some(header) = value.to::<TableHeader>() {
+            return TableChild::Header(header.clone());
+        }
+        if let Some(row) = value.to::<TableRow>() {
+            return TableChild::Row(row.clone());
+        }
+        if let Some(column) = value.to::<TableColumn>() {
+            return TableChild::Column(column.clone());
+        }
+        if let Some(footer) = value.to::<TableFooter>() {
+            return TableChild::Footer(footer.clone());
+        }
+        if let Some(title) = value.to::<TableTitle>() {
+            return TableChild::Title(title.clone());
+        }
+        TableChild::Item(TableCell::from(value))
+    }
+}
+
+/// A repeatable table header.
+///
+/// The header occupies the first rows of the table and is repeated at the top
+/// of each page the table spans, unless `repeat` is set to `{false}`. It must
+/// be the first child of the table. See the [grid header]($grid.header) for
+/// more information.
+#[elem(name = "header", title = "Table Header")]
+pub struct TableHeader {
+    /// Whether this header should be repeated across pages.
+    #[default(true)]
+    pub repeat: bool,
+
+    /// Further rows of the table to repeat along with the header.
+    ///
+    /// Rows are given by their index, starting at zero with the table's first
+    /// row and counting the header's own rows as well. They don't need to
+    /// follow the header directly. Once a row has been laid out, it is
+    /// repeated below the header on each following page, in the rows'
+    /// original order. Lines between the repeated rows are drawn like between
+    /// any other adjacent rows.
+    ///
+    /// ```example
+    /// #set page(height: 6em)
+    /// #table(
+    ///   columns: 2,
+    ///   table.header(rows: (2,))[*City*][*Temp.*],
+    ///   table.cell(colspan: 2)[Measured at noon],
+    ///   [], [°C],
+    ///   [Berlin], [21],
+    ///   [Paris], [24],
+    ///   [Rome], [28],
+    /// )
+    /// ```
+    #[borrowed]
+    pub rows: Vec<usize>,
+
+    /// The cells within the header.
+    #[variadic]
+    pub children: Vec<TableCell>,
+}
+
+/// A row of table cells, optionally belonging to a group.
+///
+/// Rows structure a table explicitly instead of relying on the number of
+/// columns to break a flat list of cells into rows. If all cells of a table
+/// are in rows, each row must span exactly as many columns as the table has,
+/// counting the [colspan]($table.cell.colspan) of its cells. Otherwise, a row
+/// starts on a new line and takes up as many whole lines as its cells need.
+///
+/// Consecutive rows with the same `group` form a band, whose index is passed
+/// to the table's [`fill`]($table.fill) function as a named `band` argument.
+/// This is useful to stripe groups of rows, e.g. all rows of one customer. See
+/// the [grid row]($grid.row) for more information.
+///
+/// ```example
+/// #table(
+///   columns: 2,
+///   fill: (x, y, band: none) => if band != none and calc.odd(band) {
+///     luma(230)
+///   },
+///   table.row(group: "A")[Alice][Tea],
+///   table.row(group: "A")[Alice][Cake],
+///   table.row(group: "B")[Bob][Coffee],
+///   table.row(group: "C")[Carol][Tea],
+/// )
+/// ```
+#[elem(name = "row", title = "Table Row")]
+pub struct TableRow {
+    /// The key of the group this row belongs to. Can be any value, rows are
+    /// grouped by comparing their keys for equality. If `{none}`, the row
+    /// doesn't belong to a group.
+    pub group: Option<Value>,
+
+    /// Whether to keep the row on one page with the other kept rows of its
+    /// group.
+    ///
+    /// Consecutive kept rows with the same `group` are moved to the next
+    /// page as a whole if they don't fit on the current one. A kept row
+    /// without a group is only kept together with itself. If the rows don't
+    /// fit on a page of their own either, they are split across pages as
+    /// usual and a warning is emitted.
+    ///
+    /// ```example
+    /// #set page(height: 9em)
+    /// #table(
+    ///   columns: 2,
+    ///   [Bob], [Coffee],
+    ///   [Bob], [Cake],
+    ///   table.row(group: "A", keep: true)[Alice][Tea],
+    ///   table.row(group: "A", keep: true)[Alice][Scones],
+    /// )
+    /// ```
+    #[default(false)]
+    pub keep: bool,
+
+    /// A key identifying the row, which is passed to the functions of the
+    /// table's [`fill`]($table.fill), [`align`]($table.align),
+    /// [`stroke`]($table.stroke) and [`background`]($table.background) as a
+    /// named `row-key` argument.
+    ///
+    /// Unlike the row's index, the key stays with the row's data when rows
+    /// are left out or reordered, so the styling follows the data. Can be any
+    /// value. The functions only receive the argument for rows with a key,
+    /// so functions taking just the position keep working for tables without
+    /// keys.
+    ///
+    /// ```example
+    /// #let data = (
+    ///   (name: "Alice", late: false),
+    ///   (name: "Bob", late: true),
+    /// )
+    /// #table(
+    ///   fill: (x, y, row-key: none) => if row-key == "late" { red },
+    ///   ..data.map(entry => table.row(
+    ///     key: if entry.late { "late" },
+    ///     entry.name,
+    ///   )),
+    /// )
+    /// ```
+    pub key: Option<Value>,
+
+    /// The cells within the row.
+    #[variadic]
+    pub children: Vec<TableCell>,
+}
+
+/// Configures a column of the table.
+///
+/// A column can be declared anywhere among the table's children. It doesn't
+/// occupy a cell itself.
+///
+/// ```example
+/// #table(
+///   columns: 2,
+///   table.column(1, format: n => [#calc.round(n, digits: 2) €]),
+///   [*Item*], [*Price*],
+///   [Bread], [2.5],
+///   [Cheese], [4.333],
+///   [Wine], [_on request_],
+/// )
+/// ```
+#[elem(name = "column", title = "Table Column")]
+pub struct TableColumn {
+    /// The index of the column, starting at zero.
+    #[required]
+    pub x: usize,
+
+    /// How to format the numeric cells in this column.
+    ///
+    /// Applies to cells whose body is just an integer or a float, such as
+    /// `[12]` or `[4.5]`. Cells with any other body, including numbers with
+    /// explicit formatting like `[*12*]`, are left as is.
+    ///
+    /// - A function receives the number and must return content or a string
+    ///   to display instead.
+    /// - With `{"number"}`, the digits are grouped in threes and written with
+    ///   the separators of the [text language]($text.lang) and
+    ///   [region]($text.region), e.g. `1.234,56` in German and `1,234.56` in
+    ///   English.
+    ///
+    /// ```example
+    /// #set text(lang: "de")
+    /// #table(
+    ///   columns: 2,
+    ///   table.column(1, format: "number"),
+    ///   [Einwohner], [3645000],
+    ///   [Fläche], [891.12],
+    /// )
+    /// ```
+    pub format: Option<ColumnFormat>,
+
+    /// The separator between groups of three digits in numbers formatted
+    /// with `{"number"}`.
+    ///
+    /// When `{auto}`, it depends on the text language and region. Set it to
+    /// `{""}` to not group digits at all.
+    pub thousands_separator: Smart<EcoString>,
+
+    /// The decimal separator of numbers formatted with `{"number"}` and of
+    /// numbers aligned with `{"decimal"}`.
+    ///
+    /// When `{auto}`, it depends on the text language and region.
+    ///
+    /// ```example
+    /// #table(
+    ///   columns: 2,
+    ///   table.column(
+    ///     1,
+    ///     format: "number",
+    ///     thousands-separator: "'",
+    ///     decimal-separator: ",",
+    ///   ),
+    ///   [Budget], [1250000.5],
+    /// )
+    /// ```
+    pub decimal_separator: Smart<char>,
+
+    /// How to align the numeric cells in this column.
+    ///
+    /// With `{"decimal"}`, numbers are aligned at their
+    /// [decimal separator]($table.column.decimal-separator), which is a comma
+    /// or period depending on the [text language]($text.lang). The digits
+    /// before it are aligned to the right and the ones after it to the left.
+    /// Whole numbers end where the separator would be. Cells with anything
+    /// but a number, possibly with a sign and digit grouping, keep their
+    /// alignment. This also applies to numbers produced by the column's
+    /// [`format`]($table.column.format).
+    ///
+    /// ```example
+    /// #table(
+    ///   columns: 2,
+    ///   table.column(1, align: "decimal"),
+    ///   [*Item*], [*Price*],
+    ///   [Bread], [2.5],
+    ///   [Cheese], [14.333],
+    ///   [Wine], [120],
+    /// )
+    /// ```
+    pub align: Option<ColumnAlign>,
+
+    /// How the column deals with content that doesn't fit when the table
+    /// runs out of space. Only applies to automatically sized columns.
+    ///
+    /// - With `{auto}`, the column grows to fit its content and wraps only
+    ///   when the table runs out of space, sharing the remaining space fairly
+    ///   with the other automatically sized columns.
+    /// - With `{"wrap"}`, the column gives up its width first, wrapping its
+    ///   content before the other columns have to.
+    /// - With `{"expand"}`, the column keeps the width of its content and
+    ///   only the other columns wrap. The table may then exceed the available
+    ///   width.
+    ///
+    /// ```example
+    /// #set page(width: 180pt)
+    /// #table(
+    ///   columns: 2,
+    ///   table.column(1, mode: "wrap"),
+    ///   [Keeps its line if it can],
+    ///   [Gives up its width before the first column does],
+    /// )
+    /// ```
+    pub mode: Smart<ColumnMode>,
+
+    /// A key identifying the column, which is passed to the functions of the
+    /// table's per-cell properties as a named `column-key` argument. See the
+    /// row's [`key`]($table.row.key) for more details.
+    ///
+    /// ```example
+    /// #table(
+    ///   columns: 2,
+    ///   table.column(1, key: "price"),
+    ///   align: (x, y, column-key: none) => {
+    ///     if column-key == "price" { right } else { left }
+    ///   },
+    ///   [Bread], [2.50],
+    ///   [Cheese], [14.00],
+    /// )
+    /// ```
+    pub key: Option<Value>,
+}
+
+/// How to format the numeric cells of a table column.
+#[derive(Debug, Clone, PartialEq, Hash)]
+pub enum ColumnFormat {
+    /// Format numbers with the separators of the text language.
+    Number,
+    /// Format numbers with a function.
+    Func(Func),
+}
+
+cast! {
+    ColumnFormat,
+    self => match self {
+        Self::Number => "number".into_value(),
+        Self::Func(func) => func.into_value(),
+    },
+    "number" => Self::Number,
+    func: Func => Self::Func(func),
+}
+
+/// How to align the numeric cells of a table column.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Cast)]
+pub enum ColumnAlign {
+    /// Align numbers at their decimal separator.
+    Decimal,
+}
+
+/// How densely a table is laid out.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Cast)]
+pub enum TableDensity {
+    /// Little padding around the cells' content.
+    Compact,
+    /// The default padding.
+    Comfortable,
+    /// Generous padding around the cells' content.
+    Spacious,
+}
+
+impl Default for TableDensity {
+    fn default() -> Self {
+        Self::Comfortable
+    }
+}
+
+impl TableDensity {
+    /// The inset of the cells for this density.
+    pub fn inset(self) -> Rel<Length> {
+        let pt = match self {
+            Self::Compact => 2.5,
+            Self::Comfortable => 5.0,
+            Self::Spacious => 8.0,
+        };
+        Abs::pt(pt).into()
+    }
+}
+
+/// What to do with a table that is wider than the available space.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Cast)]
+pub enum TableOverflow {
+    /// Let the table extend past the available space.
+    Visible,
+    /// Let the table extend past the available space and emit a warning.
+    Warn,
+    /// Cut the table off at the edge of the available space.
+    Clip,
+    /// Scale the table down to the available width.
+    Shrink,
+}
+
+/// A table footer which can aggregate the numbers in the table.
+///
+/// The footer starts on a new row after the cells above it. Its own cells are
+/// placed first. Then, each free slot in the footer's first row is filled with
+/// the aggregate of its column, if any.
+///
+/// Only cells whose body is just an integer or a float, like `[12]` or
+/// `[4.5]`, are aggregated. Other cells, as well as cells in the header and
+/// cells spanning multiple columns, are skipped. A column without any numbers
+/// has no aggregate. The result is formatted by the column's
+/// [`format`]($table.column.format), if any.
+///
+/// ```example
+/// #table(
+///   columns: 3,
+///   [*Item*], [*Count*], [*Price*],
+///   [Bread], [2], [2.5],
+///   [Cheese], [1], [4.25],
+///   [Wine], [3], [_n/a_],
+///   table.footer(aggregate: (none, "sum", "average"))[*Total*],
+/// )
+/// ```
+#[elem(name = "footer", title = "Table Footer")]
+pub struct TableFooter {
+    /// How to aggregate the columns. Either a single aggregate for all
+    /// columns or an array with an aggregate or `{none}` for each column.
+    ///
+    /// The available aggregates are `{"sum"}`, `{"average"}`, `{"min"}` and
+    /// `{"max"}`. The result is an integer if all aggregated numbers are
+    /// integers, except for the average.
+    pub aggregate: Option<Aggregates>,
+
+    /// The cells within the footer.
+    #[variadic]
+    pub children: Vec<TableCell>,
+}
+
+/// The aggregates of the columns in a table footer.
+#[derive(Debug, Clone, PartialEq, Hash)]
+pub enum Aggregates {
+    /// The same aggregate for all columns.
+    All(Aggregate),
+    /// An aggregate for each column.
+    Columns(Vec<Option<Aggregate>>),
+}
+
+cast! {
+    Aggregates,
+    self => match self {
+        Self::All(aggregate) => aggregate.into_value(),
+        Self::Columns(aggregates) => aggregates.into_value(),
+    },
+    v: Aggregate => Self::All(v),
+    v: Vec<Option<Aggregate>> => Self::Columns(v),
+}
+
+/// A title shown in a row of the table itself.
+///
+/// The title spans all columns and occupies the first or last row of the
+/// table, depending on its `position`. It is not affected by the table's
+/// [`fill`]($table.fill) and the rows after a title at the top are filled as
+/// if the title wasn't there. A title at the top repeats along with the
+/// [header]($table.header) on every page the table spans, a title at the
+/// bottom is only shown once.
+///
+/// Unlike a table's [`caption`]($table.caption), a title is not numbered and
+/// doesn't make the table referenceable. If the table is put into a
+/// [figure]($figure) with a caption, the figure's caption takes precedence and
+/// the table's title is not shown.
+///
+/// ```example
+/// #table(
+///   columns: 3,
+///   fill: (_, y) => if calc.odd(y) { luma(230) },
+///   table.title[*Fruit prices*],
+///   [Apples], [Pears], [Plums],
+///   [1.20], [0.90], [2.10],
+/// )
+/// ```
+#[elem(name = "title", title = "Table Title")]
+pub struct TableTitle {
+    /// The title's position in the table. Either `{top}` or `{bottom}`.
+    #[default(VAlign::Top)]
+    #[parse({
+        let option: Option<Spanned<VAlign>> = args.named("position")?;
+        if let Some(Spanned { v: align, span }) = option {
+            if align == VAlign::Horizon {
+                bail!(span, "expected `top` or `bottom`");
+            }
+        }
+        option.map(|spanned| spanned.v)
+    })]
+    pub position: VAlign,
+
+    /// How to align the title's content.
+    #[default(Align::CENTER)]
+    pub align: Align,
+
+    /// The title shown instead when it repeats along with the header on a
+    /// following page.
+    ///
+    /// By default, the title's body is followed by "(continued)" in the
+    /// [text language]($text.lang), e.g. "(Fortsetzung)" in German. With
+    /// `{none}`, the title repeats unchanged.
+    ///
+    /// ```example
+    /// #set page(height: 120pt)
+    /// #table(
+    ///   columns: 2,
+    ///   table.title[*Fruit prices*],
+    ///   table.header[*Fruit*][*Price*],
+    ///   ..range(6).map(i => ([Fruit #i], [#i.00])).flatten(),
+    /// )
+    /// ```
+    #[default(Smart::Auto)]
+    pub continued: Smart<Option<Content>>,
+
+    /// The title's body.
+    #[required]
+    pub body: Content,
+}
+
+impl TableTitle {
+    /// The body of the title when it is repeated on a following page.
+    fn continued_body(&self, styles: StyleChain) -> Option<Content> {
+        match self.continued(styles) {
+            Smart::Custom(continued) => continued,
+            Smart::Auto => {
+                let mut body = self.body().clone();
+                let suffix = Continued::local_name_in(styles);
+
+                // Full-width parentheses don't need a space before them.
+                if !suffix.starts_with('（') {
+                    body += SpaceElem::new().pack();
+                }
+
+                Some(body + TextElem::packed(suffix))
+            }
+        }
+    }
+}
+
+/// Marks a table title repeated on a following page.
+struct Continued;
+
+impl LocalName for Continued {
+    fn translate(lang: Lang, _: Option<Region>) -> Option<&'static str> {
+        Some(match lang {
+            Lang::ALBANIAN => "(vazhdim)",
+            Lang::ARABIC => "(تابع)",
+            Lang::BOKMÅL => "(fortsatt)",
+            Lang::CHINESE => "（续）",
+            Lang::CZECH => "(pokračování)",
+            Lang::DANISH => "(fortsat)",
+            Lang::DUTCH => "(vervolg)",
+            Lang::ESTONIAN => "(järg)",
+            Lang::FILIPINO => "(karugtong)",
+            Lang::FINNISH => "(jatkoa)",
+            Lang::FRENCH => "(suite)",
+            Lang::GERMAN => "(Fortsetzung)",
+            Lang::GREEK => "(συνέχεια)",
+            Lang::HUNGARIAN => "(folytatás)",
+            Lang::ITALIAN => "(continua)",
+            Lang::NYNORSK => "(framhald)",
+            Lang::POLISH => "(ciąg dalszy)",
+            Lang::PORTUGUESE => "(continuação)",
+            Lang::ROMANIAN => "(continuare)",
+            Lang::RUSSIAN => "(продолжение)",
+            Lang::SERBIAN => "(наставак)",
+            Lang::SLOVENIAN => "(nadaljevanje)",
+            Lang::SPANISH => "(continuación)",
+            Lang::SWEDISH => "(fortsättning)",
+            Lang::TURKISH => "(devam)",
+            Lang::UKRAINIAN => "(продовження)",
+            Lang::VIETNAMESE => "(tiếp theo)",
+            Lang::JAPANESE => "（続き）",
+            Lang::ENGLISH => "(continued)",
+            _ => return None,
+        })
+    }
+}
+
 /// A cell in the table. Use this to either override table properties for a
 /// particular cell, or in show rules to apply certain styles to multiple cells
 /// at once.
@@ -258,7 +1988,29 @@ impl Figurable for TableElem {}
 ///   [M.], table.cell(inset: 0pt)[Player]
 /// )
 /// ```
-#[elem(name = "cell", title = "Table Cell", Show)]
+///
+/// Cells may also span multiple columns through their `colspan` field. The
+/// lines between the columns a cell spans are not drawn, so that it reads as
+/// one cell, while its outer border is drawn as usual. Each cell below a
+/// spanning cell draws its part of the spanning cell's bottom edge with its
+/// own top stroke override, if it has one.
+///
+/// # Labels
+/// A cell can be given a label through the `label` argument. Like any labelled
+/// element, it can then be linked to or queried, which is useful to refer to a
+/// specific value in the table from elsewhere. Labels must be unique among the
+/// cells of a table.
+///
+/// ```example
+/// #table(
+///   columns: 2,
+///   [Apples], table.cell(label: <apples>)[12],
+///   [Pears], [7],
+/// )
+///
+/// We sold #link(<apples>)[a dozen apples].
+/// ```
+#[elem(name = "cell", title = "Table Cell", Construct, Show)]
 pub struct TableCell {
     /// The cell's body.
     #[required]
@@ -270,8 +2022,313 @@ pub struct TableCell {
     /// The cell's alignment override.
     align: Smart<Align>,
 
+    /// The cell's writing direction override.
+    ///
+    /// This sets the [text direction]($text.dir) of the cell's body, while
+    /// the order of the table's columns stays as is. The `{start}` and `{end}`
+    /// alignments of the cell follow it: In a right-to-left cell, they mean
+    /// right and left. The override also takes precedence over the direction
+    /// of a table laid out right-to-left. When `{auto}`, the cell keeps the
+    /// direction of its surroundings.
+    ///
+    /// ```example
+    /// #table(
+    ///   columns: (auto, 80pt),
+    ///   align: start,
+    ///   [English], [Hello],
+    ///   [Arabic], table.cell(dir: rtl)[مرحبا],
+    /// )
+    /// ```
+    dir: Smart<TextDir>,
+
     /// The cell's inset override.
+    ///
+    /// If only some sides are given, the others are taken from the table's
+    /// [`inset`]($table.inset). Note that a later set rule for the cell's
+    /// inset replaces an earlier one as a whole instead of merging with it.
+    ///
+    /// The inset may be negative, in which case the cell's body bleeds past
+    /// the cell's edges. The cell's fill and stroke are not affected by this.
     inset: Smart<Sides<Option<Rel<Length>>>>,
+
+    /// The amount of columns spanned by this cell.
+    #[default(NonZeroUsize::ONE)]
+    colspan: NonZeroUsize,
+
+    /// The cell's layer.
+    ///
+    /// Within a row, cells on higher layers are drawn on top of cells on lower
+    /// layers, including their fills. Cells on the same layer are drawn in the
+    /// order they were specified in. This is useful when a cell's content
+    /// intentionally overlaps its neighbours, e.g. through a negative inset.
+    ///
+    /// ```example
+    /// #table(
+    ///   columns: 2,
+    ///   table.cell(inset: (right: -12pt), layer: 1)[Overlapping text],
+    ///   table.cell(fill: yellow)[],
+    /// )
+    /// ```
+    layer: i64,
+
+    /// Whether to scale the cell's body down when it is too wide for its
+    /// column(s), instead of wrapping it.
+    ///
+    /// The body is scaled down uniformly, to at most half its size. If it
+    /// still doesn't fit at that scale, it wraps as usual. For content with
+    /// multiple lines, the widest line determines the scale.
+    ///
+    /// ```example
+    /// #table(
+    ///   columns: (50pt, auto),
+    ///   table.cell(shrink-to-fit: true)[1,234,567.89], [Total],
+    /// )
+    /// ```
+    shrink_to_fit: bool,
+
+    /// The angle by which to rotate the cell's body.
+    ///
+    /// Unlike with the [`rotate`]($rotate) function, the rotated body takes
+    /// part in the layout of the table: The column is sized to the width of
+    /// the rotated body and the row grows to its height. This is useful for
+    /// narrow columns with long headers. A cell with a rotated body is not
+    /// broken across pages.
+    ///
+    /// ```example
+    /// #table(
+    ///   columns: 3,
+    ///   align: center + horizon,
+    ///   table.cell(rotate: -90deg)[*Participants*],
+    ///   table.cell(rotate: -90deg)[*Average age*],
+    ///   table.cell(rotate: -90deg)[*Share of women*],
+    ///   [120], [34], [52%],
+    /// )
+    /// ```
+    rotate: Angle,
+
+    /// The writing mode of the cell's body.
+    ///
+    /// In `{"vertical"}` mode, the body's text runs from top to bottom with
+    /// upright characters, like in vertical CJK text, while the rest of the
+    /// table stays horizontal. An `{auto}`-sized column is then as wide as the
+    /// widest character and the row grows with the length of the text. Spaces
+    /// between words are dropped and elements other than text are kept as
+    /// they are.
+    ///
+    /// ```example
+    /// #set text(font: "Noto Serif CJK SC")
+    /// #table(
+    ///   columns: 2,
+    ///   align: center,
+    ///   table.cell(writing: "vertical")[春眠不觉晓],
+    ///   [孟浩然],
+    /// )
+    /// ```
+    #[default(CellWriting::Horizontal)]
+    writing: CellWriting,
+
+    /// How to treat content which doesn't fit into the cell.
+    ///
+    /// This only takes effect for cells in rows of fixed height. Cells in
+    /// `{auto}` rows grow with their content and may break across pages
+    /// instead, so their content never overflows.
+    ///
+    /// - `{"visible"}` shows the content beyond the cell's bounds.
+    /// - `{"clip"}` cuts the content off at the cell's bounds.
+    /// - `{"ellipsis"}` additionally shows an ellipsis in the cell's bottom
+    ///   right corner.
+    /// - `{"fade"}` additionally fades the content out towards the cell's
+    ///   bottom edge, into the cell's fill or white if it has none.
+    ///
+    /// ```example
+    /// #table(
+    ///   columns: 3 * (60pt,),
+    ///   rows: 30pt,
+    ///   fill: luma(240),
+    ///   ..("clip", "ellipsis", "fade").map(mode => table.cell(
+    ///     overflow: mode,
+    ///     lorem(10),
+    ///   )),
+    /// )
+    /// ```
+    #[default(CellOverflow::Visible)]
+    overflow: CellOverflow,
+
+    /// The cell's stroke override for individual sides.
+    ///
+    /// Sides which aren't given keep the table's [`stroke`]($table.stroke),
+    /// while sides set to `{none}` have no line at all. When several strokes
+    /// claim an edge between two cells, the first of these wins:
+    ///
+    /// 1. The override of the cell above or to the left of the edge.
+    /// 2. The override of the cell below or to the right of the edge.
+    /// 3. The table's stroke for the cell above or to the left of the edge.
+    ///
+    /// This only applies to the default `{"grid"}`
+    /// [stroke mode]($table.stroke-mode).
+    ///
+    /// ```example
+    /// #table(
+    ///   columns: 3,
+    ///   stroke: none,
+    ///   ..([A], [B], [C]).map(table.cell.with(stroke: (bottom: 1pt))),
+    ///   [1], table.cell(stroke: (x: 0.5pt))[2], [3],
+    /// )
+    /// ```
+    stroke: Sides<Option<Option<Stroke>>>,
+
+    /// The width below which the cell's `{auto}`-sized columns don't shrink,
+    /// even if its content is narrower.
+    ///
+    /// This keeps columns consistent across tables, for example in forms, or
+    /// reserves space for content added later. If several cells in a column
+    /// have a minimum width, the largest one applies. A cell spanning multiple
+    /// columns widens them like wide content would. Columns keep their minimum
+    /// width even if the table then no longer fits into the available space.
+    ///
+    /// ```example
+    /// #table(
+    ///   columns: 2,
+    ///   table.cell(min-width: 3cm)[Name], [Signature],
+    ///   [], [],
+    /// )
+    /// ```
+    #[resolve]
+    min_width: Length,
+
+    /// Whether the cell's content may overflow into the empty cells following
+    /// it in its row instead of wrapping, like text in a spreadsheet.
+    ///
+    /// The content spills over the cells up to the first one which isn't
+    /// empty or the edge of the table. This never changes the widths of the
+    /// columns: they are sized as if the cell didn't spill. The cells spilled
+    /// into keep their own fill and stroke, including the line between them
+    /// and the spilling cell.
+    ///
+    /// ```example
+    /// #table(
+    ///   columns: (40pt,) * 3,
+    ///   table.cell(spill: true)[A long note], [], [C],
+    ///   table.cell(spill: true)[A long note], [B], [C],
+    /// )
+    /// ```
+    #[default(false)]
+    spill: bool,
+
+    /// Whether a table nested in the cell takes the [stroke]($table.stroke),
+    /// [inset]($table.inset) and [alignment]($table.align) of this table as
+    /// its defaults.
+    ///
+    /// Nested tables otherwise start from the defaults and set rules for
+    /// tables, regardless of how the outer table is configured. Arguments
+    /// passed to the nested table itself still take precedence.
+    ///
+    /// ```example
+    /// #table(
+    ///   columns: 2,
+    ///   stroke: blue,
+    ///   inset: 8pt,
+    ///   [Outer],
+    ///   table.cell(inherit: true, table(
+    ///     columns: 2,
+    ///     [Inner], [Cells],
+    ///   )),
+    /// )
+    /// ```
+    #[default(false)]
+    inherit: bool,
+
+    /// A note explaining the cell's content.
+    ///
+    /// The cell is marked with the note's number and the note is listed below
+    /// the table. See the table's [`note-numbering`]($table.note-numbering)
+    /// for how notes are numbered.
+    ///
+    /// ```example
+    /// #table(
+    ///   columns: 2,
+    ///   [Sample], [Result],
+    ///   [A], table.cell(note: [Measured twice.])[0.42],
+    ///   [B], [0.57],
+    /// )
+    /// ```
+    note: Option<Content>,
+
+    /// A function which renders the cell instead of the default.
+    ///
+    /// The function receives the cell's body, its inset as a dictionary of
+    /// relative lengths with the keys `left`, `top`, `right` and `bottom`, and
+    /// its alignment, which may be `{auto}`. It must return content.
+    ///
+    /// The content takes the place of the padded and aligned body: The column
+    /// grows to its width and the row to its height, like with any other body.
+    /// The cell's `rotate`, `shrink-to-fit` and `overflow` are then not
+    /// applied, as they are part of the default rendering. The cell's fill,
+    /// background and stroke are still drawn by the table.
+    ///
+    /// ```example
+    /// #let boxed(body, inset, align) = pad(..inset, box(
+    ///   stroke: (left: 2pt + red),
+    ///   inset: (left: 4pt),
+    ///   body,
+    /// ))
+    ///
+    /// #table(
+    ///   columns: 2,
+    ///   [Status], table.cell(render: boxed)[Failed],
+    /// )
+    /// ```
+    render: Option<Func>,
+
+    /// The column of the cell, starting at zero.
+    ///
+    /// This and the cell's `y` and `name` are only known once the cell is
+    /// placed into the table. They are thus available in show rules on table
+    /// cells, but not on a freshly created cell.
+    #[synthesized]
+    x: usize,
+
+    /// The row of the cell, starting at zero. A title above the table
+    /// doesn't count as a row.
+    #[synthesized]
+    y: usize,
+
+    /// The name of the cell in the A1 notation of spreadsheets, which is its
+    /// column as letters followed by its row starting at one. For example,
+    /// the cell in the second column of the third row is named `{"B3"}`.
+    ///
+    /// ```example
+    /// #show table.cell: it => [#text(gray, it.name) #it.body]
+    /// #table(
+    ///   columns: 3,
+    ///   [Name], [Qty], [Price],
+    ///   [Pens], [2], [1.50],
+    /// )
+    /// ```
+    #[synthesized]
+    name: EcoString,
+
+    /// The stroke of the table the cell is in.
+    #[internal]
+    #[ghost]
+    #[default(Celled::Value(Some(Stroke::default())))]
+    parent_stroke: Celled<Option<Stroke>>,
+
+    /// The inset of the table the cell is in.
+    #[internal]
+    #[ghost]
+    #[default(Sides::splat(Some(TableDensity::default().inset())))]
+    parent_inset: Sides<Option<Rel<Length>>>,
+
+    /// The alignment of the table the cell is in.
+    #[internal]
+    #[ghost]
+    parent_align: Celled<Smart<Align>>,
+
+    /// The body shown instead when the cell is repeated along with the
+    /// header on a following page. Used for the table's title.
+    #[internal]
+    continued: Option<Content>,
 }
 
 cast! {
@@ -285,16 +2342,82 @@ impl Default for TableCell {
     }
 }
 
+impl Construct for TableCell {
+    fn construct(_: &mut Engine, args: &mut Args) -> SourceResult<Content> {
+        let label: Option<Label> = args.named("label")?;
+
+        let mut cell = Self::new(args.expect("body")?);
+        if let Some(fill) = args.named("fill")? {
+            cell.push_fill(fill);
+        }
+        if let Some(align) = args.named("align")? {
+            cell.push_align(align);
+        }
+        if let Some(dir) = args.named("dir")? {
+            cell.push_dir(dir);
+        }
+        if let Some(inset) = args.named("inset")? {
+            cell.push_inset(inset);
+        }
+        if let Some(colspan) = args.named("colspan")? {
+            cell.push_colspan(colspan);
+        }
+        if let Some(layer) = args.named("layer")? {
+            cell.push_layer(layer);
+        }
+        if let Some(shrink_to_fit) = args.named("shrink-to-fit")? {
+            cell.push_shrink_to_fit(shrink_to_fit);
+        }
+        if let Some(rotate) = args.named("rotate")? {
+            cell.push_rotate(rotate);
+        }
+        if let Some(writing) = args.named("writing")? {
+            cell.push_writing(writing);
+        }
+        if let Some(overflow) = args.named("overflow")? {
+            cell.push_overflow(overflow);
+        }
+        if let Some(stroke) = args.named("stroke")? {
+            cell.push_stroke(stroke);
+        }
+        if let Some(min_width) = args.named("min-width")? {
+            cell.push_min_width(min_width);
+        }
+        if let Some(spill) = args.named("spill")? {
+            cell.push_spill(spill);
+        }
+        if let Some(inherit) = args.named("inherit")? {
+            cell.push_inherit(inherit);
+        }
+        if let Some(note) = args.named("note")? {
+            cell.push_note(note);
+        }
+        if let Some(render) = args.named("render")? {
+            cell.push_render(render);
+        }
+
+        let content = cell.spanned(args.span).pack();
+        Ok(match label {
+            Some(label) => content.labelled(label),
+            None => content,
+        })
+    }
+}
+
 impl ResolvableCell for TableCell {
     fn resolve_cell(
         mut self,
-        _: usize,
-        _: usize,
+        x: usize,
+        y: usize,
         fill: &Option<Paint>,
         align: Smart<Align>,
         inset: Sides<Rel<Length>>,
         styles: StyleChain,
     ) -> Cell {
+        self.push_x(x);
+        self.push_y(y);
+        self.push_name(cell_name(x, y));
+
         let fill = self.fill(styles).unwrap_or_else(|| fill.clone());
         self.push_fill(Smart::Custom(fill.clone()));
         self.push_align(match align {
@@ -306,20 +2429,166 @@ impl ResolvableCell for TableCell {
             // the outer alignment when it is effectively displayed).
             Smart::Auto => self.align(styles),
         });
+        if let Smart::Custom(valign) = TableElem::valign_in(styles) {
+            self.push_align(Smart::Custom(match self.align(styles) {
+                Smart::Custom(Align::H(x)) => x + valign,
+                Smart::Custom(align) => align,
+                Smart::Auto => valign.into(),
+            }));
+        }
         self.push_inset(Smart::Custom(
             self.inset(styles).map_or(inset, |inner| inner.fold(inset)).map(Some),
         ));
 
-        Cell { body: self.pack(), fill }
+        let colspan = self.colspan(styles);
+        self.push_colspan(colspan);
+        let layer = self.layer(styles);
+        let rotated = !self.rotate(styles).is_zero();
+        let resolve = |stroke: Stroke| stroke.resolve(styles).unwrap_or_default();
+        let stroke_overrides =
+            self.stroke(styles).map(|side| side.map(|stroke| stroke.map(resolve)));
+        let min_width = self.min_width(styles);
+        let spill = self.spill(styles);
+        let empty = self.body().is_empty();
+        let continued = self.continued(styles).map(|body| {
+            let mut cell = self.clone();
+            cell.push_body(body);
+            cell.pack()
+        });
+
+        let mut cell = Cell {
+            body: self.pack(),
+            fill,
+            colspan,
+            layer,
+            stroke: None,
+            stroke_overrides,
+            background: None,
+            rotated,
+            min_width,
+            spill,
+            empty,
+            continued: None,
+        };
+        cell.continued = continued.map(|body| Box::new(Cell { body, ..cell.clone() }));
+        cell
+    }
+
+    fn colspan(&self, styles: StyleChain) -> NonZeroUsize {
+        self.colspan(styles)
+    }
+
+    fn body(&self) -> &Content {
+        self.body()
+    }
+
+    fn set_body(&mut self, body: Content) {
+        self.push_body(body);
     }
 }
 
 impl Show for TableCell {
-    fn show(&self, _engine: &mut Engine, styles: StyleChain) -> SourceResult<Content> {
-        show_grid_cell(self.body().clone(), self.inset(styles), self.align(styles))
+    fn show(&self, engine: &mut Engine, styles: StyleChain) -> SourceResult<Content> {
+        let mut body = self.body().clone();
+        if self.inherit(styles) {
+            let mut parent = Styles::new();
+            parent.set(TableElem::set_stroke(self.parent_stroke(styles)));
+            parent.set(TableElem::set_inset(self.parent_inset(styles)));
+            parent.set(TableElem::set_align(self.parent_align(styles)));
+            body = body.styled_with_map(parent);
+        }
+
+        if self.writing(styles) == CellWriting::Vertical {
+            body = write_vertically(&body);
+        }
+
+        let shown = if let Some(render) = self.render(styles) {
+            let inset =
+                self.inset(styles).unwrap_or_default().map(Option::unwrap_or_default);
+            let inset = dict! {
+                "left" => inset.left,
+                "top" => inset.top,
+                "right" => inset.right,
+                "bottom" => inset.bottom,
+            };
+            let args =
+                [body.into_value(), inset.into_value(), self.align(styles).into_value()];
+            render.call(engine, args)?.cast().at(render.span())?
+        } else {
+            show_grid_cell(
+                body,
+                self.inset(styles),
+                self.align(styles),
+                CellDisplay {
+                    rotate: self.rotate(styles),
+                    shrink: self.shrink_to_fit(styles),
+                    overflow: self.overflow(styles),
+                    fill: self.fill(styles).unwrap_or(None),
+                    baseline_grid: TableElem::baseline_grid_in(styles),
+                },
+            )?
+        };
+
+        // The direction applies around the alignment, so that the cell's
+        // `start` and `end` alignment follow it.
+        Ok(match self.dir(styles) {
+            Smart::Custom(dir) => shown.styled(TextElem::set_dir(dir)),
+            Smart::Auto => shown,
+        })
     }
 }
 
+/// The name of the cell at the given position in A1 notation.
+fn cell_name(x: usize, y: usize) -> EcoString {
+    let mut column = vec![];
+    let mut n = x + 1;
+    while n > 0 {
+        n -= 1;
+        column.push(char::from(b'A' + (n % 26) as u8));
+        n /= 26;
+    }
+    let column: EcoString = column.into_iter().rev().collect();
+    eco_format!("{column}{}", y + 1)
+}
+
+/// The writing mode of a table cell's body.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Cast)]
+pub enum CellWriting {
+    /// Lines run from left to right or right to left.
+    Horizontal,
+    /// Characters run from top to bottom.
+    Vertical,
+}
+
+/// Breaks the lines of the text in the content after each character, so that
+/// it runs from top to bottom. Spaces are dropped.
+fn write_vertically(content: &Content) -> Content {
+    if let Some(elem) = content.to::<TextElem>() {
+        let mut text = EcoString::new();
+        for grapheme in elem.text().graphemes(true) {
+            if !grapheme.chars().all(char::is_whitespace) {
+                text.push_str(grapheme);
+                text.push('\n');
+            }
+        }
+        return elem.clone().with_text(text).pack();
+    }
+
+    if let Some(children) = content.to_sequence() {
+        return Content::sequence(
+            children
+                .filter(|child| !child.is::<SpaceElem>())
+                .map(|child| write_vertically(child)),
+        );
+    }
+
+    if let Some((body, styles)) = content.to_styled() {
+        return write_vertically(body).styled_with_map(styles.clone());
+    }
+
+    content.clone()
+}
+
 impl From<Content> for TableCell {
     fn from(value: Content) -> Self {
         value
@@ -5,6 +5,7 @@ use ecow::EcoString;
 use crate::foundations::{cast, StyleChain};
 use crate::layout::Dir;
 use crate::text::TextElem;
+use crate::util::option_eq;
 
 /// An identifier for a natural language.
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
@@ -158,8 +159,15 @@ cast! {
 
 /// The name with which an element is referenced.
 pub trait LocalName {
-    /// Get the name in the given language and (optionally) region.
-    fn local_name(lang: Lang, region: Option<Region>) -> &'static str;
+    /// Get the name specifically for the given language and (optionally)
+    /// region, if there is a translation for it.
+    fn translate(lang: Lang, region: Option<Region>) -> Option<&'static str>;
+
+    /// Get the name in the given language and (optionally) region, falling
+    /// back to the language in general and finally to English.
+    fn local_name(lang: Lang, region: Option<Region>) -> &'static str {
+        resolve_local_name(lang, region, Self::translate)
+    }
 
     /// Gets the local name from the style chain.
     fn local_name_in(styles: StyleChain) -> &'static str
@@ -170,10 +178,51 @@ pub trait LocalName {
     }
 }
 
+/// Walk from the most specific language and region combination down to just
+/// the language and finally to English until there is a translation.
+fn resolve_local_name(
+    lang: Lang,
+    region: Option<Region>,
+    translate: fn(Lang, Option<Region>) -> Option<&'static str>,
+) -> &'static str {
+    region
+        .and_then(|region| translate(lang, Some(region)))
+        .or_else(|| translate(lang, None))
+        .or_else(|| translate(Lang::ENGLISH, None))
+        .unwrap_or_default()
+}
+
+/// The digit grouping and decimal separator used for numbers in the given
+/// language and (optionally) region.
+pub fn number_separators(lang: Lang, region: Option<Region>) -> (char, char) {
+    match lang {
+        Lang::GERMAN if option_eq(region, "CH") || option_eq(region, "LI") => {
+            ('\u{2019}', '.')
+        }
+        Lang::ARABIC
+        | Lang::CHINESE
+        | Lang::ENGLISH
+        | Lang::FILIPINO
+        | Lang::JAPANESE => (',', '.'),
+        Lang::DANISH
+        | Lang::DUTCH
+        | Lang::GERMAN
+        | Lang::GREEK
+        | Lang::ITALIAN
+        | Lang::PORTUGUESE
+        | Lang::ROMANIAN
+        | Lang::SERBIAN
+        | Lang::SLOVENIAN
+        | Lang::SPANISH
+        | Lang::TURKISH
+        | Lang::VIETNAMESE => ('.', ','),
+        _ => ('\u{202F}', ','),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::util::option_eq;
 
     #[test]
     fn test_region_option_eq() {
@@ -181,4 +230,35 @@ mod tests {
         assert!(option_eq(region, "US"));
         assert!(!option_eq(region, "AB"));
     }
+
+    #[test]
+    fn test_resolve_local_name() {
+        fn translate(lang: Lang, region: Option<Region>) -> Option<&'static str> {
+            Some(match lang {
+                Lang::PORTUGUESE if option_eq(region, "PT") => "Secção",
+                Lang::PORTUGUESE if region.is_none() => "Seção",
+                Lang::ENGLISH => "Section",
+                _ => return None,
+            })
+        }
+
+        let resolve = |lang, region: Option<&str>| {
+            let region = region.map(|region| region.parse().unwrap());
+            resolve_local_name(lang, region, translate)
+        };
+
+        assert_eq!(resolve(Lang::PORTUGUESE, Some("PT")), "Secção");
+        assert_eq!(resolve(Lang::PORTUGUESE, Some("BR")), "Seção");
+        assert_eq!(resolve(Lang::PORTUGUESE, None), "Seção");
+        assert_eq!(resolve(Lang::GERMAN, Some("AT")), "Section");
+    }
+
+    #[test]
+    fn test_number_separators() {
+        let region = |code: &str| Some(code.parse().unwrap());
+        assert_eq!(number_separators(Lang::ENGLISH, None), (',', '.'));
+        assert_eq!(number_separators(Lang::GERMAN, None), ('.', ','));
+        assert_eq!(number_separators(Lang::GERMAN, region("CH")), ('\u{2019}', '.'));
+        assert_eq!(number_separators(Lang::FRENCH, region("FR")), ('\u{202F}', ','));
+    }
 }
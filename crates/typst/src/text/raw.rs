@@ -429,8 +429,8 @@ impl Finalize for RawElem {
 }
 
 impl LocalName for RawElem {
-    fn local_name(lang: Lang, region: Option<Region>) -> &'static str {
-        match lang {
+    fn translate(lang: Lang, region: Option<Region>) -> Option<&'static str> {
+        Some(match lang {
             Lang::ALBANIAN => "List",
             Lang::ARABIC => "قائمة",
             Lang::BOKMÅL => "Utskrift",
@@ -458,8 +458,9 @@ impl LocalName for RawElem {
             Lang::UKRAINIAN => "Лістинг",
             Lang::VIETNAMESE => "Chương trình", // TODO: This may be wrong.
             Lang::JAPANESE => "リスト",
-            Lang::ENGLISH | _ => "Listing",
-        }
+            Lang::ENGLISH => "Listing",
+            _ => return None,
+        })
     }
 }
 
@@ -239,7 +239,8 @@ impl Layout for ImageElem {
             frame.clip(Path::rect(frame.size()));
         }
 
-        // Apply metadata.
+        // Apply metadata to the full target size, so that a link covers the
+        // whole box of the image, regardless of its transparency and fit.
         frame.meta(styles, false);
 
         Ok(Fragment::frame(frame))
@@ -247,8 +248,8 @@ impl Layout for ImageElem {
 }
 
 impl LocalName for ImageElem {
-    fn local_name(lang: Lang, region: Option<Region>) -> &'static str {
-        match lang {
+    fn translate(lang: Lang, region: Option<Region>) -> Option<&'static str> {
+        Some(match lang {
             Lang::ALBANIAN => "Figurë",
             Lang::ARABIC => "شكل",
             Lang::BOKMÅL => "Figur",
@@ -278,8 +279,9 @@ impl LocalName for ImageElem {
             Lang::UKRAINIAN => "Рисунок",
             Lang::VIETNAMESE => "Hình",
             Lang::JAPANESE => "図",
-            Lang::ENGLISH | _ => "Figure",
-        }
+            Lang::ENGLISH => "Figure",
+            _ => return None,
+        })
     }
 }
 
@@ -12,6 +12,13 @@ use unscanny::Scanner;
 const TEXT: &str = include_str!("../typ/compiler/bench.typ");
 const FONT: &[u8] = include_bytes!("../../assets/fonts/LinLibertine_R.ttf");
 
+// The same table with 10,000 cells, once with only fixed columns, whose cells
+// aren't measured, and once with a single auto column, which makes the
+// layouter visit all of them.
+const TABLE_FIXED: &str = "#table(columns: (40pt,) * 10, ..range(10000).map(str))";
+const TABLE_AUTO: &str =
+    "#table(columns: (40pt,) * 9 + (auto,), ..range(10000).map(str))";
+
 main!(
     bench_decode,
     bench_scan,
@@ -20,6 +27,8 @@ main!(
     bench_eval,
     bench_compile,
     bench_render,
+    bench_table_fixed,
+    bench_table_auto,
 );
 
 fn bench_decode(iai: &mut Iai) {
@@ -78,6 +87,18 @@ fn bench_render(iai: &mut Iai) {
     iai.run(|| typst_render::render(&document.pages[0], 1.0, Color::WHITE))
 }
 
+fn bench_table_fixed(iai: &mut Iai) {
+    let world = BenchWorld::with_source(TABLE_FIXED);
+    let mut tracer = Tracer::new();
+    iai.run(|| typst::compile(&world, &mut tracer));
+}
+
+fn bench_table_auto(iai: &mut Iai) {
+    let world = BenchWorld::with_source(TABLE_AUTO);
+    let mut tracer = Tracer::new();
+    iai.run(|| typst::compile(&world, &mut tracer));
+}
+
 struct BenchWorld {
     library: Prehashed<Library>,
     book: Prehashed<FontBook>,
@@ -87,6 +108,10 @@ struct BenchWorld {
 
 impl BenchWorld {
     fn new() -> Self {
+        Self::with_source(TEXT)
+    }
+
+    fn with_source(text: &str) -> Self {
         let font = Font::new(FONT.into(), 0).unwrap();
         let book = FontBook::from_fonts([&font]);
 
@@ -94,7 +119,7 @@ impl BenchWorld {
             library: Prehashed::new(Library::default()),
             book: Prehashed::new(book),
             font,
-            source: Source::detached(TEXT),
+            source: Source::detached(text),
         }
     }
 
@@ -15,13 +15,14 @@ use ecow::EcoString;
 use oxipng::{InFile, Options, OutFile};
 use rayon::iter::{ParallelBridge, ParallelIterator};
 use tiny_skia as sk;
-use typst::diag::{bail, FileError, FileResult, Severity, StrResult};
+use typst::diag::{bail, FileError, FileResult, Severity, SourceResult, StrResult};
+use typst::engine::Engine;
 use typst::eval::Tracer;
 use typst::foundations::{
-    eco_format, func, Bytes, Datetime, NoneValue, Repr, Smart, Value,
+    eco_format, func, Array, Bytes, Datetime, IntoValue, NoneValue, Repr, Smart, Value,
 };
 use typst::introspection::Meta;
-use typst::layout::{Abs, Frame, FrameItem, Margin, PageElem, Transform};
+use typst::layout::{Abs, Align, Celled, Frame, FrameItem, Margin, PageElem, Transform};
 use typst::model::Document;
 use typst::syntax::{FileId, PackageVersion, Source, SyntaxNode, VirtualPath};
 use typst::text::{Font, FontBook, TextElem, TextSize};
@@ -189,6 +190,24 @@ fn library() -> Library {
         NoneValue
     }
 
+    /// Resolves a per-cell alignment for each cell of a grid with the given
+    /// size, to test `Celled` outside of grids and tables.
+    #[func]
+    fn celled_align(
+        engine: &mut Engine,
+        align: Celled<Smart<Align>>,
+        columns: usize,
+        rows: usize,
+    ) -> SourceResult<Array> {
+        let mut resolved = Array::new();
+        for y in 0..rows {
+            for x in 0..columns {
+                resolved.push(align.resolve(engine, x, y)?.into_value());
+            }
+        }
+        Ok(resolved)
+    }
+
     // Set page width to 120pt with 10pt margins, so that the inner page is
     // exactly 100pt wide. Page height is unbounded and font size is 10pt so
     // that it multiplies to nice round numbers.
@@ -205,6 +224,7 @@ fn library() -> Library {
     lib.global.scope_mut().define_func::<test>();
     lib.global.scope_mut().define_func::<test_repr>();
     lib.global.scope_mut().define_func::<print>();
+    lib.global.scope_mut().define_func::<celled_align>();
     lib.global
         .scope_mut()
         .define("conifer", Color::from_u8(0x9f, 0xEB, 0x52, 0xFF));
@@ -963,7 +983,7 @@ fn render_links(canvas: &mut sk::Pixmap, ts: sk::Transform, frame: &Frame) {
                 let ts = ts.pre_concat(to_sk_transform(&group.transform));
                 render_links(canvas, ts, &group.frame);
             }
-            FrameItem::Meta(Meta::Link(_), size) => {
+            FrameItem::Meta(Meta::Link(..), size) => {
                 let w = size.x.to_pt() as f32;
                 let h = size.y.to_pt() as f32;
                 let rect = sk::Rect::from_xywh(0.0, 0.0, w, h).unwrap();
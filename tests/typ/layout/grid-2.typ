@@ -4,7 +4,7 @@
 #set page(width: 11cm, height: 2.5cm)
 #grid(
   columns: 5,
-  column-gutter: (2fr, 1fr, 1fr),
+  column-gutter: (2fr, 1fr, 1fr, 1fr),
   row-gutter: 6pt,
   [*Quarter*],
   [Expenditure],
@@ -22,7 +22,7 @@
 #grid(
   columns: 4 * (1fr,),
   row-gutter: 10pt,
-  column-gutter: (0pt, 10%),
+  column-gutter: (0pt, 10%, 10%),
   align(top, image("/files/rhino.png")),
   align(top, rect(inset: 0pt, fill: eastern, align(right)[LoL])),
   [rofl],
@@ -52,7 +52,7 @@
 #grid(
   columns: 4 * (1fr,),
   row-gutter: 10pt,
-  column-gutter: (0pt, 10%),
+  column-gutter: (0pt, 10%, 10%),
   [A], [B], [C], [D],
   grid(columns: 2, [A], [B], [C\ ]*3, [D]),
   align(top, rect(inset: 0pt, fill: eastern, align(right)[LoL])),
@@ -0,0 +1,30 @@
+// Test balancing the widths of auto columns.
+
+---
+// Before and after balancing.
+// Ref: false
+#set page(width: 200pt)
+#let prose(balance) = table(
+  columns: 3,
+  balance: balance,
+  [Short], [Also short], lorem(20),
+)
+
+#prose(false)
+#prose(true)
+
+// The prose column gets narrower when balanced, so the table gets taller.
+#style(styles => test(
+  measure(prose(true), styles).height > measure(prose(false), styles).height,
+  true,
+))
+
+---
+// Fixed and fractional columns are excluded from balancing.
+// Ref: false
+#set page(width: 200pt)
+#grid(
+  columns: (30pt, auto, auto, 1fr),
+  balance: true,
+  [Fixed], [Short], lorem(10), [Fr],
+)
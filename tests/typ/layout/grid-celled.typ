@@ -0,0 +1,24 @@
+// Test resolving per-cell values outside of grids and tables.
+
+---
+// A single value applies to every cell.
+// Ref: false
+#test(celled-align(left, 2, 2), (left, left, left, left))
+
+---
+// An array repeats per column.
+// Ref: false
+#test(celled-align((left, right), 3, 2), (left, right, left, left, right, left))
+#test(celled-align((), 2, 1), (auto, auto))
+
+---
+// A function receives the column and row.
+// Ref: false
+#test(
+  celled-align((x, y) => if x == y { center } else { auto }, 2, 2),
+  (center, auto, auto, center),
+)
+
+---
+// Error: 15-28 expected alignment or auto, found length
+#celled-align((x, y) => 1pt, 1, 1)
@@ -0,0 +1,49 @@
+// Test columns fitting a share of their contents.
+
+---
+// The column is scaled from its natural width.
+// Ref: false
+#style(styles => {
+  let full = measure(grid(columns: auto, box(width: 40pt)), styles)
+  let share = measure(grid(columns: (content: 50%), box(width: 40pt)), styles)
+  test(full.width, 40pt)
+  test(share.width, 20pt)
+})
+
+---
+// The contents rewrap to the reduced width.
+// Ref: false
+#style(styles => {
+  let text = [Alpha beta gamma delta]
+  let full = measure(grid(columns: auto, text), styles)
+  let share = measure(grid(columns: (content: 60%), text), styles)
+  test(share.height > full.height, true)
+})
+
+---
+// The minimum widths of the cells are kept.
+// Ref: false
+#style(styles => {
+  let t = table(
+    columns: ((content: 25%), auto),
+    inset: 0pt,
+    stroke: none,
+    table.cell(min-width: 30pt, box(width: 80pt)),
+    [B],
+  )
+  test(measure(t, styles).width > 30pt, true)
+  test(measure(t, styles).width < 80pt, true)
+})
+
+---
+// The share round-trips through the columns field.
+// Ref: false
+#test(grid(columns: (1fr, (content: 80%))).columns, (1fr, (content: 80%)))
+
+---
+// Error: 16-32 content share must not be negative
+#grid(columns: (content: -10%))[A]
+
+---
+// Error: 16-43 unexpected key "width", valid keys are "content"
+#grid(columns: (content: 80%, width: 1pt))[A]
@@ -0,0 +1,25 @@
+// Test gutters that differ between tracks.
+
+---
+// Each gap has its own size.
+// Ref: false
+#grid(
+  columns: 4 * (1cm,),
+  column-gutter: (2pt, 12pt, 2pt),
+  row-gutter: (4pt, 0pt),
+  ..range(12).map(i => rect(width: 100%, fill: luma(200))[#i])
+)
+
+---
+// Gutters are repeated cyclically when there are more gaps than sizes.
+// Ref: false
+#style(styles => {
+  let size = measure(grid(
+    columns: 5 * (10pt,),
+    rows: 4 * (10pt,),
+    column-gutter: (2pt, 10pt),
+    row-gutter: (6pt, 1pt),
+  ), styles)
+  test(size.width, 50pt + 2 * 2pt + 2 * 10pt)
+  test(size.height, 40pt + 2 * 6pt + 1pt)
+})
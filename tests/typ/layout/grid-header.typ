@@ -0,0 +1,77 @@
+// Test grid and table headers and cells spanning multiple columns.
+
+---
+// A two-level header which is repeated when the table breaks across pages.
+// Ref: false
+#set page(height: 100pt)
+#table(
+  columns: 5,
+  align: center,
+  table.header(
+    [],
+    table.cell(colspan: 2)[*2022*],
+    table.cell(colspan: 2)[*2023*],
+    [*Region*], [*H1*], [*H2*], [*H1*], [*H2*],
+  ),
+  ..range(12).map(n => ([Region #n], [1], [2], [3], [4])).flatten()
+)
+
+---
+// Cells spanning multiple columns are still shown in row-major order.
+// Ref: false
+#grid(
+  columns: 3,
+  gutter: 3pt,
+  grid.header(
+    grid.cell(colspan: 3)[#metadata(0) <cell>],
+  ),
+  grid.cell(colspan: 2)[#metadata(1) <cell>], [#metadata(2) <cell>],
+  [#metadata(3) <cell>], grid.cell(colspan: 2)[#metadata(4) <cell>],
+)
+#locate(loc => test(query(<cell>, loc).map(it => it.value), range(5)))
+
+---
+// A cell which doesn't fit into the current row moves to the next one.
+// Ref: false
+#show grid.cell: it => [#metadata((it.colspan, it.body)) <cell>]
+#grid(
+  columns: 2,
+  [a], grid.cell(colspan: 2)[b], [c],
+)
+#locate(loc => test(
+  query(<cell>, loc).map(it => it.value),
+  ((1, [a]), (1, []), (2, [b]), (1, [c]), (1, [])),
+))
+
+---
+// Non-repeated headers are allowed.
+// Ref: false
+#set page(height: 60pt)
+#table(
+  columns: 2,
+  table.header(repeat: false, [*A*], [*B*]),
+  ..range(10).map(str)
+)
+
+---
+#grid(
+  columns: 2,
+  // Error: 3-27 cell's colspan would cause it to exceed the available column(s)
+  // Hint: 3-27 try reducing the cell's colspan or adding more columns
+  grid.cell(colspan: 3)[a],
+)
+
+---
+#table(
+  [a],
+  // Error: 3-20 header must start at the first row
+  // Hint: 3-20 try moving the header to the beginning of the grid
+  table.header([b]),
+)
+
+---
+#grid(
+  grid.header([a]),
+  // Error: 3-19 cannot have more than one header
+  grid.header([b]),
+)
@@ -0,0 +1,44 @@
+// Test cell insets.
+
+---
+// Content bleeds past the cell's edges, but the tracks are sized as usual.
+// Ref: false
+#table(
+  columns: (auto, 1fr),
+  fill: luma(230),
+  table.cell(inset: (x: -3pt, top: -4pt))[Tab], [B],
+  [C], [D],
+)
+
+---
+// Insets larger than the content don't produce negative track sizes.
+// Ref: false
+#let tiny = table(
+  columns: 1,
+  stroke: none,
+  table.cell(inset: -20pt)[x],
+)
+#style(styles => {
+  let size = measure(tiny, styles)
+  test(size.width >= 0pt and size.height >= 0pt, true)
+})
+
+---
+// Partial insets only override the given sides.
+// Ref: false
+#let inset-of(..args) = {
+  show table.cell: it => [#metadata(it.inset) <inset>]
+  table(..args)
+}
+#set table(inset: (x: 8pt))
+#inset-of([A], table.cell(inset: (top: 1pt))[B])
+#set table(inset: (top: 0pt))
+#inset-of([C])
+#locate(loc => test(
+  query(<inset>, loc).map(it => it.value),
+  (
+    (left: 8pt, top: 5pt, right: 8pt, bottom: 5pt),
+    (left: 8pt, top: 1pt, right: 8pt, bottom: 5pt),
+    (left: 8pt, top: 0pt, right: 8pt, bottom: 5pt),
+  ),
+))
@@ -0,0 +1,21 @@
+// Test cell layers.
+
+---
+// Overlapping content is covered by the fill of a cell on a higher layer.
+#table(
+  columns: 3,
+  table.cell(inset: (right: -20pt))[Overlapping content],
+  table.cell(fill: yellow, layer: 1)[Cover],
+  table.cell(fill: aqua, layer: -1)[Below],
+)
+
+---
+// Layers don't affect the order in which cells are laid out.
+// Ref: false
+#grid(
+  columns: 3,
+  grid.cell(layer: 2)[#metadata(0) <cell>],
+  grid.cell(layer: -1)[#metadata(1) <cell>],
+  [#metadata(2) <cell>],
+)
+#locate(loc => test(query(<cell>, loc).map(it => it.value), range(3)))
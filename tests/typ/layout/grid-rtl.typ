@@ -7,3 +7,26 @@
 ---
 #set text(dir: rtl)
 #table(columns: 2)[A][B][C][D]
+
+---
+// Test that cells are laid out in logical row-major order, even in RTL grids.
+// Ref: false
+#set text(dir: rtl)
+#table(
+  columns: 3,
+  ..range(6).map(i => [#metadata(i) <cell>])
+)
+#locate(loc => test(query(<cell>, loc).map(it => it.value), range(6)))
+
+---
+// Cells spanning columns keep their logical position in RTL grids.
+// Ref: false
+#set text(dir: rtl)
+#table(
+  columns: 3,
+  [#metadata(0) <cell>],
+  table.cell(colspan: 2)[#metadata(1) <cell>],
+  table.cell(colspan: 2)[#metadata(2) <cell>],
+  [#metadata(3) <cell>],
+)
+#locate(loc => test(query(<cell>, loc).map(it => it.value), range(4)))
@@ -0,0 +1,22 @@
+// Test line joins at the corners of grid and table borders.
+
+---
+// Thick borders with each line join.
+#for join in ("miter", "round", "bevel") {
+  table(
+    columns: 3,
+    stroke: (thickness: 4pt, join: join),
+    [A], [B], [C],
+    table.cell(colspan: 2)[D], [E],
+  )
+}
+
+---
+// Semi-transparent borders are not drawn twice at the corners.
+#grid(
+  columns: 2,
+  inset: 5pt,
+  stroke: 4pt + blue.transparentize(50%),
+  [A], [B],
+  [C], [D],
+)
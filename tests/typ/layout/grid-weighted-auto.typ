@@ -0,0 +1,61 @@
+// Test columns distributing leftover space in proportion to their contents.
+
+---
+// Wider columns receive a larger share of the leftover space.
+// Ref: false
+#let mark = locate(loc => [#metadata(loc.position().x) <x>])
+#block(width: 100pt, grid(
+  columns: ("weighted-auto", "weighted-auto", 0pt),
+  [#mark#box(width: 10pt)], [#mark#box(width: 30pt)], mark,
+))
+#locate(loc => {
+  let xs = query(<x>, loc).map(it => it.value)
+  test(xs.at(1) - xs.at(0), 25pt)
+  test(xs.at(2) - xs.at(1), 75pt)
+})
+
+---
+// Fixed columns are resolved before the leftover space is shared.
+// Ref: false
+#let mark = locate(loc => [#metadata(loc.position().x) <x>])
+#block(width: 100pt, grid(
+  columns: (20pt, "weighted-auto", "weighted-auto", 0pt),
+  [], [#mark#box(width: 10pt)], [#mark#box(width: 30pt)], mark,
+))
+#locate(loc => {
+  let xs = query(<x>, loc).map(it => it.value)
+  test(xs.at(1) - xs.at(0), 20pt)
+  test(xs.at(2) - xs.at(1), 60pt)
+})
+
+---
+// Fractional columns take all of the leftover space.
+// Ref: false
+#let mark = locate(loc => [#metadata(loc.position().x) <x>])
+#block(width: 100pt, grid(
+  columns: ("weighted-auto", 1fr, 0pt),
+  [#mark#box(width: 10pt)], mark, mark,
+))
+#locate(loc => {
+  let xs = query(<x>, loc).map(it => it.value)
+  test(xs.at(1) - xs.at(0), 10pt)
+  test(xs.at(2) - xs.at(1), 90pt)
+})
+
+---
+// Without a width constraint, the columns fit their contents.
+// Ref: false
+#style(styles => {
+  let g = grid(
+    columns: ("weighted-auto", "weighted-auto"),
+    box(width: 10pt),
+    box(width: 30pt),
+  )
+  test(measure(g, styles).width, 40pt)
+})
+
+---
+// The sizing round-trips through the columns field.
+// Ref: false
+#test(grid(columns: "weighted-auto").columns, ("weighted-auto",))
+#test(grid(columns: ("weighted-auto", 1fr)).columns, ("weighted-auto", 1fr))
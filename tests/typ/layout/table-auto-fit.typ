@@ -0,0 +1,57 @@
+// Test columns fitting the available width.
+
+---
+// As many columns as fit are laid out.
+// Ref: false
+#let cards = range(1, 8).map(n => [Card #n])
+#let fit(..args) = block(width: 200pt, table(..args, ..cards))
+#style(styles => test(
+  measure(fit(columns: (auto-fit: 60pt)), styles),
+  measure(fit(columns: (1fr,) * 3), styles),
+))
+
+---
+// Gutters take space away from the columns.
+// Ref: false
+#let cards = range(1, 8).map(n => [Card #n])
+#let fit(..args) = block(width: 200pt, table(..args, ..cards))
+#style(styles => test(
+  measure(fit(columns: (auto-fit: 60pt), column-gutter: 20pt), styles),
+  measure(fit(columns: (1fr,) * 2, column-gutter: 20pt), styles),
+))
+
+---
+// There is always at least one column.
+// Ref: false
+#let fit(..args) = block(width: 20pt, table(..args, [A], [B]))
+#style(styles => test(
+  measure(fit(columns: (auto-fit: 60pt)), styles),
+  measure(fit(columns: 1fr), styles),
+))
+
+---
+// Without a limited width, each cell gets its own column.
+// Ref: false
+#style(styles => test(
+  measure(table(columns: (auto-fit: 20pt), [A], [B]), styles),
+  measure(table(columns: (20pt, 20pt), [A], [B]), styles),
+))
+
+---
+// Without a limited width, the cells of headers and rows count as well.
+// Ref: false
+#let cells = (table.header[A][B][C], table.row[D][E][F], table.row[G][H][I])
+#style(styles => test(
+  measure(table(columns: (auto-fit: 20pt), ..cells), styles),
+  measure(table(columns: (20pt,) * 3, ..cells), styles),
+))
+
+---
+// The last row is filled even if the table errors on incomplete rows.
+// Ref: false
+#set table(on-incomplete-row: "error")
+#block(width: 200pt, table(columns: (auto-fit: 60pt), [A], [B], [C], [D]))
+
+---
+// Error: 2-48 minimum column width must be positive
+#table(columns: (auto-fit: 0pt), [A], [B], [C])
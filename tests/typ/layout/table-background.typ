@@ -0,0 +1,51 @@
+// Test content drawn behind table cells.
+
+---
+// The background is laid out into the size of each cell.
+// Ref: false
+#table(
+  columns: (20pt, 30pt),
+  rows: 10pt,
+  inset: 2pt,
+  background: (x, y) => layout(size => [#metadata(size) <size>]),
+  [A], [B],
+  table.cell(colspan: 2)[C],
+)
+#locate(loc => test(
+  query(<size>, loc).map(it => it.value),
+  (
+    (width: 20pt, height: 10pt),
+    (width: 30pt, height: 10pt),
+    (width: 50pt, height: 10pt),
+  ),
+))
+
+---
+// The function may leave cells without background.
+// Ref: false
+#table(
+  columns: 2,
+  background: (x, y) => if x == 1 [#metadata((x, y)) <bg>],
+  [A], [B],
+  [C], [D],
+)
+#locate(loc => test(query(<bg>, loc).map(it => it.value), ((1, 0), (1, 1))))
+
+---
+// The background is drawn above the fill and below the body, also for cells
+// on other layers.
+// Ref: false
+#table(
+  columns: 2,
+  fill: yellow,
+  background: rect(
+    width: 100%,
+    height: 100%,
+    fill: pattern(size: (4pt, 4pt), line(end: (4pt, 4pt))),
+  ),
+  [A], table.cell(layer: 1)[B],
+)
+
+---
+// Error: 14-16 expected content, none, array, or function, found integer
+#table(background: 12)[A]
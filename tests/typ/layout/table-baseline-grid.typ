@@ -0,0 +1,41 @@
+// Test snapping the text of table cells to a baseline grid.
+
+---
+// Cells with different top insets line up on the grid.
+// Ref: false
+#set table(baseline-grid: 20pt, stroke: none)
+#style(styles => {
+  let small = measure(table(inset: 0pt)[A], styles)
+  let large = measure(table(inset: (top: 5pt, rest: 0pt))[A], styles)
+  test(small.height, large.height)
+})
+#table(
+  columns: 3,
+  inset: (x: 5pt, bottom: 5pt),
+  table.cell(inset: (top: 0pt))[Top],
+  table.cell(inset: (top: 8pt))[Lower],
+  table.cell(inset: (top: 3pt))[Two \ lines],
+)
+
+---
+// Cells without text are left as they are.
+// Ref: false
+#style(styles => {
+  let size = measure(table(baseline-grid: 20pt, inset: 0pt, rect(height: 7pt)), styles)
+  test(size.height, 7pt)
+})
+
+---
+// Aligned content snaps as well.
+// Ref: false
+#table(
+  columns: 3,
+  rows: 60pt,
+  baseline-grid: 12pt,
+  align: (top, horizon, bottom),
+  [Top], [Middle], [Bottom],
+)
+
+---
+// Error: 23-28 expected length or none, found string
+#table(baseline-grid: "4pt")
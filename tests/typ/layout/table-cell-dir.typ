@@ -0,0 +1,42 @@
+// Test per-cell writing direction.
+
+---
+// A right-to-left cell in a left-to-right table aligns its start to the right.
+// Ref: false
+#let mark = locate(loc => [#metadata(loc.position().x) <x>])
+#table(
+  columns: 100pt,
+  align: start,
+  table.cell(dir: rtl)[#mark مرحبا],
+  table.cell(align: right, mark),
+  mark,
+)
+
+#locate(loc => {
+  let (rtl, right, ltr) = query(<x>, loc).map(it => it.value)
+  test(rtl, right)
+  test(ltr < right, true)
+})
+
+---
+// The cell's direction takes precedence over the table's direction.
+// Ref: false
+#let mark = locate(loc => [#metadata(loc.position().x) <x>])
+#set text(dir: rtl)
+#table(
+  columns: 100pt,
+  align: start,
+  table.cell(dir: ltr, mark),
+  table.cell(align: left, mark),
+  mark,
+)
+
+#locate(loc => {
+  let (ltr, left, rtl) = query(<x>, loc).map(it => it.value)
+  test(ltr, left)
+  test(rtl > left, true)
+})
+
+---
+// Error: 24-27 text direction must be horizontal
+#table(table.cell(dir: ttb)[A])
@@ -0,0 +1,49 @@
+// Test nested tables inheriting the look of their table.
+
+---
+// The inset is only inherited when a cell opts into it.
+// Ref: false
+#style(styles => {
+  let inherited = measure(table(
+    inset: 10pt,
+    table.cell(inherit: true, table(stroke: none, [A])),
+  ), styles)
+  let explicit = measure(table(
+    inset: 10pt,
+    table(inset: 10pt, stroke: none, [A]),
+  ), styles)
+  let plain = measure(table(
+    inset: 10pt,
+    table(stroke: none, [A]),
+  ), styles)
+  test(inherited, explicit)
+  test(plain == explicit, false)
+})
+
+---
+// Arguments of the nested table take precedence.
+// Ref: false
+#style(styles => {
+  let inherited = measure(table(
+    inset: 10pt,
+    table.cell(inherit: true, table(inset: 2pt, stroke: none, [A])),
+  ), styles)
+  let explicit = measure(table(
+    inset: 10pt,
+    table(inset: 2pt, stroke: none, [A]),
+  ), styles)
+  test(inherited, explicit)
+})
+
+---
+// Inherited strokes, insets and alignments.
+// Ref: false
+#table(
+  columns: 2,
+  stroke: blue,
+  inset: 8pt,
+  align: center,
+  [Outer], [Table],
+  table.cell(inherit: true, table(columns: 2, [Inner], [Cells], [A], [B])),
+  table(columns: 2, [Inner], [Cells], [A], [B]),
+)
@@ -0,0 +1,26 @@
+// Test labelling table cells.
+
+---
+// A labelled cell can be queried and linked to.
+// Ref: false
+#table(
+  columns: 2,
+  [A1], [B1],
+  [A2], table.cell(label: <b2>, fill: yellow)[B2],
+)
+
+#link(<b2>)[Go to B2]
+#locate(loc => {
+  let cells = query(<b2>, loc)
+  test(cells.len(), 1)
+  test(cells.first().body, [B2])
+  test(link.resolve(<b2>, loc).page, 1)
+})
+
+---
+#table(
+  table.cell(label: <a>)[A],
+  // Error: 3-28 label `<a>` is already used by another cell
+  // Hint: 3-28 cell labels must be unique within a grid
+  table.cell(label: <a>)[B],
+)
@@ -0,0 +1,41 @@
+// Test minimum widths of table cells.
+
+---
+// An auto column doesn't get narrower than the largest minimum width.
+// Ref: false
+#style(styles => {
+  let size = measure(table(
+    columns: 1,
+    inset: 0pt,
+    stroke: none,
+    table.cell(min-width: 20pt)[A],
+    table.cell(min-width: 40pt)[B],
+  ), styles)
+  test(size.width, 40pt)
+})
+
+---
+// Wider content still widens the column.
+// Ref: false
+#style(styles => {
+  let size = measure(table(
+    inset: 0pt,
+    stroke: none,
+    table.cell(min-width: 10pt, box(width: 30pt)),
+  ), styles)
+  test(size.width, 30pt)
+})
+
+---
+// A cell spanning multiple columns widens the last of them.
+// Ref: false
+#style(styles => {
+  let size = measure(table(
+    columns: 2,
+    inset: 0pt,
+    stroke: none,
+    table.cell(colspan: 2, min-width: 50pt)[A],
+    [B], [C],
+  ), styles)
+  test(size.width, 50pt)
+})
@@ -0,0 +1,35 @@
+// Test the coordinates and names of table cells in show rules.
+
+---
+// A show rule can prepend the cell's name to its body.
+// Ref: false
+#show table.cell: it => [#metadata(it.name + ": " + it.body.text) <cell>]
+#table(
+  columns: 3,
+  table.header[Name][Qty][Price],
+  [Pens], table.cell(colspan: 2)[n/a],
+  [Ink], [2], [1.50],
+)
+#locate(loc => test(
+  query(<cell>, loc).map(it => it.value),
+  (
+    "A1: Name", "B1: Qty", "C1: Price",
+    "A2: Pens", "B2: n/a",
+    "A3: Ink", "B3: 2", "C3: 1.50",
+  ),
+))
+
+---
+// Columns after the 26th are named with two letters and a title above
+// the table doesn't count as a row.
+// Ref: false
+#show table.cell: it => [#metadata((it.x, it.y, it.name)) <cell>]
+#table(
+  columns: 28,
+  table.title[Caption],
+  ..range(28).map(str),
+)
+#locate(loc => test(
+  query(<cell>, loc).map(it => it.value).slice(-3),
+  ((25, 0, "Z1"), (26, 0, "AA1"), (27, 0, "AB1")),
+))
@@ -0,0 +1,37 @@
+// Test notes of table cells.
+
+---
+// Notes are numbered in the order their cells are given and listed below the
+// table.
+// Ref: false
+#table(
+  columns: 2,
+  note-numbering: n => [#metadata(n) <num>#n],
+  table.header([Year], table.cell(note: [In millions.])[Revenue]),
+  [2022], table.cell(note: [Estimated.#metadata(none) <note>])[120],
+  [2023], [135],
+)
+#locate(loc => {
+  test(query(<num>, loc).map(it => it.value), (1, 2, 1, 2))
+  test(query(<note>, loc).len(), 1)
+})
+
+---
+// The notes come before a bottom title.
+// Ref: false
+#table(
+  columns: 2,
+  note-numbering: "*",
+  table.title(position: bottom)[Quarterly results],
+  [Q1], table.cell(note: [Preliminary.])[12],
+  [Q2], [14],
+)
+
+---
+// The notes take a line of their own.
+// Ref: false
+#style(styles => {
+  let plain = measure(table(columns: 2, [A], [B]), styles)
+  let noted = measure(table(columns: 2, [A], table.cell(note: [N])[B]), styles)
+  test(plain.height < noted.height, true)
+})
@@ -0,0 +1,25 @@
+// Test overflow treatments of table cells.
+
+---
+// Ref: false
+#table(
+  columns: 4 * (50pt,),
+  rows: 24pt,
+  fill: (x, _) => if x == 3 { yellow },
+  ..("visible", "clip", "ellipsis", "fade").map(mode => table.cell(
+    overflow: mode,
+    lorem(8),
+  )),
+)
+
+---
+// Cells in auto rows grow instead of overflowing.
+// Ref: false
+#style(styles => {
+  let cell(mode) = table(columns: 50pt, table.cell(overflow: mode, lorem(8)))
+  test(measure(cell("clip"), styles), measure(cell("visible"), styles))
+})
+
+---
+// Error: 29-35 expected "visible", "clip", "ellipsis", or "fade"
+#table(table.cell(overflow: "hide")[A])
@@ -0,0 +1,42 @@
+// Test custom cell renderers.
+
+---
+// The renderer receives the body, the resolved inset and the alignment.
+// Ref: false
+#let render(body, inset, align) = [#metadata((body, inset, align)) <args>]
+#table(
+  inset: (x: 4pt, y: 2pt),
+  align: right,
+  table.cell(render: render)[A],
+)
+
+#locate(loc => test(
+  query(<args>, loc).first().value,
+  ([A], (left: 4pt + 0%, top: 2pt + 0%, right: 4pt + 0%, bottom: 2pt + 0%), right),
+))
+
+---
+// The rendered content replaces the padded body.
+// Ref: false
+#let bare(body, inset, align) = body
+#style(styles => test(
+  measure(table(stroke: none, table.cell(render: bare)[Text]), styles),
+  measure(table(stroke: none, inset: 0pt)[Text], styles),
+))
+
+---
+// A renderer can draw within the cell, around the padded body.
+// Ref: false
+#table(
+  columns: 2,
+  [Status],
+  table.cell(render: (body, inset, align) => pad(..inset, box(
+    stroke: (left: 2pt + red),
+    inset: (left: 4pt),
+    body,
+  )))[Failed],
+)
+
+---
+// Error: 27-36 expected content, found integer
+#table(table.cell(render: (..) => 1)[A])
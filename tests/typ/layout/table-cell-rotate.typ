@@ -0,0 +1,43 @@
+// Test rotated table cells.
+
+---
+// Ref: false
+#table(
+  columns: 4,
+  align: center + horizon,
+  [],
+  table.cell(rotate: -90deg)[*Participants*],
+  table.cell(rotate: -90deg)[*Average age*],
+  table.cell(rotate: -90deg)[*Share of women*],
+  [Study A], [120], [34], [52%],
+  [Study B], [85], [41], [47%],
+)
+
+---
+// Rotated headers make the columns narrower and the header row taller.
+// Ref: false
+#style(styles => {
+  let header = ([Participants], [Average age], [Share of women])
+  let cells = ([120], [34], [52%])
+  let rotated = header.map(table.cell.with(rotate: 90deg))
+  let plain = measure(table(columns: 3, ..header, ..cells), styles)
+  let turned = measure(table(columns: 3, ..rotated, ..cells), styles)
+  test(turned.width < plain.width, true)
+  test(turned.height > plain.height, true)
+})
+
+---
+// A rotated cell which doesn't fit into the rest of the page isn't wrapped,
+// but moves to the next page.
+// Ref: false
+#set page(height: 100pt, margin: 0pt)
+#show table.cell: it => [#metadata(none) <cell> #it]
+#v(60pt)
+#table(
+  table.cell(rotate: 90deg)[Participants],
+  [120],
+)
+#locate(loc => test(
+  query(<cell>, loc).map(it => it.location().page()),
+  (2, 2),
+))
@@ -0,0 +1,63 @@
+// Test cells spilling into the empty cells following them.
+
+---
+// The content spills up to the first non-empty cell.
+// Ref: false
+#let note = table.cell(spill: true, {
+  let b = box(width: 30pt, height: 10pt)
+  [#b #b #b]
+})
+#style(styles => {
+  let measure-table(..cells) = measure(table(
+    columns: (40pt,) * 3,
+    inset: 0pt,
+    stroke: none,
+    ..cells,
+  ), styles)
+  let free = measure-table(note, [], [])
+  let stopped = measure-table(note, [], [C])
+  let blocked = measure-table(note, [B], [C])
+  let plain = measure-table(note.body, [B], [C])
+  test(free.height < stopped.height, true)
+  test(stopped.height < blocked.height, true)
+  test(blocked.height, plain.height)
+
+  // The columns are sized as if the cell didn't spill.
+  test(free.width, 120pt)
+})
+
+---
+// The content also spills into the empty cells at the end of the row which
+// are added automatically.
+// Ref: false
+#style(styles => {
+  let b = box(width: 30pt, height: 10pt)
+  let t(..cells) = measure(table(
+    columns: (40pt,) * 3,
+    inset: 0pt,
+    stroke: none,
+    ..cells,
+  ), styles)
+  test(
+    t(table.cell(spill: true)[#b #b #b]).height,
+    t(table.cell(spill: true)[#b #b #b], [], []).height,
+  )
+})
+
+---
+// In RTL tables, the content spills towards the left.
+// Ref: false
+#set text(dir: rtl)
+#style(styles => {
+  let b = box(width: 30pt, height: 10pt)
+  let t(..cells) = measure(table(
+    columns: (40pt,) * 3,
+    inset: 0pt,
+    stroke: none,
+    ..cells,
+  ), styles)
+  test(
+    t(table.cell(spill: true)[#b #b #b], [], []).height < t([#b #b #b], [], []).height,
+    true,
+  )
+})
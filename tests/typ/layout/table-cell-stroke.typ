@@ -0,0 +1,36 @@
+// Test stroke overrides for individual sides of table cells.
+
+---
+// Only the bottom edge of the header cells is drawn.
+// Ref: false
+#table(
+  columns: 3,
+  stroke: none,
+  ..([A], [B], [C]).map(table.cell.with(stroke: (bottom: 1pt))),
+  [1], [2], [3],
+)
+
+---
+// Disabling the table's stroke through a set rule keeps the lines of the
+// cells, also in nested tables.
+// Ref: false
+#set table(stroke: none)
+#table(
+  columns: 2,
+  table.cell(stroke: (bottom: 1pt))[A], table.cell(stroke: (left: 1pt))[B],
+  [C], table(columns: 2, table.cell(stroke: (right: 1pt))[D], [E]),
+)
+
+---
+// Unspecified sides keep the table's stroke, `none` erases a side.
+// Ref: false
+#table(
+  columns: 3,
+  table.cell(stroke: (bottom: 2pt + red))[A], [B], [C],
+  [1], table.cell(stroke: (bottom: none))[2], [3],
+  [4], [5], table.cell(stroke: (left: none, top: blue))[6],
+)
+
+---
+// Error: 27-54 unexpected key "middle", valid keys are "left", "top", "right", "bottom", "x", "y", and "rest"
+#table(table.cell(stroke: (bottom: 1pt, middle: 2pt))[A])
@@ -0,0 +1,29 @@
+// Test vertical writing in table cells.
+
+---
+// A vertical cell is as wide as a character and as tall as its characters
+// stacked, while the other cells stay horizontal.
+// Ref: false
+#set text(font: "Noto Serif CJK SC")
+#set table(inset: 0pt, stroke: none)
+#style(styles => {
+  let size(body) = measure(body, styles)
+  let stacked = "春眠不觉晓".clusters().map(c => [#c]).join(linebreak())
+  let vertical = size(table(
+    columns: 2,
+    table.cell(writing: "vertical")[春眠不觉晓],
+    [孟浩然],
+  ))
+  test(vertical.width, size[春].width + size[孟浩然].width)
+  test(vertical.height, size(stacked).height)
+})
+
+---
+// Spaces are dropped and styles are kept.
+// Ref: false
+#set text(font: "Noto Serif CJK SC")
+#set table(inset: 0pt, stroke: none)
+#style(styles => test(
+  measure(table(table.cell(writing: "vertical")[春 *眠*]), styles),
+  measure(table[春#linebreak()*眠*], styles),
+))
@@ -0,0 +1,47 @@
+// Test collapsing tables into label-value pairs in narrow regions.
+
+---
+// The header's cells label the cells in the collapsed table.
+// Ref: false
+#show table.cell: it => [#metadata(it.body) <cell>]
+#block(width: 100pt, table(
+  columns: 2,
+  collapse-below: 150pt,
+  table.header[A][B],
+  [1], [2],
+  [3], [4],
+))
+#locate(loc => test(
+  query(<cell>, loc).map(it => it.value),
+  ([A], [1], [B], [2], [A], [3], [B], [4]),
+))
+
+---
+// Without a header, the columns are numbered.
+// Ref: false
+#show table.cell: it => [#metadata(it.body) <cell>]
+#block(width: 100pt, table(
+  columns: 2,
+  collapse-below: 150pt,
+  [x], [y],
+  table.cell(colspan: 2)[z],
+))
+#locate(loc => test(
+  query(<cell>, loc).map(it => it.value),
+  ([1], [x], [2], [y], [1], [z]),
+))
+
+---
+// In a wide enough region, the table is laid out as usual.
+// Ref: false
+#show table.cell: it => [#metadata(it.body) <cell>]
+#block(width: 200pt, table(
+  columns: 2,
+  collapse-below: 150pt,
+  table.header[A][B],
+  [1], [2],
+))
+#locate(loc => test(
+  query(<cell>, loc).map(it => it.value),
+  ([A], [B], [1], [2]),
+))
@@ -0,0 +1,26 @@
+// Test the lines around and within cells spanning multiple columns.
+
+---
+// No line crosses a spanning cell in any stroke mode, also with dashes.
+// Ref: false
+#let cells = (
+  table.cell(colspan: 2)[Merged], [A],
+  [B], [C], [D],
+  table.cell(colspan: 3)[Wide],
+)
+#for mode in ("grid", "cells") {
+  table(columns: 3, stroke: (dash: "dashed"), stroke-mode: mode, ..cells)
+  table(columns: 3, gutter: 3pt, stroke: (dash: "dotted"), stroke-mode: mode, ..cells)
+}
+
+---
+// Cells below a spanning cell draw their parts of its bottom edge.
+// Ref: false
+#table(
+  columns: 3,
+  stroke: (dash: "dashed"),
+  table.cell(colspan: 3)[Merged],
+  table.cell(stroke: (top: 2pt + red))[A],
+  [B],
+  table.cell(stroke: (top: 2pt + blue))[C],
+)
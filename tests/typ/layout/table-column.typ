@@ -0,0 +1,124 @@
+// Test per-column number formatting.
+
+---
+// Only plain numbers are formatted.
+// Ref: false
+#show table.cell: it => [#metadata(it.body) <cell>]
+#table(
+  columns: 2,
+  table.column(1, format: n => str(n) + "€"),
+  [Bread], [2],
+  [Cheese], [4.5],
+  [Wine], [*12*],
+  [Water], [free],
+)
+#locate(loc => test(
+  query(<cell>, loc).map(it => it.value),
+  ([Bread], [2€], [Cheese], [4.5€], [Wine], [*12*], [Water], [free]),
+))
+
+---
+// Strings are accepted as formatted values.
+// Ref: false
+#table(
+  table.column(0, format: n => str(n * 2)),
+  [21],
+)
+
+---
+#table(
+  columns: 2,
+  // Error: 3-18 column index 2 is out of range
+  // Hint: 3-18 the table only has 2 column(s)
+  table.column(2),
+)
+
+---
+#table(
+  // Error: 27-33 expected content, found integer
+  table.column(0, format: n => n),
+  [1],
+)
+
+---
+// Numbers are written with the separators of the text language.
+// Ref: false
+#show table.cell: it => [#metadata(it.body.at("text", default: none)) <number>]
+#let numbers = table.with(
+  table.column(0, format: "number"),
+  [1234.56], [1234567], [0.5], [*1234*],
+)
+
+#numbers()
+#text(lang: "de", numbers())
+#text(lang: "de", region: "ch", numbers())
+#locate(loc => test(
+  query(<number>, loc).map(it => it.value).chunks(4),
+  (
+    ("1,234.56", "1,234,567", "0.5", none),
+    ("1.234,56", "1.234.567", "0,5", none),
+    ("1’234.56", "1’234’567", "0.5", none),
+  ),
+))
+
+---
+// The separators can be overridden regardless of the language.
+// Ref: false
+#show table.cell: it => [#metadata(it.body.text) <number>]
+#set text(lang: "de")
+#table(
+  columns: 2,
+  table.column(0, format: "number", thousands-separator: ""),
+  table.column(1, format: "number", thousands-separator: " ", decimal-separator: "."),
+  [1234.56], [1234.56],
+)
+#locate(loc => test(
+  query(<number>, loc).map(it => it.value),
+  ("1234,56", "1 234.56"),
+))
+
+---
+// Error: 32-41 expected "number", function, or none, found string
+#table(table.column(0, format: "numbers"), [1])
+
+---
+// Error: 61-65 expected exactly one character
+#table(table.column(0, format: "number", decimal-separator: "ab"), [1])
+
+---
+// A wrapping column gives up its width before the other columns.
+// Ref: false
+#let long = [Lorem ipsum dolor sit amet consectetur]
+#let widths(mode) = table(
+  columns: 2,
+  inset: 0pt,
+  stroke: none,
+  table.column(1, mode: mode),
+  layout(size => [#metadata(size.width) <first>]),
+  long,
+  long,
+)
+
+#block(width: 200pt, {
+  widths(auto)
+  widths("wrap")
+  widths("expand")
+})
+
+#locate(loc => {
+  let (fair, wrap, expand) = query(<first>, loc).map(it => it.value)
+  test(fair < wrap, true)
+  test(expand < fair, true)
+})
+
+---
+// Modes don't change columns that fit.
+// Ref: false
+#style(styles => test(
+  measure(table(columns: 2, table.column(0, mode: "wrap"), [A], [B]), styles),
+  measure(table(columns: 2, table.column(0, mode: "expand"), [A], [B]), styles),
+))
+
+---
+// Error: 30-36 expected "wrap", "expand", or auto
+#table(table.column(0, mode: "fill"))
@@ -0,0 +1,42 @@
+// Test inferring the number of table columns.
+
+---
+// From the header.
+// Ref: false
+#style(styles => {
+  let inferred = measure(table(
+    table.header[A][B][C],
+    [1], [2], [3],
+  ), styles)
+  let explicit = measure(table(
+    columns: 3,
+    table.header[A][B][C],
+    [1], [2], [3],
+  ), styles)
+  test(inferred, explicit)
+})
+
+---
+// From the first row, counting cells spanning multiple columns.
+// Ref: false
+#style(styles => {
+  let inferred = measure(table(
+    table.row(table.cell(colspan: 2)[A], [B]),
+    [1], [2], [3],
+  ), styles)
+  let explicit = measure(table(
+    columns: 3,
+    table.cell(colspan: 2)[A], [B],
+    [1], [2], [3],
+  ), styles)
+  test(inferred, explicit)
+})
+
+---
+// Explicit columns take precedence.
+// Ref: false
+#style(styles => {
+  let single = measure(table(columns: 1, table.header[A][B]), styles)
+  let double = measure(table(columns: 2, table.header[A][B]), styles)
+  test(single.height > double.height, true)
+})
@@ -0,0 +1,31 @@
+// Test aligning numbers in table columns at their decimal separator.
+
+---
+// Ref: false
+#table(
+  columns: 2,
+  table.column(1, align: "decimal"),
+  [*Item*], [*Price*],
+  [Bread], [2.5],
+  [Cheese], [14.333],
+  [Wine], [120],
+  [Debt], [-1 200.05],
+  [Water], [_free_],
+)
+
+---
+// The separator depends on the text language.
+// Ref: false
+#set text(lang: "de")
+#table(
+  columns: 2,
+  table.column(0, align: "decimal"),
+  table.column(1, align: "decimal", format: n => str(n).replace(".", ",")),
+  [1,5], [2.5],
+  [10,25], [3],
+  [1.000,1], [0.125],
+)
+
+---
+// Error: 31-37 expected "decimal" or none
+#table(table.column(0, align: "left"))
@@ -0,0 +1,42 @@
+// Test the density presets of tables.
+
+---
+// The density changes the padding of all cells uniformly.
+// Ref: false
+#let sized(styles, ..args) = measure(table(
+  columns: 2,
+  stroke: none,
+  ..args,
+  ..range(4).map(_ => box(width: 10pt, height: 10pt)),
+), styles)
+
+#style(styles => {
+  test(sized(styles, density: "compact"), (width: 30pt, height: 30pt))
+  test(sized(styles), (width: 40pt, height: 40pt))
+  test(sized(styles, density: "comfortable"), (width: 40pt, height: 40pt))
+  test(sized(styles, density: "spacious"), (width: 52pt, height: 52pt))
+})
+
+---
+// An explicit inset overrides the preset, but only on the given sides.
+// Ref: false
+#let sized(styles, ..args) = measure(table(
+  columns: 2,
+  stroke: none,
+  ..args,
+  ..range(4).map(_ => box(width: 10pt, height: 10pt)),
+), styles)
+
+#style(styles => {
+  test(sized(styles, density: "spacious", inset: 0pt), (width: 20pt, height: 20pt))
+  test(sized(styles, density: "compact", inset: (x: 5pt)), (width: 40pt, height: 30pt))
+})
+
+#set table(inset: (y: 1pt))
+#style(styles => {
+  test(sized(styles, density: "spacious"), (width: 52pt, height: 24pt))
+})
+
+---
+// Error: 17-24 expected "compact", "comfortable", or "spacious"
+#table(density: "dense")
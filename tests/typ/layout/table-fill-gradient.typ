@@ -0,0 +1,43 @@
+// Test filling table cells with gradients and patterns.
+
+---
+// Functions can return gradients, which reach the cells unchanged.
+// Ref: false
+#let colors = (red, green, blue)
+#show table.cell: it => [#metadata(it.fill) <fill>#it]
+#table(
+  columns: 3,
+  fill: (x, _) => gradient.linear(white, colors.at(x)),
+  [A], [B], [C],
+  table.cell(colspan: 2)[Wide], [D],
+)
+#locate(loc => test(
+  query(<fill>, loc).map(it => it.value),
+  (0, 1, 2, 0, 2).map(x => gradient.linear(white, colors.at(x))),
+))
+
+---
+// Gradients relative to each cell and to the whole table.
+#table(
+  columns: (1fr,) * 3,
+  rows: 20pt,
+  fill: gradient.linear(red, blue),
+  [], [], [],
+)
+#table(
+  columns: (1fr,) * 3,
+  rows: 20pt,
+  fill: gradient.linear(red, blue, relative: "parent"),
+  [], [], [],
+)
+
+---
+// The same pattern in several cells starts anew in each of them.
+#let pat = pattern(size: (12pt, 12pt), line(start: (0%, 0%), end: (100%, 100%)))
+#table(
+  columns: (25pt, 30pt, 35pt),
+  rows: 20pt,
+  fill: (x, _) => if x > 0 { pat },
+  stroke: (x, _) => if x == 2 { (paint: pat, thickness: 4pt) },
+  [], [], [],
+)
@@ -0,0 +1,39 @@
+// Test aggregating footers.
+
+---
+// Columns are aggregated separately, non-numeric cells are skipped.
+// Ref: false
+#show table.cell: it => [#metadata(it.body) <cell>]
+#table(
+  columns: 3,
+  table.header[Item][Count][Price],
+  [Bread], [2], [2.5],
+  [Cheese], [1], [4.25],
+  [Wine], [3], [n/a],
+  table.footer(aggregate: (none, "sum", "average"))[Total],
+)
+#locate(loc => test(
+  query(<cell>, loc).map(it => it.value).slice(-3),
+  ([Total], [6], [3.375]),
+))
+
+---
+// The result is formatted like the rest of its column.
+// Ref: false
+#show table.cell: it => [#metadata(it.body) <cell>]
+#table(
+  columns: 2,
+  table.column(1, format: n => str(n) + " €"),
+  [A], [10],
+  [B], [5],
+  table.footer(aggregate: "max"),
+)
+#locate(loc => {
+  let bodies = query(<cell>, loc).map(it => it.value)
+  test(bodies.len(), 6)
+  test(bodies.last().text, "10 €")
+})
+
+---
+// Error: 32-37 expected "sum", "average", "min", "max", array, or none
+#table(table.footer(aggregate: "avg"))
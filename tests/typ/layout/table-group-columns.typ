@@ -0,0 +1,37 @@
+// Test grouping columns with a wider gutter.
+
+---
+// Ref: false
+#table(
+  columns: 7,
+  group-columns: (size: 3, gutter: 8pt),
+  [*Q*], ..range(1, 7).map(n => [#n]),
+  [*A*], ..range(6).map(n => [#(n * 12)]),
+)
+
+---
+// The group gutter replaces the column gutter at group boundaries.
+// Ref: false
+#let cells = range(5).map(n => [#n])
+#let groups = (size: 2, gutter: 8pt)
+#style(styles => test(
+  measure(table(columns: 5, column-gutter: 2pt, group-columns: groups, ..cells), styles),
+  measure(table(columns: 5, column-gutter: (2pt, 8pt, 2pt, 8pt), ..cells), styles),
+))
+
+---
+// Without a group boundary, the gutter stays as is.
+// Ref: false
+#let groups = (size: 3, gutter: 8pt)
+#style(styles => test(
+  measure(table(columns: 3, group-columns: groups, [A], [B], [C]), styles),
+  measure(table(columns: 3, [A], [B], [C]), styles),
+))
+
+---
+// Error: 35-44 dictionary does not contain key "gutter"
+#table(columns: 2, group-columns: (size: 2))
+
+---
+// Error: 35-57 number must be positive
+#table(columns: 2, group-columns: (size: 0, gutter: 2pt))
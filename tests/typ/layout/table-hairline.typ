@@ -0,0 +1,33 @@
+// Test the minimum thickness of table lines.
+
+---
+// Thin lines of all stroke modes are thickened.
+#set table(columns: 2, hairline: 0.5pt)
+#table(stroke: 0.01pt, [A], [B], [C], [D])
+#table(stroke: 0.01pt, stroke-mode: "cells", [A], [B], [C], [D])
+#table(
+  stroke: 0.01pt,
+  stroke-mode: "booktabs",
+  table.header[A][B],
+  [C], [D],
+)
+#table(
+  stroke: none,
+  table.cell(stroke: 0.01pt)[A], [B],
+  [C], table.cell(stroke: (bottom: 0.02pt))[D],
+)
+
+---
+// Thicker lines are left as they are and the layout doesn't change.
+// Ref: false
+#let cells = ([A], [B], [C], [D])
+#style(styles => {
+  let exact = measure(table(columns: 2, stroke: 0.01pt, ..cells), styles)
+  let thick = measure(table(columns: 2, stroke: 0.01pt, hairline: 1pt, ..cells), styles)
+  test(exact, thick)
+})
+#table(columns: 2, stroke: 2pt, hairline: 0.5pt, ..cells)
+
+---
+// Error: 17-23 expected length, found string
+#table(hairline: "thin")
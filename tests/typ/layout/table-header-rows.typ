@@ -0,0 +1,36 @@
+// Test repeating further rows along with a table header.
+
+---
+// The units row is repeated below the header on each page.
+// Ref: false
+#set page(height: 6em)
+#table(
+  columns: 2,
+  table.header(rows: (2,))[*City*][*Temp.*],
+  table.cell(colspan: 2)[Measured at noon],
+  [], [°C],
+  [Berlin], [21],
+  [Paris], [24],
+  [Rome], [28],
+  [Madrid], [31],
+)
+
+---
+// Repeated rows without header cells and with gutter.
+// Ref: false
+#set page(height: 6em)
+#table(
+  columns: 2,
+  gutter: 2pt,
+  stroke-mode: "booktabs",
+  table.header(rows: (0,)),
+  [*City*], [*Temp.*],
+  [Berlin], [21],
+  [Paris], [24],
+  [Rome], [28],
+)
+
+---
+// Error: 20-50 row index 5 is out of range
+// Hint: 20-50 the grid only has 2 row(s)
+#table(columns: 2, table.header(rows: (5,))[A][B], [C], [D])
@@ -0,0 +1,43 @@
+// Test the rule below a table's header.
+
+---
+// The rule is repeated below the header on each page.
+// Ref: false
+#set page(height: 8em)
+#table(
+  columns: 2,
+  header-stroke: 1.5pt,
+  table.header[*City*][*Temp.*],
+  [Berlin], [21],
+  [Paris], [24],
+  [Rome], [28],
+  [Madrid], [31],
+)
+
+---
+// The rule doesn't change the table's size.
+// Ref: false
+#let cities(..args) = table(
+  columns: 2,
+  ..args,
+  table.header[*City*][*Temp.*],
+  [Berlin], [21],
+)
+#style(styles => test(
+  measure(cities(header-stroke: 3pt + red), styles),
+  measure(cities(), styles),
+))
+
+---
+// The rule replaces the lighter rule of booktabs and is ignored without a
+// header.
+// Ref: false
+#table(
+  columns: 2,
+  stroke-mode: "booktabs",
+  header-stroke: 1pt,
+  table.header[*City*][*Temp.*],
+  [Berlin], [21],
+)
+#table(columns: 2, header-stroke: 1pt, [Berlin], [21])
+
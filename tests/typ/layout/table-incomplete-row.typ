@@ -0,0 +1,45 @@
+// Test the handling of an incomplete last row.
+
+---
+// By default and with "fill", the row is filled up with empty cells.
+// Ref: false
+#show table.cell: it => [#metadata(it.body) <cell>#it]
+#table(columns: 3, [A], [B], [C], [D])
+#table(columns: 3, on-incomplete-row: "fill", [A], [B], [C], [D])
+#locate(loc => test(query(<cell>, loc).len(), 12))
+
+---
+// With "drop", the row is left out.
+// Ref: false
+#show table.cell: it => [#metadata(it.body) <cell>#it]
+#table(
+  columns: 3,
+  on-incomplete-row: "drop",
+  [A], [B], [C],
+  [D], table.cell(colspan: 2)[E],
+  [F], [G],
+)
+#locate(loc => test(
+  query(<cell>, loc).map(it => it.value),
+  ([A], [B], [C], [D], [E]),
+))
+
+---
+// Complete rows and rows of the footer are left alone.
+// Ref: false
+#table(columns: 2, on-incomplete-row: "error", [A], table.cell(colspan: 2)[B])
+#table(
+  columns: 2,
+  on-incomplete-row: "error",
+  [A], [B],
+  table.footer[Total],
+)
+
+---
+// Error: 58-61 last row only fills 1 of 2 columns
+// Hint: 58-61 add cells or set `on-incomplete-row` to "fill" or "drop"
+#table(columns: 2, on-incomplete-row: "error", [A], [B], [C])
+
+---
+// Error: 39-47 expected "fill", "error", or "drop"
+#table(columns: 2, on-incomplete-row: "ignore")
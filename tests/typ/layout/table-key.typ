@@ -0,0 +1,45 @@
+// Test keying the per-cell properties of tables by rows and columns.
+
+---
+// Keys follow the rows and columns, whatever their position.
+// Ref: false
+#show table.cell: it => [#metadata((it.fill, it.align)) <cell>]
+#table(
+  columns: 2,
+  table.column(0, key: "name"),
+  fill: (x, y, row-key: none, column-key: none) => {
+    if row-key == "late" and column-key == "name" { red }
+  },
+  align: (x, y, column-key: none) => if column-key == "name" { right } else { auto },
+  ..("late", "early").map(key => table.row(key: key)[#key][#key]),
+)
+#locate(loc => test(
+  query(<cell>, loc).map(it => it.value),
+  ((red, right), (none, auto), (none, right), (none, auto)),
+))
+
+---
+// Functions taking just the position work for tables without keys.
+// Ref: false
+#table(
+  columns: 2,
+  fill: (x, y) => none,
+  align: (x, y) => left,
+  table.column(1, format: "number"),
+  table.row(group: none)[a][1],
+)
+
+---
+#table(
+  // Error: 10-24 unexpected argument: row-key
+  align: (x, y) => left,
+  table.row(key: 1)[a],
+)
+
+---
+#table(
+  // Error: 11-25 unexpected argument: column-key
+  stroke: (x, y) => none,
+  table.column(0, key: "a"),
+  [a],
+)
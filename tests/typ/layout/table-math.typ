@@ -0,0 +1,27 @@
+// Test rows fitting the equations in their cells.
+
+---
+// A tall inline equation reaching below its line still fits into the row.
+// Ref: false
+#style(styles => {
+  let eq = $sum_(i=1)^n 1/(i^2)$
+  let row = table(inset: 0pt, stroke: none, eq)
+  test(measure(row, styles).height > measure(eq, styles).height, true)
+})
+
+---
+// A block equation fits exactly, including its limits.
+// Ref: false
+#style(styles => {
+  let eq = $ sum_(i=1)^n 1/(i^2) $
+  let row = table(inset: 0pt, stroke: none, eq)
+  test(measure(row, styles).height, measure(eq, styles).height)
+})
+
+---
+// The descenders of plain text don't grow the row.
+// Ref: false
+#style(styles => {
+  let row = table(inset: 0pt, stroke: none)[Typography]
+  test(measure(row, styles).height, measure[Typography].height)
+})
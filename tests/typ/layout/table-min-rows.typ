@@ -0,0 +1,48 @@
+// Test keeping table rows together across pages.
+
+---
+// The last page gets at least two rows.
+// Ref: false
+#set page(height: 100pt, margin: 0pt)
+#show table.cell: it => [#metadata(none) <cell>]
+#table(
+  rows: 20pt,
+  min-rows-together: 2,
+  ..range(11).map(str),
+)
+#locate(loc => test(
+  query(<cell>, loc).map(it => it.location().page()),
+  (1, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3),
+))
+
+---
+// The table moves to the next page instead of leaving a single row behind.
+// Ref: false
+#set page(height: 100pt, margin: 0pt)
+#show table.cell: it => [#metadata(none) <cell>]
+#v(70pt)
+#table(
+  rows: 20pt,
+  min-rows-together: 2,
+  ..range(3).map(str),
+)
+#locate(loc => test(
+  query(<cell>, loc).map(it => it.location().page()),
+  (2, 2, 2),
+))
+
+---
+// No effect if the table has fewer rows than the minimum.
+// Ref: false
+#set page(height: 100pt, margin: 0pt)
+#show table.cell: it => [#metadata(none) <cell>]
+#v(70pt)
+#table(
+  rows: 20pt,
+  min-rows-together: 4,
+  ..range(3).map(str),
+)
+#locate(loc => test(
+  query(<cell>, loc).map(it => it.location().page()),
+  (1, 2, 2),
+))
@@ -0,0 +1,53 @@
+// Test tables that are wider than the available space.
+
+---
+// By default, a wide table silently extends past the available space.
+// Ref: false
+#table(columns: (60pt, 60pt), [A], [B])
+
+---
+// Ref: false
+// Warning: 2-58 table is wider than the available space
+// Hint: 2-58 set its `overflow` to `"shrink"` or `"clip"` to make it fit
+#table(columns: (60pt, 60pt), overflow: "warn", [A], [B])
+
+---
+// A shrunk table is scaled down uniformly, a clipped one keeps its size.
+// Ref: false
+#let wide(overflow) = block(width: 100pt, table(
+  columns: (100pt, 100pt),
+  rows: 20pt,
+  overflow: overflow,
+  [A], [B],
+))
+#style(styles => {
+  test(measure(wide("shrink"), styles).height, 10pt)
+  test(measure(wide("clip"), styles).height, 20pt)
+})
+
+---
+// Tables that fit are left alone.
+// Ref: false
+#let narrow = block(width: 100pt, table(
+  columns: (50pt, 50pt),
+  rows: 20pt,
+  overflow: "shrink",
+  [A], [B],
+))
+#style(styles => test(measure(narrow, styles).height, 20pt))
+
+---
+// A shrunk table is measured without reporting warnings, so that the
+// warnings of its cells are reported once.
+// Ref: false
+// Warning: 4:8-4:26 label `<nowhere>` does not exist in the document
+// Hint: 4:8-4:26 the link's body is shown without a link
+#block(width: 100pt, table(
+  columns: (100pt, 100pt),
+  overflow: "shrink",
+  [A], link(<nowhere>)[B],
+))
+
+---
+// Error: 18-24 expected "visible", "warn", "clip", or "shrink"
+#table(overflow: "auto")
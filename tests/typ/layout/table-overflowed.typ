@@ -0,0 +1,40 @@
+// Test querying the cells which overflowed a table.
+
+---
+// Nothing overflows.
+// Ref: false
+#table(columns: 2, [A], [B], [C], [D]) <fits>
+#locate(loc => test(query(<fits>, loc).first().overflowed, ()))
+
+---
+// Content taller than a row of fixed height.
+// Ref: false
+#table(
+  columns: 2,
+  rows: 2em,
+  [A], [B \ C \ D],
+  [E \ F \ G], [H],
+) <tall>
+#locate(loc => test(query(<tall>, loc).first().overflowed, ((1, 0), (0, 1))))
+
+---
+// Content wider than its column.
+// Ref: false
+#table(
+  columns: (2em, auto),
+  box(width: 5em)[A], [B],
+) <wide>
+#locate(loc => test(query(<wide>, loc).first().overflowed, ((0, 0),)))
+
+---
+// Positions are logical, even with gutter and in right-to-left tables.
+// Ref: false
+#set text(dir: rtl)
+#table(
+  columns: 2,
+  rows: 2em,
+  gutter: 3pt,
+  [A], [B \ C \ D],
+  [E], [F],
+) <rtl>
+#locate(loc => test(query(<rtl>, loc).first().overflowed, ((1, 0),)))
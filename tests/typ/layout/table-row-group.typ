@@ -0,0 +1,56 @@
+// Test striping grouped rows.
+
+---
+// Bands change whenever the group key does.
+// Ref: false
+#show table.cell: it => [#metadata(it.fill) <cell>]
+#table(
+  columns: 2,
+  fill: (x, y, band: none) => if band == none { red } else if calc.odd(band) { blue },
+  table.row(group: "A")[Alice][Tea],
+  table.row(group: "A")[Alice][Cake],
+  table.row(group: "B")[Bob][Coffee],
+  table.row(group: "A")[Alice][Tea],
+  [Total], [4],
+)
+#locate(loc => test(
+  query(<cell>, loc).map(it => it.value),
+  (none, none, none, none, blue, blue, none, none, red, red),
+))
+
+---
+// A row always occupies whole lines.
+// Ref: false
+#show grid.cell: it => [#metadata((it.fill, it.body)) <cell>]
+#grid(
+  columns: 3,
+  fill: (x, y, band: none) => if band != none { green },
+  [a],
+  grid.row(group: 1)[b][c][d][e],
+  [f],
+)
+#locate(loc => test(
+  query(<cell>, loc).map(it => it.value),
+  (
+    (none, [a]), (none, []), (none, []),
+    (green, [b]), (green, [c]), (green, [d]),
+    (green, [e]), (green, []), (green, []),
+    (none, [f]), (none, []), (none, []),
+  ),
+))
+
+---
+// Rows without a group don't receive a band.
+// Ref: false
+#table(
+  fill: (x, y) => none,
+  table.row[a],
+  table.row(group: none)[b],
+)
+
+---
+#table(
+  // Error: 9-23 unexpected argument: band
+  fill: (x, y) => none,
+  table.row(group: 1)[a],
+)
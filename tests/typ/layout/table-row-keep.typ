@@ -0,0 +1,36 @@
+// Test keeping groups of table rows on one page.
+
+---
+// Kept rows of the same group move to the next page as a whole.
+// Ref: false
+#set page(height: 100pt, margin: 0pt)
+#show table.cell: it => [#metadata(none) <cell>]
+#table(
+  rows: 20pt,
+  [0], [1], [2],
+  table.row(group: "A", keep: true)[3],
+  table.row(group: "A", keep: true)[4],
+  table.row(group: "A", keep: true)[5],
+  [6],
+)
+#locate(loc => test(
+  query(<cell>, loc).map(it => it.location().page()),
+  (1, 1, 1, 2, 2, 2, 2),
+))
+
+---
+// A group that doesn't fit on a page of its own is split anyway.
+// Ref: false
+#set page(height: 100pt, margin: 0pt)
+#show table.cell: it => [#metadata(none) <cell>]
+#table(
+  rows: 20pt,
+  [0],
+  // Warning: 26-68 rows kept together don't fit on one page
+  // Hint: 26-68 they are split across pages instead
+  ..range(1, 7).map(n => table.row(group: "A", keep: true, str(n))),
+)
+#locate(loc => test(
+  query(<cell>, loc).map(it => it.location().page()),
+  (1, 1, 1, 1, 1, 2, 2),
+))
@@ -0,0 +1,37 @@
+// Test tables made up of explicit rows.
+
+---
+// Rows matching the columns.
+// Ref: false
+#table(
+  columns: 3,
+  table.header[A][B][C],
+  table.row[1][2][3],
+  table.row(table.cell(colspan: 2)[4], [5]),
+)
+
+---
+// Rows can still be mixed with loose cells, which lifts the check.
+// Ref: false
+#table(
+  columns: 2,
+  table.row[1][2][3],
+  [4],
+)
+
+---
+#table(
+  columns: 2,
+  table.row[1][2],
+  // Error: 3-21 row 1 spans 3 column(s), but the table has 2
+  // Hint: 3-21 add or remove cells or change their colspans
+  table.row[3][4][5],
+)
+
+---
+#table(
+  columns: 3,
+  // Error: 3-18 row 0 spans 2 column(s), but the table has 3
+  // Hint: 3-18 add or remove cells or change their colspans
+  table.row[1][2],
+)
@@ -0,0 +1,32 @@
+// Test passing table cells as an array of rows.
+
+---
+// The number of columns is inferred from the rows.
+// Ref: false
+#style(styles => {
+  let data = measure(table(rows-data: (([A], [B]), ([C], [D]))), styles)
+  let flat = measure(table(columns: 2, [A], [B], [C], [D]), styles)
+  test(data, flat)
+})
+
+---
+// Explicit columns and cells passed directly come first.
+// Ref: false
+#style(styles => {
+  let data = measure(table(
+    columns: (1fr, 2fr),
+    table.header[X][Y],
+    rows-data: (([A], [B]),),
+  ), styles)
+  let flat = measure(table(
+    columns: (1fr, 2fr),
+    table.header[X][Y],
+    [A], [B],
+  ), styles)
+  test(data, flat)
+})
+
+---
+// Error: 19-39 row 1 has 1 cell(s), but the first row has 2
+// Hint: 19-39 all rows of `rows-data` must have the same length
+#table(rows-data: (([A], [B]), ([C],)))
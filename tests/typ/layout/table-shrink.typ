@@ -0,0 +1,27 @@
+// Test shrinking table cells to fit their column.
+
+---
+// A shrunk cell stays on one line instead of wrapping.
+// Ref: false
+#let cell(shrink) = table(
+  columns: 60pt,
+  inset: 0pt,
+  table.cell(shrink-to-fit: shrink)[Lorem ipsum dolor],
+)
+
+#cell(false)
+#cell(true)
+
+#style(styles => {
+  let line = measure([Lorem], styles).height
+  test(measure(cell(false), styles).height > line, true)
+  test(measure(cell(true), styles).height < line, true)
+})
+
+---
+// Content that fits is not scaled.
+// Ref: false
+#style(styles => test(
+  measure(table(columns: 60pt, table.cell(shrink-to-fit: true)[A]), styles),
+  measure(table(columns: 60pt, [A]), styles),
+))
@@ -0,0 +1,40 @@
+// Test striping the body rows of tables.
+
+---
+// The header and footer keep the fill, the first body row is the first stripe.
+// Ref: false
+#let expected = (H: green, "1": red, "2": blue, "3": red, F: green)
+#show table.cell: it => {
+  test(it.fill, expected.at(it.body.text))
+  it
+}
+#table(
+  fill: green,
+  stripes: (red, blue),
+  table.header[H],
+  [1], [2], [3],
+  table.footer[F],
+)
+
+---
+// Empty positions of the body are striped as well.
+// Ref: false
+#show table.cell: it => {
+  if it.body == [] { test(it.fill, blue) }
+  it
+}
+#table(
+  columns: 2,
+  stripes: (red, blue),
+  [A], [B],
+  [C],
+)
+
+---
+// Without stripes, the fill applies everywhere.
+// Ref: false
+#show table.cell: it => {
+  test(it.fill, green)
+  it
+}
+#table(fill: green, table.header[H], [1], [2])
@@ -0,0 +1,41 @@
+// Test per-cell table strokes.
+
+---
+// Strokes from a function. The diagonal cells own their bottom and right
+// edges, as well as their top and left edges at the table's border.
+// Ref: false
+#table(
+  columns: 3,
+  stroke: (x, y) => if x == y { 2pt + red } else { 0.5pt },
+  [1], [2], [3],
+  [4], [5], [6],
+  [7], [8], [9],
+)
+
+---
+// An array of strokes applies per column, also with spanning cells and
+// gutter.
+// Ref: false
+#table(
+  columns: 3,
+  gutter: 3pt,
+  stroke: (blue, none, 1pt + green),
+  table.cell(colspan: 2)[A], [B],
+  [C], [D], [E],
+)
+
+---
+// Per-cell strokes also work in the cells stroke mode.
+// Ref: false
+#table(
+  columns: 2,
+  stroke-mode: "cells",
+  stroke: (x, y) => if calc.even(x + y) { red },
+  [A], [B], [C], [D],
+)
+
+---
+// A single stroke still works as before.
+// Ref: false
+#table(columns: 2, stroke: 2pt + blue, [A], [B])
+#table(columns: 2, stroke: none, [A], [B])
@@ -0,0 +1,47 @@
+// Test drawing a separate border around each cell and open tables.
+
+---
+// Cells look like separate cards with a gutter.
+// Ref: false
+#table(
+  columns: 3,
+  gutter: 4pt,
+  stroke-mode: "cells",
+  [A], table.cell(colspan: 2)[B],
+  [D], [E], [F],
+)
+
+---
+// Without a gutter, shared edges are drawn twice.
+// Ref: false
+#table(
+  columns: 2,
+  stroke-mode: "cells",
+  [A], [B],
+)
+
+---
+// Only horizontal rules, with a lighter one below the header.
+// Ref: false
+#table(
+  columns: 3,
+  stroke-mode: "booktabs",
+  table.header[Name][Size][Age],
+  [Alice], [1.62m], [29],
+  [Bob], [1.80m], [35],
+)
+
+---
+// Without a header, only the top and bottom rules are drawn.
+// Ref: false
+#table(
+  columns: 2,
+  stroke: 2pt + blue,
+  stroke-mode: "booktabs",
+  [A], [B],
+  [C], [D],
+)
+
+---
+// Error: 21-27 expected "grid", "cells", or "booktabs"
+#table(stroke-mode: "card")
@@ -0,0 +1,125 @@
+// Test titles in a row of the table.
+
+---
+// The title spans all columns and is skipped by the fill.
+// Ref: false
+#show table.cell: it => [#metadata((it.fill, it.colspan, it.align)) <cell>]
+#table(
+  columns: 2,
+  fill: (x, y) => if y == 0 { red } else { blue },
+  table.title[Title],
+  [A], [B],
+  [C], [D],
+)
+#locate(loc => test(
+  query(<cell>, loc).map(it => it.value),
+  (
+    (none, 2, center),
+    (red, 1, auto), (red, 1, auto),
+    (blue, 1, auto), (blue, 1, auto),
+  ),
+))
+
+---
+// A title at the bottom comes after all other cells.
+// Ref: false
+#show table.cell: it => [#metadata(it.body) <cell>]
+#table(
+  columns: 2,
+  table.title(position: bottom, align: left)[Notes],
+  [A], [B],
+  [C],
+)
+#locate(loc => {
+  let bodies = query(<cell>, loc).map(it => it.value)
+  test(bodies.len(), 5)
+  test(bodies.slice(0, 3), ([A], [B], [C]))
+  test(bodies.last(), [Notes])
+})
+
+---
+// The caption of an enclosing figure takes precedence.
+// Ref: false
+#show table.cell: it => [#metadata(it.body) <cell>]
+#figure(
+  caption: [Figure],
+  table(
+    columns: 2,
+    table.title[Table],
+    [A], [B],
+  ),
+)
+#locate(loc => test(query(<cell>, loc).map(it => it.value), ([A], [B])))
+
+---
+// A top title repeats along with the header.
+// Ref: false
+#set page(height: 80pt)
+#table(
+  columns: 2,
+  table.title[*Numbers*],
+  table.header[*A*][*B*],
+  ..range(10).map(str),
+)
+
+---
+// A repeated title is replaced by its continued variant.
+// Ref: false
+#set page(height: 80pt)
+#table(
+  columns: 1,
+  inset: 2pt,
+  table.title(
+    continued: [#metadata("continued") <title>],
+  )[#metadata("first") <title>],
+  table.header[H],
+  ..range(12).map(str),
+)
+#locate(loc => {
+  let titles = query(<title>, loc)
+  test(titles.map(it => it.value), ("first", "continued", "continued"))
+  test(titles.map(it => it.location().page()), (1, 2, 3))
+})
+
+---
+// By default, the title is marked as continued in the text language.
+// Ref: false
+#set page(height: 80pt)
+#show "(continued)": it => [#metadata(it.text) <suffix>]
+#show "(Fortsetzung)": it => [#metadata(it.text) <suffix>]
+#let numbers = table(
+  columns: 1,
+  inset: 2pt,
+  table.title[Numbers],
+  table.header[H],
+  ..range(9).map(str),
+)
+#numbers
+#pagebreak()
+#text(lang: "de", numbers)
+#locate(loc => {
+  let suffixes = query(<suffix>, loc)
+  test(suffixes.map(it => it.value), ("(continued)",) * 2 + ("(Fortsetzung)",) * 2)
+  test(suffixes.map(it => it.location().page()), (2, 3, 5, 6))
+})
+
+---
+// With `none`, the title repeats unchanged.
+// Ref: false
+#set page(height: 80pt)
+#table(
+  columns: 1,
+  inset: 2pt,
+  table.title(continued: none)[#metadata("title") <title>],
+  table.header[H],
+  ..range(12).map(str),
+)
+#locate(loc => test(query(<title>, loc).map(it => it.value), ("title",) * 3))
+
+---
+// Error: 24-38 table cannot have more than one title
+#table(table.title[A], table.title[B])
+
+---
+// Error: 30-37 expected `top` or `bottom`
+#table(table.title(position: horizon)[A])
@@ -0,0 +1,38 @@
+// Test the default vertical alignment of table cells.
+
+---
+// Cells are centered in their row, unless they have a vertical alignment.
+// Ref: false
+#let mark = locate(loc => [#metadata(loc.position().y) <y>])
+#table(
+  columns: 4,
+  inset: 0pt,
+  valign: horizon,
+  box(height: 40pt),
+  mark,
+  table.cell(align: bottom, mark),
+  table.cell(align: left, mark),
+)
+
+#locate(loc => {
+  let (horizon, bottom, left) = query(<y>, loc).map(it => it.value)
+  test(horizon < bottom, true)
+  test(left, horizon)
+})
+
+---
+// Without it, cells stay at the top.
+// Ref: false
+#let mark = locate(loc => [#metadata(loc.position().y) <y>])
+#table(
+  columns: 3,
+  inset: 0pt,
+  box(height: 40pt),
+  mark,
+  table.cell(align: horizon, mark),
+)
+
+#locate(loc => {
+  let (top, horizon) = query(<y>, loc).map(it => it.value)
+  test(top < horizon, true)
+})
@@ -113,3 +113,18 @@
 ---
 // Error: 14-19 expected color, gradient, pattern, none, array, or function, found string
 #table(fill: "hey")
+
+---
+// A table with a caption is wrapped in a figure.
+// Ref: false
+#table(columns: 2, caption: [Planets], [Mercury], [Venus]) <planets>
+#figure(table[A], caption: [Explicit])
+#table[No caption]
+
+#locate(loc => {
+  let planets = query(<planets>, loc).first()
+  test(planets.func(), figure)
+  test(planets.kind, table)
+  test(planets.caption.body, [Planets])
+  test(query(figure.where(kind: table), loc).len(), 2)
+})
@@ -33,11 +33,35 @@ https://example.com/#(((nested))) \
 #[https://example.com/] \
 https://example.com/)
 
+---
+// Check that trailing punctuation is not part of automatic links, while
+// closing brackets that match an opening bracket in the URL are.
+// Ref: false
+#[
+  #show link: it => test(it.dest, "https://typst.app/")
+  See https://typst.app/. \
+  See https://typst.app/, or \
+  See https://typst.app/; or \
+  (See https://typst.app/) \
+  (See https://typst.app/).
+]
+#[
+  #show link: it => test(it.dest, "https://typst.app/(docs)")
+  See https://typst.app/(docs). \
+  (See https://typst.app/(docs)) \
+  (See https://typst.app/(docs);)
+]
+
 ---
 // Verify that opening brackets without closing brackets throw an error.
 // Error: 1-22 automatic links cannot contain unbalanced brackets, use the `link` function instead
 https://exam(ple.com/
 
+---
+// Verify that mismatched brackets throw an error.
+// Error: 1-24 automatic links cannot contain unbalanced brackets, use the `link` function instead
+https://typst.app/[docs)
+
 ---
 // Styled with underline and color.
 #show link: it => underline(text(fill: rgb("283663"), it))
@@ -57,6 +81,12 @@ My cool #box(move(dx: 0.7cm, dy: 0.7cm, rotate(10deg, scale(200%, mylink))))
   #box(move(dx: 10pt, image("/files/rhino.png", width: 1cm)))
 ])
 
+---
+// An image with transparent parts is clickable in its whole box, including
+// the space left by its fit.
+// Ref: false
+#link("https://example.com/", image("/files/rhino.png", width: 2cm, height: 1cm))
+
 ---
 // Link to page one.
 #link((page: 1, x: 10pt, y: 20pt))[Back to the start]
@@ -67,11 +97,481 @@ Text <hey>
 #link(<hey>)[Go to text.]
 
 ---
+// Ref: false
+// Warning: 2-20 label `<hey>` does not exist in the document
+// Hint: 2-20 the link's body is shown without a link
+#link(<hey>)[Nope.]
+
+---
+// Ref: false
+// Warning: 2-18 label `<hey>` does not exist in the document
+// Hint: 2-18 the link's body is shown without a link
+// Warning: 20-35 label `<ho>` does not exist in the document
+// Hint: 20-35 the link's body is shown without a link
+#link(<hey>)[One] #link(<ho>)[Two]
+
+---
+#set link(strict: true)
 // Error: 2-20 label `<hey>` does not exist in the document
 #link(<hey>)[Nope.]
 
 ---
+#set link(strict: true)
 Text <hey>
 Text <hey>
 // Error: 2-20 label `<hey>` occurs multiple times in the document
 #link(<hey>)[Nope.]
+
+---
+// Test resolving link targets to positions.
+// Ref: false
+= Introduction <intro>
+#locate(loc => test(link.resolve(<intro>, loc).page, 1))
+#locate(loc => test(
+  link.resolve((page: 1, x: 10pt, y: 5pt), loc).position,
+  (page: 1, x: 10pt, y: 5pt),
+))
+
+---
+// Error: 16-41 label `<nope>` does not exist in the document
+#locate(loc => link.resolve(<nope>, loc))
+
+---
+// Error: 16-54 cannot resolve a URL to a position
+#locate(loc => link.resolve("https://typst.app", loc))
+
+---
+// Links to labels without a body display the reference text.
+// Ref: false
+#set heading(numbering: "1.")
+= Introduction <intro>
+See #link(<intro>) or #link(<intro>)[the introduction].
+
+---
+// A custom supplement replaces the element's one, unless there is a body.
+// Ref: false
+#set heading(numbering: "1.")
+#show "Section": it => [#metadata(it.text) <default>]
+#show "§": it => [#metadata(it.text) <custom>]
+= Introduction <intro>
+#link(<intro>) \
+#link(<intro>, supplement: [§]) \
+#link(<intro>, supplement: [§])[the introduction] \
+#link(<intro>, supplement: none)
+#locate(loc => {
+  test(query(<default>, loc).len(), 1)
+  test(query(<custom>, loc).len(), 1)
+})
+
+---
+= Unnumbered <unnumbered>
+// Error: 2-20 cannot reference heading without numbering
+// Hint: 2-20 you can enable heading numbering with `#set heading(numbering: "1.")`
+#link(<unnumbered>)
+
+---
+// Page anchors resolve relative to the page they are used on.
+// Ref: false
+#set page(height: 100pt)
+#link("page-top")[Top]
+#pagebreak()
+#locate(loc => {
+  test(link.resolve("page-top", loc).position, (page: 2, x: 0pt, y: 0pt))
+  test(link.resolve("page-bottom", loc).position, (page: 2, x: 0pt, y: 100pt))
+})
+#link("page-bottom")[Bottom]
+
+---
+// Links to pages past the end of the document point to the last page.
+// Ref: false
+// Warning: 2-39 link points to page 5, but the document only has 1 page(s)
+// Hint: 2-39 the link will point to the last page instead
+#link((page: 5, x: 0pt, y: 0pt))[Far]
+#locate(loc => test(
+  // Warning: 3-48 link points to page 5, but the document only has 1 page(s)
+  // Hint: 3-48 the link will point to the last page instead
+  link.resolve((page: 5, x: 0pt, y: 0pt), loc).position,
+  (page: 1, x: 0pt, y: 0pt),
+))
+
+---
+// Listing the external links of the document.
+// Ref: false
+#set page(height: 60pt)
+= Intro <intro>
+#link("https://typst.app/")[Typst] \
+#link("mailto:hi@typst.app") \
+#link(<intro>)[Intro]
+#pagebreak()
+#link("https://typst.app")[Again] \
+#link("https://example.com/a")[Example]
+
+#locate(loc => {
+  let all = link.all(loc)
+  test(all.map(it => it.url), (
+    "https://typst.app/",
+    "mailto:hi@typst.app",
+    "https://example.com/a",
+  ))
+  test(all.map(it => it.pages), ((1, 2), (1,), (2,)))
+  test(all.first().body, [Typst])
+  test(
+    link.all(loc, by-domain: true).pairs().map(((k, v)) => (k, v.len())),
+    (("typst.app", 2), ("example.com", 1)),
+  )
+})
+
+---
+// Long URLs can break after their separators.
+// Ref: false
+#let url = "https://example.com/a/rather/long/path-to-a/page.html"
+#style(styles => {
+  let plain = measure(box(width: 60pt, link(url)), styles)
+  let broken = measure(box(width: 60pt, link(url, break-url: true)), styles)
+  test(broken.height > plain.height, true)
+})
+
+---
+// Hyphenation of the link's body can be enabled.
+// Ref: false
+#set text(hyphenate: true)
+#style(styles => {
+  let body = [extraordinarily]
+  let plain = measure(box(width: 30pt, link("https://a.b")[#body]), styles)
+  let hyph = measure(box(width: 30pt, link("https://a.b", hyphenate: auto)[#body]), styles)
+  test(hyph.height > plain.height, true)
+})
+
+---
+// User styles from show-set rules apply to the link's body.
+// Ref: false
+#style(styles => {
+  let plain = measure(link("https://a.b")[Go], styles)
+  [
+    #show link: set text(size: 20pt, weight: "bold")
+    #style(styles => {
+      let styled = measure(link("https://a.b")[Go], styles)
+      test(styled.height > plain.height, true)
+      test(styled.width > plain.width, true)
+    })
+  ]
+})
+
+---
+// The link's hyphenation takes precedence over a show-set rule.
+// Ref: false
+#set text(hyphenate: true)
+#show link: set text(hyphenate: true, fill: blue)
+#style(styles => {
+  let body = [extraordinarily]
+  let plain = measure(box(width: 30pt, link("https://a.b")[#body]), styles)
+  let hyph = measure(box(width: 30pt, link("https://a.b", hyphenate: auto)[#body]), styles)
+  test(hyph.height > plain.height, true)
+})
+
+---
+// Hyphenation set explicitly within the link's body is kept.
+// Ref: false
+#style(styles => {
+  let word = [extraordinarily]
+  let hyph = text(hyphenate: true, word)
+  let plain = measure(box(width: 30pt, link("https://a.b", word)), styles)
+  let nested = measure(box(width: 30pt, link("https://a.b")[Go #hyph]), styles)
+  let set = measure(box(width: 30pt, link("https://a.b")[
+    #set text(hyphenate: true)
+    Go #word
+  ]), styles)
+  test(nested, measure(box(width: 30pt)[Go #hyph], styles))
+  test(set, nested)
+  test(nested.height > plain.height, true)
+})
+
+---
+// Test building URLs with query parameters.
+// Ref: false
+#test(link.url("https://a.b/"), "https://a.b/")
+#test(
+  link.url("https://a.b/s", params: (q: "a b&c=d", n: 2, ok: true)),
+  "https://a.b/s?q=a%20b%26c%3Dd&n=2&ok=true",
+)
+#test(
+  link.url("https://a.b/?x=1#top", params: (tag: ("ä", "~b"))),
+  "https://a.b/?x=1&tag=%C3%A4&tag=~b#top",
+)
+
+---
+// Error: 2-43 expected string, number or boolean for parameter `q`, found content
+#link.url("https://a.b", params: (q: [a]))
+
+---
+// Links within the document can lead above their target.
+// Ref: false
+#set link(offset: 2em)
+#link(<target>)[Up] \
+#link((page: 1, x: 0pt, y: 10pt))[Top]
+
+#v(20pt)
+= Target <target>
+
+---
+// An indicator is appended to links to URLs only.
+// Ref: false
+#style(styles => {
+  let plain = measure(link("https://a.b")[Typst], styles)
+  let marked = measure(link("https://a.b", indicator: sym.arrow.tr)[Typst], styles)
+  test(marked.width > plain.width, true)
+  test(marked.height, plain.height)
+})
+
+#set link(indicator: sym.arrow.tr)
+= Target <indicated>
+#style(styles => {
+  let internal = measure(link(<indicated>)[Typst], styles)
+  test(internal.width, measure([Typst], styles).width)
+})
+
+---
+// Relative URLs and bare fragments are kept as they are.
+// Ref: false
+= Team <team>
+#link("about.html#team")[About us] \
+#link("#team")[Our team]
+
+---
+// Ref: false
+// Warning: 2-24 no label `<nowhere>` matches the fragment of this link
+// Hint: 2-24 the link leads nowhere in PDF
+#link("#nowhere")[Gone]
+
+---
+// Hit padding doesn't move or resize the visible link.
+// Ref: false
+#style(styles => {
+  let plain = measure(link("https://a.b")[Typst], styles)
+  let padded = measure(link("https://a.b", hit-padding: 5pt)[Typst], styles)
+  test(padded, plain)
+})
+
+#set link(hit-padding: 3pt)
+#link("https://a.b")[A]#link("https://c.d")[B]
+
+---
+// Heading anchors resolve relative to the given location.
+// Ref: false
+= One <one>
+#locate(loc => {
+  test(link.resolve("prev-heading", loc), link.resolve(<one>, loc))
+  test(link.resolve("next-heading", loc), link.resolve(<two>, loc))
+})
+== Two <two>
+#link("prev-heading", level: 1) \
+#link("next-heading")[Skip ahead]
+= Three
+
+---
+// Ref: false
+// Warning: 2-39 there is no next heading
+// Hint: 2-39 the link's body is shown without a link
+#link("next-heading")[Nothing follows]
+
+---
+// Ref: false
+= Chapter
+// Warning: 2-38 there is no previous heading of level 2
+// Hint: 2-38 the link's body is shown without a link
+#link("prev-heading", level: 2)[Back]
+
+---
+// Error: 30-31 number must be positive
+#link("next-heading", level: 0)
+
+---
+// Error: 19-27 unexpected argument: level
+#link("page-top", level: 1)[Top]
+
+---
+// Links can go to the figures nested in a labelled figure.
+// Ref: false
+#show figure.where(kind: "sub"): set figure(numbering: "a", supplement: [Part])
+#let sub(body) = figure(body, kind: "sub", caption: body)
+#figure(
+  grid(columns: 2, sub[A], sub[B]),
+  caption: [Both],
+) <main>
+#link(<main>, sub: "b") \
+#link(<main>, sub: "1")[First part]
+// Warning: 2-27 figure `<main>` has no subfigure `c`, it has 2
+// Hint: 2-27 the link's body is shown without a link
+#link(<main>, sub: "c")[C]
+// Warning: 2-28 invalid subfigure `bb`, expected a letter or a number
+// Hint: 2-28 the link's body is shown without a link
+#link(<main>, sub: "bb")[B]
+
+---
+// Ref: false
+// Warning: 2-28 figure `<plain>` has no subfigures
+// Hint: 2-28 the link's body is shown without a link
+#link(<plain>, sub: "a")[A]
+#figure(rect[Plain], caption: [Plain]) <plain>
+
+---
+// Ref: false
+= Heading <intro>
+// Warning: 2-28 label `<intro>` does not refer to a figure
+// Hint: 2-28 the link's body is shown without a link
+#link(<intro>, sub: "a")[A]
+
+---
+// Test resolving a label to its number, page and position.
+// Ref: false
+#set math.equation(numbering: "(1)")
+$ E = m c^2 $ <energy>
+
+#locate(loc => {
+  let target = link.resolve(<energy>, loc)
+  let elem = query(<energy>, loc).first()
+  test(target.number, [#numbering("(1)", 1)])
+  test(target.page, 1)
+  test(target.position, elem.location().position())
+})
+
+---
+// Error: 18-43 label `<nope>` does not exist in the document
+#locate(loc => [#link.resolve(<nope>, loc)])
+
+---
+// Targets other than labels of numbered elements have no number.
+// Ref: false
+= Intro <intro>
+#locate(loc => {
+  test(link.resolve(<intro>, loc).number, none)
+  test(link.resolve("page-top", loc).number, none)
+  test(link.resolve("page-top", loc).page, 1)
+})
+
+---
+// Test rewriting URLs, which leaves the body as is.
+// Ref: false
+#let transform(url) = {
+  test(url, "https://a.b")
+  "https://staging.a.b/a/much/longer/path"
+}
+#style(styles => test(
+  measure(link("https://a.b"), styles),
+  measure({
+    set link(transform: transform)
+    link("https://a.b")
+  }, styles),
+))
+
+---
+// Links within the document aren't rewritten.
+// Ref: false
+#set link(transform: url => panic("rewritten"))
+= Intro <intro>
+#link(<intro>)[Intro]
+
+---
+// Error: 22-30 expected string, found integer
+#set link(transform: url => 1)
+#link("https://a.b")
+
+---
+// Disabled links show their body as is.
+// Ref: false
+#set link(enabled: false)
+#link("https://typst.app")[Typst] \
+#link("https://typst.app") \
+= Intro <intro>
+#link(<intro>)[Intro]
+
+---
+// Disabled links don't override the text's hyphenation.
+// Ref: false
+#set text(hyphenate: true)
+#style(styles => test(
+  measure(block(width: 40pt, link("https://a.b", enabled: false)[extraordinarily]), styles),
+  measure(block(width: 40pt)[extraordinarily], styles),
+))
+
+---
+// Disabled links to URLs aren't rewritten.
+// Ref: false
+#set link(enabled: false, transform: url => panic("rewritten"))
+#link("https://a.b")
+
+---
+// Disabled links don't resolve their target, so a missing label is fine.
+// Ref: false
+#set link(enabled: false, strict: true)
+#link(<missing>)[Missing] \
+#link("#nowhere")[Fragment]
+
+---
+// Only links to URLs with allowed schemes lead anywhere.
+// Ref: false
+// Warning: 2-36 links to `javascript:` URLs are not allowed
+// Hint: 2-36 the link's body is shown without a link
+#link("javascript:alert(1)")[Click] \
+#link("HTTPS://typst.app")[Typst] \
+#link("about.html")[About]
+
+---
+// Link to pages by their displayed page number.
+// Ref: false
+#set page(height: 40pt, numbering: "i")
+#let at(page) = (page: page, x: 0pt, y: 5pt, page-numbering: "display")
+#link(at("ii"))[Preface]
+#pagebreak()
+Preface
+#set page(numbering: "1")
+#counter(page).update(1)
+#link(at(2))[Two]
+#pagebreak()
+#locate(loc => {
+  test(link.resolve(at("ii"), loc).position, (page: 2, x: 0pt, y: 5pt))
+  test(link.resolve(at(1), loc).position, (page: 3, x: 0pt, y: 5pt))
+  test(link.resolve(at("2"), loc).position, (page: 4, x: 0pt, y: 5pt))
+  test(
+    link.resolve((page: 2, x: 0pt, y: 5pt, page-numbering: "physical"), loc).position,
+    (page: 2, x: 0pt, y: 5pt),
+  )
+})
+
+---
+// Error: 18-92 there is no page numbered `iv`
+#locate(loc => [#link.resolve((page: "iv", x: 0pt, y: 0pt, page-numbering: "display"), loc)])
+
+---
+// The kind of destination can be told apart in queries.
+// Ref: false
+= Intro <intro>
+#link("https://typst.app")[Typst]
+#link(<intro>)[Intro]
+#link((page: 1, x: 0pt, y: 0pt))[Top]
+#link("prev-heading", level: 1)[Back]
+#locate(loc => {
+  let targets = query(link, loc).map(it => it.target)
+  test(targets.at(0), (kind: "url", value: "https://typst.app"))
+  test(targets.at(1), (kind: "label", value: <intro>))
+  test(targets.at(2), (kind: "position", value: (page: 1, x: 0pt, y: 0pt)))
+  test(targets.at(3), (kind: "heading", value: "prev-heading", level: 1))
+  test(targets.map(it => it.value), query(link, loc).map(it => it.dest))
+})
+
+---
+// Aliases are followed through to the label they finally lead to.
+// Ref: false
+#set heading(numbering: "1.")
+#set link(aliases: (draft: <old>))
+#set link(aliases: (old: <intro>))
+#show "Target": it => [#metadata(it.text) <target>]
+= Preface
+#heading(supplement: [Target])[Introduction] <intro>
+See #link(<draft>).
+#locate(loc => test(query(<target>, loc).len(), 1))
+
+---
+#set link(aliases: (a: <b>, b: <a>))
+// Error: 2-14 label aliases form a cycle: <a> -> <b> -> <a>
+#link(<a>)[A]